@@ -0,0 +1,33 @@
+// Build script - Guard the embedded compromised-packages snapshot
+// Purpose: Warn when data/compromised-packages.txt is not the full upstream list written
+//          by update_snapshot.sh. The build still succeeds (cargo install must work from
+//          any checkout); release.sh refuses to release without a fresh snapshot.
+
+use std::fs;
+
+const SNAPSHOT: &str = "data/compromised-packages.txt";
+
+/// Fewer entries than this means the file is not the upstream list (600+ packages)
+/// Keep in sync with MIN_ENTRIES in update_snapshot.sh
+const MIN_SNAPSHOT_ENTRIES: usize = 500;
+
+fn main() {
+    println!("cargo:rerun-if-changed={SNAPSHOT}");
+
+    let entries = fs::read_to_string(SNAPSHOT).map(|content| {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .count()
+    });
+    let problem = match entries {
+        Ok(entries) if entries >= MIN_SNAPSHOT_ENTRIES => return,
+        Ok(entries) => format!(
+            "{SNAPSHOT} holds only {entries} packages (expected at least {MIN_SNAPSHOT_ENTRIES})"
+        ),
+        Err(e) => format!("{SNAPSHOT} is missing: {e}"),
+    };
+
+    println!("cargo:warning={problem} - run ./update_snapshot.sh before a release build");
+}
@@ -34,6 +34,13 @@ if git rev-parse "$TAG" >/dev/null 2>&1; then
     fi
 fi
 
+echo ""
+echo "0️⃣ Refreshing embedded compromised-packages snapshot..."
+if ! ./update_snapshot.sh; then
+    echo "❌ Snapshot refresh failed - aborting release (offline scans need the full list)"
+    exit 1
+fi
+
 echo ""
 echo "1️⃣ Checking git status..."
 STATUS=$(git status --short)
@@ -0,0 +1,189 @@
+// Baseline - Known findings from a previous scan
+// Purpose: Report (and exit) only on findings that are not in a baseline file
+//
+// A baseline is the ScanResults JSON file written by `Baseline::write()` (--write-baseline).
+// scan_results.json does not work: it keeps absolute paths and truncates the LOW and
+// paranoid lists. Findings are fingerprinted by category + normalized path + message, where
+// paths are made relative to the scanned directory so baselines survive moving
+// the checkout (CI runners, other developer machines).
+
+use crate::detectors::{Finding, ScanResults};
+use crate::utils::{normalize_path, relative_path};
+use anyhow::{Context, Result};
+use sha2::{Digest, Sha256};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+/// Findings loaded from a baseline file
+#[derive(Debug, Clone, Default)]
+pub struct Baseline {
+    findings: Vec<Finding>,
+}
+
+// Function: fingerprint
+// Purpose: Stable identity of a finding across scans
+// Args: finding - finding to fingerprint, scan_dir - scanned directory
+// Returns: SHA-256 hex of category + normalized path + message
+pub fn fingerprint(finding: &Finding, scan_dir: &Path) -> String {
+    let path = relative_path(&finding.file_path, scan_dir);
+
+    // Messages may embed absolute paths (e.g. "found in /repo/package.json")
+    let root = normalize_path(scan_dir);
+    let root_prefix = format!("{}/", root.trim_end_matches('/'));
+    let message = finding.message.replace(&root_prefix, "");
+
+    let mut hasher = Sha256::new();
+    hasher.update(finding.category.as_bytes());
+    hasher.update([0]);
+    hasher.update(path.as_bytes());
+    hasher.update([0]);
+    hasher.update(message.as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+impl Baseline {
+    // Function: load
+    // Purpose: Load a baseline from a ScanResults JSON file
+    // Args: path - baseline file written by write()
+    // Returns: Baseline or error if the file is missing or not ScanResults JSON
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read baseline {}", path.display()))?;
+        let results: ScanResults = serde_json::from_str(&content)
+            .with_context(|| format!("Invalid baseline file {}", path.display()))?;
+        Ok(Self::from_results(&results))
+    }
+
+    pub fn from_results(results: &ScanResults) -> Self {
+        Self {
+            findings: results.all_findings().cloned().collect(),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.findings.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    // Function: apply
+    // Purpose: Remove findings that are already in the baseline
+    // Args: results - current scan results (modified in place), scan_dir - scanned directory
+    // Returns: Number of known (baselined) findings removed
+    pub fn apply(&self, results: &mut ScanResults, scan_dir: &Path) -> usize {
+        let known: HashSet<String> = self
+            .findings
+            .iter()
+            .map(|f| fingerprint(f, scan_dir))
+            .collect();
+
+        // Namespace warnings hidden by the bash "too many issues" rule have no
+        // itemized findings to compare against the baseline - drop their count
+        results.suppressed_namespace_count = 0;
+
+        results
+            .retain_findings(|f| !known.contains(&fingerprint(f, scan_dir)))
+            .len()
+    }
+
+    // Function: write
+    // Purpose: Write (or refresh) a baseline file from the current scan results
+    // Args: path - baseline file, results - full scan results, scan_dir - scanned directory
+    // Returns: Result indicating success
+    // NOTE: Paths are stored relative to scan_dir so the baseline is portable
+    pub fn write(path: &Path, results: &ScanResults, scan_dir: &Path) -> Result<()> {
+        let mut portable = results.clone();
+        portable.for_each_finding_mut(|f| {
+            f.file_path = relative_path(&f.file_path, scan_dir).into();
+        });
+
+        let json = serde_json::to_string_pretty(&portable)?;
+        fs::write(path, json)
+            .with_context(|| format!("Failed to write baseline {}", path.display()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::RiskLevel;
+    use std::path::PathBuf;
+    use tempfile::TempDir;
+
+    fn finding(path: &str, message: &str) -> Finding {
+        Finding::new(
+            PathBuf::from(path),
+            message.to_string(),
+            RiskLevel::Medium,
+            "network_exfiltration",
+        )
+    }
+
+    #[test]
+    fn test_fingerprint_ignores_scan_root() {
+        let a = finding("/ci/build/repo/src/app.js", "Hardcoded IP 10.0.0.1");
+        let b = finding("/home/dev/repo/src/app.js", "Hardcoded IP 10.0.0.1");
+        assert_eq!(
+            fingerprint(&a, Path::new("/ci/build/repo")),
+            fingerprint(&b, Path::new("/home/dev/repo"))
+        );
+
+        let c = finding("/home/dev/repo/src/app.js", "Hardcoded IP 10.0.0.2");
+        assert_ne!(
+            fingerprint(&b, Path::new("/home/dev/repo")),
+            fingerprint(&c, Path::new("/home/dev/repo"))
+        );
+    }
+
+    #[test]
+    fn test_apply_keeps_only_new_findings() {
+        let scan_dir = Path::new("/repo");
+        let mut previous = ScanResults::new();
+        previous
+            .network_exfiltration_warnings
+            .push(finding("/repo/a.js", "known"));
+        let baseline = Baseline::from_results(&previous);
+
+        let mut current = ScanResults::new();
+        current
+            .network_exfiltration_warnings
+            .push(finding("/repo/a.js", "known"));
+        current
+            .network_exfiltration_warnings
+            .push(finding("/repo/b.js", "new"));
+
+        assert_eq!(baseline.apply(&mut current, scan_dir), 1);
+        assert_eq!(current.network_exfiltration_warnings.len(), 1);
+        assert_eq!(current.network_exfiltration_warnings[0].message, "new");
+    }
+
+    #[test]
+    fn test_write_and_load_roundtrip() {
+        let temp = TempDir::new().unwrap();
+        let scan_dir = Path::new("/repo");
+        let baseline_file = temp.path().join("baseline.json");
+
+        let mut results = ScanResults::new();
+        results
+            .crypto_patterns
+            .push(finding("/repo/lib/x.js", "atob"));
+        Baseline::write(&baseline_file, &results, scan_dir).unwrap();
+
+        let content = fs::read_to_string(&baseline_file).unwrap();
+        assert!(content.contains("\"lib/x.js\""));
+
+        let baseline = Baseline::load(&baseline_file).unwrap();
+        assert_eq!(baseline.len(), 1);
+
+        // Same finding scanned from a different checkout location is known
+        let mut moved = ScanResults::new();
+        moved
+            .crypto_patterns
+            .push(finding("/other/place/lib/x.js", "atob"));
+        assert_eq!(baseline.apply(&mut moved, Path::new("/other/place")), 1);
+        assert!(moved.crypto_patterns.is_empty());
+    }
+}
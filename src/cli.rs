@@ -2,16 +2,23 @@
 // Corresponds to bash argument parsing in main()
 
 use anyhow::{bail, Result};
-use clap::Parser;
-use std::path::PathBuf;
+use clap::{Parser, Subcommand, ValueEnum};
+use shai_hulud_detector::feeds::{self, FeedOptions, FeedSource};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 #[derive(Parser, Debug)]
 #[command(name = "shai-hulud-detector")]
 #[command(version = "3.0.5")]
 #[command(about = "Shai-Hulud NPM Supply Chain Attack Detector", long_about = None)]
+#[command(args_conflicts_with_subcommands = true, subcommand_negates_reqs = true)]
 pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
     /// Directory to scan for indicators of compromise
-    pub scan_dir: PathBuf,
+    #[arg(required = true)]
+    pub scan_dir: Option<PathBuf>,
 
     /// Enable additional security checks (typosquatting, network patterns)
     /// These are general security features, not specific to Shai-Hulud
@@ -27,16 +34,138 @@ pub struct Cli {
     #[arg(long, default_value = "4")]
     pub parallelism: usize,
 
-    /// Check if package.json semver ranges (^, ~) could resolve to
+    /// Check if package.json semver ranges (^, ~, x-ranges, comparators,
+    /// hyphen ranges) and dist-tags could resolve to
     /// compromised versions. Reports LOW risk (informational) since
     /// packages are largely unpublished from npm.
     #[arg(long)]
     pub check_semver_ranges: bool,
 
+    /// Inventory installed packages: read name/version of every package in
+    /// node_modules (nested copies, pnpm .pnpm store, Yarn cache zips) and report
+    /// each installed compromised version with its path. Needs no package manager.
+    #[arg(long)]
+    pub inventory: bool,
+
+    /// Also scan the package manager caches of the current user (~/.npm/_cacache,
+    /// the pnpm store, ~/.cache/yarn, ~/.yarn/berry/cache) for compromised packages
+    /// and malicious files; findings name the cache entry to purge
+    #[arg(long)]
+    pub scan_caches: bool,
+
+    /// Decode binary bun.lockb lockfiles by running the bun CLI on them (from an
+    /// empty temporary directory). Off by default: a scanner should not execute
+    /// programs against untrusted trees, so bun.lockb files are reported as not verified
+    #[arg(long)]
+    pub decode_bun_lockb: bool,
+
     /// Save all detected file paths to FILE, grouped by severity.
     /// Output format: # HIGH / # MEDIUM / # LOW headers with file paths
     #[arg(long)]
     pub save_log: Option<PathBuf>,
+
+    /// Only run the listed detectors (comma-separated detector ids,
+    /// e.g. --only packages,malicious_hashes). Naming semver_ranges, installed_packages
+    /// or package_caches implies --check-semver-ranges, --inventory or --scan-caches
+    #[arg(long, value_delimiter = ',')]
+    pub only: Vec<String>,
+
+    /// Skip the listed detectors (comma-separated detector ids)
+    #[arg(long, value_delimiter = ',')]
+    pub skip: Vec<String>,
+
+    /// Write JSON results to PATH instead of ./scan_results.json.
+    /// Use '-' to write JSON to stdout (human-readable output goes to stderr)
+    #[arg(long, value_name = "PATH")]
+    pub json_out: Option<PathBuf>,
+
+    /// Output format. 'json' writes JSON results to stdout (unless --json-out
+    /// is given) and sends all human-readable output to stderr
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// Also write results as SARIF 2.1.0 to PATH (for GitHub code scanning)
+    #[arg(long, value_name = "PATH")]
+    pub sarif_out: Option<PathBuf>,
+
+    /// Only report (and exit non-zero on) findings that are not in FILE, written by
+    /// --write-baseline (scan_results.json holds absolute paths and truncated lists)
+    #[arg(long, value_name = "FILE")]
+    pub baseline: Option<PathBuf>,
+
+    /// Write all current findings to FILE as the new baseline
+    /// (combine with --baseline FILE to refresh an existing baseline)
+    #[arg(long, value_name = "FILE")]
+    pub write_baseline: Option<PathBuf>,
+
+    /// Suppression file with reviewed exceptions
+    /// (default: .shai-hulud-ignore.toml in the scanned directory, if present)
+    #[arg(long, value_name = "FILE")]
+    pub ignore_file: Option<PathBuf>,
+
+    /// Never access the network: use --feed PATH, the local cache or the embedded list
+    #[arg(long, global = true)]
+    pub offline: bool,
+
+    /// Compromised package feed to use instead of the upstream GitHub list
+    /// (https:// URL or local file in compromised-packages.txt format)
+    #[arg(long, global = true, value_name = "PATH_OR_URL")]
+    pub feed: Option<FeedSource>,
+
+    /// IOC data (malicious hashes, namespaces, verified files) to use instead of
+    /// the upstream GitHub data/iocs.toml (https:// URL or local file)
+    #[arg(long, global = true, value_name = "PATH_OR_URL")]
+    pub ioc_feed: Option<FeedSource>,
+
+    /// Additional IOC file (data/iocs.toml format) merged into the loaded lists (repeatable)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub extra_iocs: Vec<PathBuf>,
+
+    /// Feed cache directory (default: $SHAI_HULUD_CACHE_DIR or
+    /// $XDG_CACHE_HOME/shai-hulud-detector)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub cache_dir: Option<PathBuf>,
+
+    /// Use the cached feed without re-fetching while it is younger than this
+    /// (e.g. 900, 30m, 6h, 1d; 0 = always revalidate with ETag/Last-Modified)
+    #[arg(long, global = true, value_name = "DURATION", default_value = "0", value_parser = feeds::parse_ttl)]
+    pub feed_ttl: Duration,
+
+    /// Fail the scan if the compromised package feed is not current (download,
+    /// revalidated or --feed-ttl cache, or --feed file) instead of falling back to a
+    /// stale cache, local copy or the embedded snapshot
+    #[arg(long)]
+    pub require_fresh_feed: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Download IOC feeds into the local cache without scanning
+    /// (then scan with --offline for deterministic results)
+    UpdateFeeds,
+
+    /// Pin compromised packages to the nearest safe version with npm `overrides`,
+    /// yarn `resolutions` or `pnpm.overrides` in each affected package.json.
+    /// Shows a diff; nothing is written without --apply
+    Fix {
+        /// Project directory to fix
+        dir: PathBuf,
+
+        /// Only show the diff (default)
+        #[arg(long, conflicts_with = "apply")]
+        dry_run: bool,
+
+        /// Write the changes to package.json
+        #[arg(long)]
+        apply: bool,
+    },
+}
+
+/// Output format selected with --format
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OutputFormat {
+    Text,
+    Json,
 }
 
 impl Cli {
@@ -45,20 +174,153 @@ impl Cli {
     // Args: self
     // Returns: Result indicating if arguments are valid
     pub fn validate(&mut self) -> Result<()> {
-        if !self.scan_dir.exists() {
-            bail!(
-                "Error: Directory '{}' does not exist.",
-                self.scan_dir.display()
-            );
+        self.feed_options().validate()?;
+        if matches!(self.command, Some(Command::UpdateFeeds)) && self.offline {
+            bail!("Error: update-feeds needs network access and cannot be used with --offline");
+        }
+
+        if let Some(Command::Fix { ref mut dir, .. }) = self.command {
+            if !dir.is_dir() {
+                bail!("Error: '{}' is not a directory.", dir.display());
+            }
+            *dir = dir.canonicalize()?;
+        }
+
+        // Subcommands don't scan a directory
+        let Some(ref scan_dir) = self.scan_dir else {
+            return Ok(());
+        };
+
+        if !scan_dir.exists() {
+            bail!("Error: Directory '{}' does not exist.", scan_dir.display());
         }
 
-        if !self.scan_dir.is_dir() {
-            bail!("Error: '{}' is not a directory.", self.scan_dir.display());
+        if !scan_dir.is_dir() {
+            bail!("Error: '{}' is not a directory.", scan_dir.display());
         }
 
         // Convert to absolute path for bash-identical output
-        self.scan_dir = self.scan_dir.canonicalize()?;
+        self.scan_dir = Some(scan_dir.canonicalize()?);
 
         Ok(())
     }
+
+    // Function: feed_options
+    // Purpose: Collect --offline / --feed / --ioc-feed / --extra-iocs / --cache-dir / --feed-ttl / --require-fresh-feed into FeedOptions
+    // Args: self
+    // Returns: FeedOptions for data loading and update-feeds
+    pub fn feed_options(&self) -> FeedOptions {
+        FeedOptions {
+            offline: self.offline,
+            source: self.feed.clone(),
+            ioc_source: self.ioc_feed.clone(),
+            extra_iocs: self.extra_iocs.clone(),
+            cache_dir: self.cache_dir.clone(),
+            ttl: self.feed_ttl,
+            require_fresh: self.require_fresh_feed,
+        }
+    }
+
+    // Function: json_output_path
+    // Purpose: Resolve where JSON results are written
+    // Args: self
+    // Returns: --json-out value, "-" for --format json, otherwise scan_results.json in cwd
+    pub fn json_output_path(&self) -> &Path {
+        match (&self.json_out, self.format) {
+            (Some(path), _) => path,
+            (None, OutputFormat::Json) => Path::new("-"),
+            (None, OutputFormat::Text) => Path::new("scan_results.json"),
+        }
+    }
+
+    // Function: json_to_stdout
+    // Purpose: Check if stdout is reserved for JSON output
+    // Args: self
+    // Returns: true if JSON goes to stdout
+    pub fn json_to_stdout(&self) -> bool {
+        self.json_output_path() == Path::new("-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_json_output_destination() {
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "."]).unwrap();
+        assert_eq!(cli.json_output_path(), Path::new("scan_results.json"));
+        assert!(!cli.json_to_stdout());
+
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "--format", "json", "."]).unwrap();
+        assert!(cli.json_to_stdout());
+
+        let cli = Cli::try_parse_from([
+            "shai-hulud-detector",
+            "--format",
+            "json",
+            "--json-out",
+            "out.json",
+            ".",
+        ])
+        .unwrap();
+        assert_eq!(cli.json_output_path(), Path::new("out.json"));
+
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "--json-out", "-", "."]).unwrap();
+        assert!(cli.json_to_stdout());
+    }
+
+    #[test]
+    fn test_update_feeds_subcommand() {
+        let cli = Cli::try_parse_from([
+            "shai-hulud-detector",
+            "update-feeds",
+            "--feed",
+            "https://example.com/feed.txt",
+        ])
+        .unwrap();
+        assert!(matches!(cli.command, Some(Command::UpdateFeeds)));
+        assert!(cli.scan_dir.is_none());
+        assert_eq!(
+            cli.feed,
+            Some(FeedSource::Url("https://example.com/feed.txt".to_string()))
+        );
+
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "--offline", "."]).unwrap();
+        assert!(cli.command.is_none());
+        assert!(cli.feed_options().offline);
+
+        assert!(Cli::try_parse_from(["shai-hulud-detector"]).is_err());
+    }
+
+    #[test]
+    fn test_fix_subcommand() {
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "fix", "--apply", "app"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Fix { ref dir, dry_run: false, apply: true }) if dir == Path::new("app")
+        ));
+
+        let cli = Cli::try_parse_from(["shai-hulud-detector", "fix", "app"]).unwrap();
+        assert!(matches!(
+            cli.command,
+            Some(Command::Fix { apply: false, .. })
+        ));
+
+        assert!(
+            Cli::try_parse_from(["shai-hulud-detector", "fix", "--dry-run", "--apply", "app"])
+                .is_err()
+        );
+
+        // Feed flags apply to the subcommand as well
+        let cli = Cli::try_parse_from([
+            "shai-hulud-detector",
+            "fix",
+            "--extra-iocs",
+            "extra.toml",
+            "app",
+        ])
+        .unwrap();
+        assert_eq!(cli.extra_iocs, vec![PathBuf::from("extra.toml")]);
+    }
 }
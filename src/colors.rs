@@ -2,6 +2,58 @@
 // Corresponds to bash color codes and print_status function
 
 use colored::Colorize;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+// Human-readable output goes to stderr when stdout is reserved for JSON (--format json / --json-out -)
+static HUMAN_OUTPUT_TO_STDERR: AtomicBool = AtomicBool::new(false);
+
+// Function: set_human_output_to_stderr
+// Purpose: Route all human-readable output (status, report, timing) to stderr
+// Args: enabled - true to write human output to stderr instead of stdout
+// Returns: None
+pub fn set_human_output_to_stderr(enabled: bool) {
+    HUMAN_OUTPUT_TO_STDERR.store(enabled, Ordering::Relaxed);
+}
+
+pub fn human_output_to_stderr() -> bool {
+    HUMAN_OUTPUT_TO_STDERR.load(Ordering::Relaxed)
+}
+
+// Library scans print nothing unless ScanOptions::quiet is false (see scanner::scan)
+static QUIET: AtomicBool = AtomicBool::new(false);
+
+// Function: set_quiet
+// Purpose: Suppress all human-readable output (status lines, progress, report)
+// Args: quiet - true to print nothing
+// Returns: Previous setting
+pub fn set_quiet(quiet: bool) -> bool {
+    QUIET.swap(quiet, Ordering::Relaxed)
+}
+
+pub fn quiet() -> bool {
+    QUIET.load(Ordering::Relaxed)
+}
+
+/// `println!` for human-readable output - honors `set_human_output_to_stderr()` and `set_quiet()`
+#[macro_export]
+macro_rules! human_println {
+    () => {
+        if $crate::colors::quiet() {
+        } else if $crate::colors::human_output_to_stderr() {
+            eprintln!()
+        } else {
+            println!()
+        }
+    };
+    ($($arg:tt)*) => {
+        if $crate::colors::quiet() {
+        } else if $crate::colors::human_output_to_stderr() {
+            eprintln!($($arg)*)
+        } else {
+            println!($($arg)*)
+        }
+    };
+}
 
 #[derive(Copy, Clone)]
 pub enum Color {
@@ -14,7 +66,7 @@ pub enum Color {
 // Function: print_status
 // Purpose: Print colored status messages to console
 // Args: color - color code, message - message text
-// Modifies: None (outputs to stdout)
+// Modifies: None (outputs to stdout, or stderr in JSON output mode)
 // Returns: Prints colored message
 // NOTE: Adds emoji prefix for HIGH RISK (🚨) and MEDIUM RISK (⚠️) to match bash output
 pub fn print_status(color: Color, message: &str) {
@@ -33,5 +85,5 @@ pub fn print_status(color: Color, message: &str) {
         Color::Green => formatted_msg.green(),
         Color::Blue => formatted_msg.blue(),
     };
-    println!("{colored_msg}");
+    human_println!("{colored_msg}");
 }
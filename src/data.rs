@@ -1,135 +1,138 @@
 // Data loading and storage
 // Corresponds to bash arrays and load_compromised_packages()
 
-use anyhow::{Context, Result};
+use crate::feeds::{
+    self, FeedMetadata, FeedOptions, FeedOrigin, FeedSource, FeedSpec, FetchStatus,
+};
+use crate::osv::{self, Advisories};
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
 
-// Known malicious file hashes (source: https://socket.dev/blog/ongoing-supply-chain-attack-targets-crowdstrike-npm-packages)
-// Corresponds to MALICIOUS_HASHLIST bash array
-pub const MALICIOUS_HASHLIST: &[&str] = &[
-    "de0e25a3e6c1e1e5998b306b7141b3dc4c0088da9d7bb47c1c00c91e6e4f85d6",
-    "81d2a004a1bca6ef87a1caf7d0e0b355ad1764238e40ff6d1b1cb77ad4f595c3",
-    "83a650ce44b2a9854802a7fb4c202877815274c129af49e6c2d1d5d5d55c501e",
-    "4b2399646573bb737c4969563303d8ee2e9ddbd1b271f1ca9e35ea78062538db",
-    "dc67467a39b70d1cd4c1f7f7a459b35058163592f4a9e8fb4dffcbba98ef210c",
-    "46faab8ab153fae6e80e7cca38eab363075bb524edd79e42269217a083628f09",
-    "b74caeaa75e077c99f7d44f46daaf9796a3be43ecf24f2a1fd381844669da777",
-    "86532ed94c5804e1ca32fa67257e1bb9de628e3e48a1f56e67042dc055effb5b", // test-cases/multi-hash-detection/file1.js
-    "aba1fcbd15c6ba6d9b96e34cec287660fff4a31632bf76f2a766c499f55ca1ee", // test-cases/multi-hash-detection/file2.js
-];
-
-// Known compromised namespaces - packages in these namespaces may be compromised
-// Corresponds to COMPROMISED_NAMESPACES bash array
-pub const COMPROMISED_NAMESPACES: &[&str] = &[
-    "@crowdstrike",
-    "@art-ws",
-    "@ngx",
-    "@ctrl",
-    "@nativescript-community",
-    "@ahmedhfarag",
-    "@operato",
-    "@teselagen",
-    "@things-factory",
-    "@hestjs",
-    "@nstudio",
-    "@basic-ui-components-stc",
-    "@nexe",
-    "@thangved",
-    "@tnf-dev",
-    "@ui-ux-gang",
-    "@yoobic",
-];
+/// Compiled-in IOC lists (data/iocs.toml), used when no fetched or cached IOC file is available
+/// Corresponds to MALICIOUS_HASHLIST / COMPROMISED_NAMESPACES bash arrays
+pub const BUILTIN_IOCS: &str = include_str!("../data/iocs.toml");
+
+/// Compromised-packages snapshot compiled into the binary (refresh with update_snapshot.sh),
+/// the last-resort fallback when no feed, cache or local file is available
+pub const EMBEDDED_SNAPSHOT: &str = include_str!("../data/compromised-packages.txt");
+
+/// Newest IOC data file format this scanner understands
+pub const IOC_FORMAT_VERSION: u32 = 1;
 
 // AI-Reviewed verified files - files manually reviewed and deemed safe
 // Each entry contains SHA-256 hash, file path, and review metadata
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+#[serde(deny_unknown_fields)]
 pub struct VerifiedFile {
-    pub hash: &'static str,
+    pub hash: String,
     #[allow(dead_code)]
-    pub path: &'static str, // Relative path from node_modules (e.g., "vue-demi/scripts/postinstall.js")
+    pub path: String, // Relative path from node_modules (e.g., "vue-demi/scripts/postinstall.js")
     #[allow(dead_code)]
-    pub package: &'static str,
-    pub reason: &'static str,
-    pub reviewed_by: &'static str,
-    pub reviewed_date: &'static str,
+    pub package: String,
+    pub reason: String,
+    pub reviewed_by: String,
+    pub reviewed_date: String,
+}
+
+/// IOC lists from an IOC data file (data/iocs.toml format)
+///
+/// Every list is optional so extra IOC files (--extra-iocs) can carry just the
+/// entries they add.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct IocData {
+    pub format_version: u32,
+    pub updated: Option<String>,
+    // Known malicious file hashes (SHA-256)
+    pub malicious_hashes: Vec<String>,
+    // Known compromised namespaces - packages in these namespaces may be compromised
+    pub compromised_namespaces: Vec<String>,
+    // Known malicious setup_bun.js / bun_environment.js hashes (Koi.ai IOCs)
+    pub setup_bun_hashes: Vec<String>,
+    pub bun_environment_hashes: Vec<String>,
+    // Known malicious package tarballs as lockfile integrity values ("sha512-<base64>")
+    pub malicious_tarball_integrity: Vec<String>,
+    pub verified_files: Vec<VerifiedFile>,
 }
 
-pub const VERIFIED_FILES: &[VerifiedFile] = &[
-    VerifiedFile {
-        hash: "ce2f8852444caccee5a19008a7582cc3bd072c39fa6008edac3ad4e489f02d5e",
-        path: "error-ex/index.js",
-        package: "error-ex@1.3.4",
-        reason: "Error message manipulation utility - extracts error properties safely",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "85378d9a0f6e2bd60b2cf2228ac75b8004fac78582eebcd0dc9f9161f25666dc",
-        path: "parse-json/index.js",
-        package: "parse-json@7.1.1",
-        reason: "JSON parser with better error messages - no network or file system access",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "c5bb23b3ca69e97ddefdb76724b1a7936ac18b5e47c3fe3c5391969d6e6d06f8",
-        path: "strip-ansi/index.js",
-        package: "strip-ansi@7.1.2",
-        reason: "ANSI escape code stripping utility - removes terminal color codes safely",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "4508758772b1f52850b576ca714bbfd6edb05f8d36492ceab573db47f5cd7d84",
-        path: "string-width/index.js",
-        package: "string-width@5.1.2",
-        reason: "Calculates display width of strings - no network or file system access",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "6e3e10026230a33197e56422a2d95fc1815528c0bde7c1c790fd1a733b04bd39",
-        path: "unist-util-visit-parents/index.js",
-        package: "unist-util-visit-parents@6.0.1",
-        reason: "Abstract syntax tree visitor utility - no network or file system access",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "10361ec7e4678874114103e47caa1c8ef1cffc78e0efce5088e081a26fe6e977",
-        path: "wrap-ansi/index.js",
-        package: "wrap-ansi@8.1.0",
-        reason: "Text wrapping utility for ANSI escape codes - no network or file system access",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "2dd3014e8ce92317dfd819fc678217d8fdf47086a4607cc49566f0dee02b832a",
-        path: "markdown-table/index.js",
-        package: "markdown-table@3.0.4",
-        reason: "Markdown table generation utility - no network or file system access",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "a5dc0fe8f78d02ddf6554e75bab527612c047b80610128fa721287f71187fd7d",
-        path: "formdata-polyfill/FormData.js",
-        package: "formdata-polyfill@4.0.10",
-        reason: "FormData polyfill for IE compatibility - wraps XMLHttpRequest for FormData support only",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-    VerifiedFile {
-        hash: "697a9732b7e7c2ea771298fe0020dd80797b280a3ce528a5d3044c89f891f1d4",
-        path: "formdata-polyfill/formdata.min.js",
-        package: "formdata-polyfill@4.0.10",
-        reason: "FormData polyfill minified - IE compatibility wrapper, no network exfiltration",
-        reviewed_by: "ai-agent",
-        reviewed_date: "2025-10-18",
-    },
-];
+impl IocData {
+    // Function: parse
+    // Purpose: Parse an IOC data file
+    // Args: content - TOML in data/iocs.toml format
+    // Returns: IocData or error (invalid TOML, unknown keys or a newer format version)
+    pub fn parse(content: &str) -> Result<Self> {
+        let data: Self = toml::from_str(content)?;
+        if data.format_version > IOC_FORMAT_VERSION {
+            bail!(
+                "IOC data format version {} is newer than supported version {IOC_FORMAT_VERSION} - update shai-hulud-detector",
+                data.format_version
+            );
+        }
+        Ok(data)
+    }
+
+    /// IOC lists compiled into the binary
+    pub fn builtin() -> Self {
+        Self::parse(BUILTIN_IOCS).expect("data/iocs.toml is valid")
+    }
+
+    /// Total number of IOC entries across all lists
+    pub fn len(&self) -> usize {
+        self.malicious_hashes.len()
+            + self.compromised_namespaces.len()
+            + self.setup_bun_hashes.len()
+            + self.bun_environment_hashes.len()
+            + self.malicious_tarball_integrity.len()
+            + self.verified_files.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // Function: merge
+    // Purpose: Add the entries of another IOC file (e.g. --extra-iocs)
+    // Args: other - IOC data to merge in
+    // Returns: None (duplicates are skipped, order is kept)
+    pub fn merge(&mut self, other: IocData) {
+        fn extend_unique<T: PartialEq>(list: &mut Vec<T>, extra: Vec<T>) {
+            for item in extra {
+                if !list.contains(&item) {
+                    list.push(item);
+                }
+            }
+        }
+        extend_unique(&mut self.malicious_hashes, other.malicious_hashes);
+        extend_unique(
+            &mut self.compromised_namespaces,
+            other.compromised_namespaces,
+        );
+        extend_unique(&mut self.setup_bun_hashes, other.setup_bun_hashes);
+        extend_unique(
+            &mut self.bun_environment_hashes,
+            other.bun_environment_hashes,
+        );
+        extend_unique(
+            &mut self.malicious_tarball_integrity,
+            other.malicious_tarball_integrity,
+        );
+        extend_unique(&mut self.verified_files, other.verified_files);
+    }
+
+    // Function: is_compromised_namespace
+    // Purpose: Check if package name belongs to compromised namespace
+    // Args: package_name - name of package to check
+    // Returns: true if package is in compromised namespace
+    #[allow(dead_code)]
+    pub fn is_compromised_namespace(&self, package_name: &str) -> bool {
+        self.compromised_namespaces
+            .iter()
+            .any(|ns| package_name.starts_with(ns.as_str()))
+    }
+}
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct CompromisedPackage {
@@ -152,155 +155,357 @@ impl CompromisedPackage {
     }
 }
 
+// Function: parse_compromised_packages
+// Purpose: Parse compromised-packages.txt content (package:version per line, # comments)
+// Args: content - feed file content
+// Returns: HashSet of CompromisedPackage entries
+pub fn parse_compromised_packages(content: &str) -> HashSet<CompromisedPackage> {
+    let mut packages = HashSet::new();
+    for line in content.lines() {
+        let line = line.trim_end_matches('\r');
+        if line.trim().starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if let Some(pkg) = CompromisedPackage::from_line(line) {
+            packages.insert(pkg);
+        }
+    }
+    packages
+}
+
+// Function: compromised_packages_feed
+// Purpose: Describe the compromised-packages.txt feed for feeds::load / feeds::update
+// Args: feeds - feed options (--feed)
+// Returns: FeedSpec of the compromised package feed
+pub fn compromised_packages_feed(feeds: &FeedOptions) -> FeedSpec<'_> {
+    FeedSpec {
+        name: feeds::COMPROMISED_PACKAGES_FEED,
+        label: "compromised packages",
+        unit: "packages",
+        default_url: feeds::DEFAULT_FEED_URL,
+        source: feeds.source.as_ref(),
+    }
+}
+
+// Function: ioc_feed
+// Purpose: Describe the IOC data feed (data/iocs.toml) for feeds::load / feeds::update
+// Args: feeds - feed options (--ioc-feed)
+// Returns: FeedSpec of the IOC data feed
+pub fn ioc_feed(feeds: &FeedOptions) -> FeedSpec<'_> {
+    FeedSpec {
+        name: feeds::IOC_FEED,
+        label: "IOC data",
+        unit: "IOCs",
+        default_url: feeds::DEFAULT_IOC_URL,
+        source: feeds.ioc_source.as_ref(),
+    }
+}
+
 // Function: load_compromised_packages
 // Purpose: Load compromised package database from external file or fallback list
-// Args: packages_file - path to compromised-packages.txt
-// Modifies: None
+// Args: packages_file - path to compromised-packages.txt, feeds - --offline / --feed / cache options
+// Modifies: Feed cache (on successful download)
 // Returns: HashSet of CompromisedPackage entries (604+ package:version entries)
+//          and metadata describing where they came from
 pub fn load_compromised_packages<P: AsRef<Path>>(
     packages_file: P,
-) -> Result<HashSet<CompromisedPackage>> {
-    let packages_file = packages_file.as_ref();
-    let mut packages = HashSet::new();
+    feeds: &FeedOptions,
+) -> Result<(HashSet<CompromisedPackage>, FeedMetadata)> {
+    let spec = compromised_packages_feed(feeds);
+    let count = |content: &str| parse_compromised_packages(content).len();
 
-    // STRATEGY: GitHub-First (always fresh data!)
-    // 1. Try GitHub download (best - always up to date)
-    // 2. Fall back to local files (offline mode)
-    // 3. Last resort: embedded minimal list
+    if let Some((content, metadata)) =
+        feeds::load(&spec, feeds, Some(packages_file.as_ref()), count)?
+    {
+        feeds.check_fresh(&metadata)?;
+        return Ok((parse_compromised_packages(&content), metadata));
+    }
+
+    // Last resort: snapshot embedded at build time (data/compromised-packages.txt)
+    let reason = if feeds.offline {
+        "❌ Offline and no cached file! (run update-feeds first)"
+    } else {
+        "❌ No internet and no cached file!"
+    };
+    crate::colors::print_status(crate::colors::Color::Red, reason);
 
-    // Try GitHub first
+    let (packages, metadata) = embedded_snapshot();
+    feeds.check_fresh(&metadata)?;
     crate::colors::print_status(
-        crate::colors::Color::Blue,
-        "📡 Fetching latest compromised packages from GitHub...",
+        crate::colors::Color::Yellow,
+        &format!(
+            "⚠️  Using embedded snapshot from {} ({} packages, sha256 {}) - may miss newer compromises",
+            snapshot_date(EMBEDDED_SNAPSHOT).unwrap_or("unknown date"),
+            packages.len(),
+            &metadata.sha256[..12]
+        ),
     );
+    Ok((packages, metadata))
+}
 
-    match download_from_github() {
-        Ok(content) => {
-            // Parse GitHub content
-            for line in content.lines() {
-                let line = line.trim_end_matches('\r');
-                if line.trim().starts_with('#') || line.trim().is_empty() {
-                    continue;
-                }
-                if let Some(pkg) = CompromisedPackage::from_line(line) {
-                    packages.insert(pkg);
-                }
-            }
+// Function: embedded_snapshot
+// Purpose: Parse the compromised-packages snapshot compiled into the binary
+// Args: None
+// Returns: Packages and metadata (generation = snapshot date, sha256 of the snapshot)
+pub fn embedded_snapshot() -> (HashSet<CompromisedPackage>, FeedMetadata) {
+    let packages = parse_compromised_packages(EMBEDDED_SNAPSHOT);
+    let mut metadata = FeedMetadata::new(
+        feeds::COMPROMISED_PACKAGES_FEED,
+        "embedded",
+        EMBEDDED_SNAPSHOT,
+        packages.len(),
+    )
+    .with_origin(FeedOrigin::Embedded);
+    // Without a Snapshot-Date header (placeholder file) the age is unknown, not "now"
+    metadata.fetched_at = snapshot_date(EMBEDDED_SNAPSHOT)
+        .map_or_else(|| "unknown".to_string(), |date| format!("{date}T00:00:00Z"));
+    (packages, metadata)
+}
 
-            crate::colors::print_status(
-                crate::colors::Color::Green,
-                &format!(
-                    "✅ Downloaded {} compromised packages from GitHub",
-                    packages.len()
-                ),
-            );
+// Function: snapshot_date
+// Purpose: Read the "# Snapshot-Date: YYYY-MM-DD" header of a feed snapshot
+// Args: content - snapshot content
+// Returns: Snapshot date, None if the header is missing
+pub fn snapshot_date(content: &str) -> Option<&str> {
+    content
+        .lines()
+        .take_while(|line| line.starts_with('#'))
+        .find_map(|line| {
+            line.trim_start_matches('#')
+                .trim()
+                .strip_prefix("Snapshot-Date:")
+        })
+        .map(str::trim)
+}
+
+// Function: load_osv_advisories
+// Purpose: Load OSV / GHSA malicious-package advisories given with --feed FILE.json or DIR
+// Args: path - OSV JSON file or directory of them (read from local disk)
+// Returns: Exact name:version entries, advisories with ranges, and feed metadata
+pub fn load_osv_advisories(
+    path: &Path,
+) -> Result<(HashSet<CompromisedPackage>, Advisories, FeedMetadata)> {
+    let (advisories, content) = osv::Advisories::load(path)?;
+    crate::colors::print_status(
+        crate::colors::Color::Blue,
+        &format!(
+            "📦 Using OSV advisories from {} ({} advisories for {} packages)",
+            path.display(),
+            advisories.len(),
+            advisories.package_count()
+        ),
+    );
+    let source = crate::utils::normalize_path(path);
+    let metadata = FeedMetadata::new("osv-advisories", &source, &content, advisories.len())
+        .with_origin(FeedOrigin::FeedFile);
+    let packages = advisories.exact_versions().collect();
+    Ok((packages, advisories, metadata))
+}
+
+// Function: load_iocs
+// Purpose: Load IOC lists (hashes, namespaces, verified files) and merge --extra-iocs
+// Args: feeds - --offline / --ioc-feed / --extra-iocs / cache options
+// Modifies: Feed cache (on successful download)
+// Returns: Merged IocData and metadata of every IOC file used
+pub fn load_iocs(feeds: &FeedOptions) -> Result<(IocData, Vec<FeedMetadata>)> {
+    let spec = ioc_feed(feeds);
+    let count = |content: &str| IocData::parse(content).map_or(0, |data| data.len());
 
-            // Cache for offline use
-            if let Err(e) = fs::write("compromised-packages.txt", &content) {
+    let loaded = match feeds::load(&spec, feeds, None, count)? {
+        Some((content, metadata)) => match IocData::parse(&content) {
+            Ok(data) => Some((data, metadata)),
+            // An explicit IOC file must be valid - downloaded/cached ones fall back
+            Err(e) if spec.source.is_some() => {
+                return Err(e.context(format!("Invalid IOC file {}", spec.source_label())));
+            }
+            Err(e) => {
                 crate::colors::print_status(
                     crate::colors::Color::Yellow,
-                    &format!("⚠️  Could not cache file: {e}"),
+                    &format!("⚠️  Invalid IOC data from {}: {e}", metadata.source),
                 );
+                None
             }
+        },
+        None => None,
+    };
 
-            return Ok(packages);
+    let (mut iocs, metadata) = loaded.unwrap_or_else(|| {
+        let data = IocData::builtin();
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!("📦 Using built-in IOC lists ({} IOCs)", data.len()),
+        );
+        let mut metadata = FeedMetadata::new(spec.name, "embedded", BUILTIN_IOCS, data.len())
+            .with_origin(FeedOrigin::Embedded);
+        if let Some(ref updated) = data.updated {
+            metadata.fetched_at = format!("{updated}T00:00:00Z");
         }
-        Err(e) => {
+        (data, metadata)
+    });
+    let mut sources = vec![metadata];
+
+    // User-supplied extra IOC files are merged into whatever was loaded
+    for extra_file in &feeds.extra_iocs {
+        let content = fs::read_to_string(extra_file)
+            .with_context(|| format!("Failed to read IOC file {}", extra_file.display()))?;
+        let extra = IocData::parse(&content)
+            .with_context(|| format!("Invalid IOC file {}", extra_file.display()))?;
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!(
+                "➕ Merging {} extra IOCs from {}",
+                extra.len(),
+                extra_file.display()
+            ),
+        );
+        let source = crate::utils::normalize_path(extra_file);
+        sources.push(
+            FeedMetadata::new("extra-iocs", &source, &content, extra.len())
+                .with_origin(FeedOrigin::FeedFile),
+        );
+        iocs.merge(extra);
+    }
+
+    Ok((iocs, sources))
+}
+
+// Function: update_feeds
+// Purpose: Refresh every feed in the cache without scanning (update-feeds subcommand)
+// Args: feeds - feed options (--feed, --ioc-feed, --cache-dir)
+// Returns: Metadata and fetch status per feed
+pub fn update_feeds(feeds: &FeedOptions) -> Result<Vec<(FeedMetadata, FetchStatus)>> {
+    let mut updated = Vec::new();
+    match feeds.source {
+        // OSV advisories are read from disk on every scan, nothing to cache
+        Some(FeedSource::File(ref path)) if osv::is_osv_source(path) => {
             crate::colors::print_status(
-                crate::colors::Color::Yellow,
-                &format!("⚠️  GitHub download failed: {e} - using cached files..."),
+                crate::colors::Color::Blue,
+                &format!(
+                    "ℹ️  OSV advisories in {} are read on every scan - not cached",
+                    path.display()
+                ),
             );
         }
+        _ => updated.push(feeds::update(
+            &compromised_packages_feed(feeds),
+            feeds,
+            |content| Ok(parse_compromised_packages(content).len()),
+        )?),
     }
+    updated.push(feeds::update(&ioc_feed(feeds), feeds, |content| {
+        Ok(IocData::parse(content)?.len())
+    })?);
+    Ok(updated)
+}
 
-    // Fallback: Try local file
-    if packages_file.exists() {
-        let content =
-            fs::read_to_string(packages_file).context("Failed to read compromised-packages.txt")?;
+/// Detection data used by a scan (IOC feeds plus where they came from)
+#[derive(Debug, Clone, Default)]
+pub struct DetectionData {
+    pub compromised_packages: HashSet<CompromisedPackage>,
+    pub malicious_hashes: HashSet<String>,
+    /// OSV advisories (--feed FILE.json / DIR) for range matching and advisory IDs
+    pub advisories: Advisories,
+    /// Namespaces, Bun payload hashes and verified files
+    pub iocs: IocData,
+    /// Source, fetch time and hash of every loaded feed (reported in scan output)
+    pub feeds: Vec<FeedMetadata>,
+}
 
-        for line in content.lines() {
-            let line = line.trim_end_matches('\r');
-            if line.trim().starts_with('#') || line.trim().is_empty() {
-                continue;
-            }
-            if let Some(pkg) = CompromisedPackage::from_line(line) {
-                packages.insert(pkg);
-            }
+// Helper to load both packages and hashes
+pub fn load_detection_data<P: AsRef<Path>>(
+    packages_file: P,
+    feeds: &FeedOptions,
+) -> Result<DetectionData> {
+    let (compromised_packages, advisories, packages_metadata) = match feeds.source {
+        Some(FeedSource::File(ref path)) if osv::is_osv_source(path) => load_osv_advisories(path)?,
+        _ => {
+            let (packages, metadata) = load_compromised_packages(packages_file, feeds)?;
+            (packages, Advisories::default(), metadata)
         }
+    };
+    let (iocs, ioc_metadata) = load_iocs(feeds)?;
+    let malicious_hashes: HashSet<String> = iocs.malicious_hashes.iter().cloned().collect();
 
+    let mut sources = vec![packages_metadata];
+    sources.extend(ioc_metadata);
+    for metadata in &sources {
         crate::colors::print_status(
             crate::colors::Color::Blue,
-            &format!("📦 Using cached file ({} packages)", packages.len()),
+            &format!("🗂️  Feed {}", metadata.summary()),
         );
-
-        return Ok(packages);
     }
 
-    // Last resort: embedded list
-    crate::colors::print_status(
-        crate::colors::Color::Red,
-        "❌ No internet and no cached file!",
-    );
-    crate::colors::print_status(
-        crate::colors::Color::Yellow,
-        "⚠️  Using embedded minimal list (7 packages only!)",
-    );
+    Ok(DetectionData {
+        compromised_packages,
+        malicious_hashes,
+        advisories,
+        iocs,
+        feeds: sources,
+    })
+}
 
-    let fallback = vec![
-        "@ctrl/tinycolor:4.1.0",
-        "@ctrl/tinycolor:4.1.1",
-        "@ctrl/tinycolor:4.1.2",
-        "@ctrl/deluge:1.2.0",
-        "angulartics2:14.1.2",
-        "koa2-swagger-ui:5.11.1",
-        "koa2-swagger-ui:5.11.2",
-    ];
-
-    for entry in fallback {
-        if let Some(pkg) = CompromisedPackage::from_line(entry) {
-            packages.insert(pkg);
-        }
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_iocs() {
+        let iocs = IocData::builtin();
+        assert_eq!(iocs.format_version, IOC_FORMAT_VERSION);
+        assert!(iocs.malicious_hashes.contains(
+            &"de0e25a3e6c1e1e5998b306b7141b3dc4c0088da9d7bb47c1c00c91e6e4f85d6".to_string()
+        ));
+        assert_eq!(iocs.compromised_namespaces[0], "@crowdstrike");
+        assert_eq!(iocs.setup_bun_hashes.len(), 1);
+        assert_eq!(iocs.bun_environment_hashes.len(), 3);
+        assert!(iocs.is_compromised_namespace("@ctrl/tinycolor"));
+        assert!(!iocs.verified_files.is_empty());
     }
 
-    Ok(packages)
-}
+    #[test]
+    fn test_embedded_snapshot_generation() {
+        let (packages, metadata) = embedded_snapshot();
+        assert!(packages.contains(&CompromisedPackage::from_line("@ctrl/tinycolor:4.1.1").unwrap()));
+        assert_eq!(metadata.entries, packages.len());
+        assert_eq!(metadata.sha256, feeds::sha256_hex(EMBEDDED_SNAPSHOT));
+        assert_eq!(
+            snapshot_date("# Snapshot-Date: 2025-11-25\na:1.0.0\n"),
+            Some("2025-11-25")
+        );
+        assert_eq!(
+            metadata.fetched_at,
+            snapshot_date(EMBEDDED_SNAPSHOT)
+                .map_or_else(|| "unknown".to_string(), |date| format!("{date}T00:00:00Z"))
+        );
 
-// Helper: Download from GitHub
-fn download_from_github() -> Result<String> {
-    let url = "https://raw.githubusercontent.com/Cobenian/shai-hulud-detect/main/compromised-packages.txt";
-
-    let response = ureq::get(url)
-        .config()
-        .timeout_global(Some(std::time::Duration::from_secs(10)))
-        .build()
-        .call()
-        .context("HTTP request failed")?;
-
-    response
-        .into_body()
-        .read_to_string()
-        .context("Failed to read response")
-}
+        let strict = FeedOptions {
+            require_fresh: true,
+            ..FeedOptions::default()
+        };
+        assert!(strict.check_fresh(&metadata).is_err());
+        assert!(FeedOptions::default().check_fresh(&metadata).is_ok());
+    }
 
-// Helper to load both packages and hashes
-pub fn load_detection_data<P: AsRef<Path>>(
-    packages_file: P,
-) -> Result<(HashSet<CompromisedPackage>, HashSet<String>)> {
-    let packages = load_compromised_packages(packages_file)?;
-    let hashes: HashSet<String> = MALICIOUS_HASHLIST
-        .iter()
-        .map(|s| (*s).to_string())
-        .collect();
-    Ok((packages, hashes))
-}
+    #[test]
+    fn test_merge_extra_iocs() {
+        let mut iocs = IocData::builtin();
+        let before = iocs.len();
+        let extra = IocData::parse(
+            r#"
+            malicious_hashes = ["0000000000000000000000000000000000000000000000000000000000000001"]
+            compromised_namespaces = ["@ctrl", "@evil-corp"]
+            "#,
+        )
+        .unwrap();
+        iocs.merge(extra);
 
-// Function: is_compromised_namespace
-// Purpose: Check if package name belongs to compromised namespace
-// Args: package_name - name of package to check
-// Returns: true if package is in compromised namespace
-#[allow(dead_code)]
-pub fn is_compromised_namespace(package_name: &str) -> bool {
-    COMPROMISED_NAMESPACES
-        .iter()
-        .any(|ns| package_name.starts_with(ns))
+        assert_eq!(iocs.len(), before + 2);
+        assert_eq!(iocs.compromised_namespaces.last().unwrap(), "@evil-corp");
+    }
+
+    #[test]
+    fn test_rejects_newer_format_and_unknown_keys() {
+        assert!(IocData::parse("format_version = 99").is_err());
+        assert!(IocData::parse("malicious_hash = []").is_err());
+    }
 }
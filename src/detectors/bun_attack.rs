@@ -4,29 +4,22 @@
 // Corresponds to bash functions:
 // - check_bun_attack_files() - Lines 271-346 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
+use crate::file_index::{FileIndex, IndexEntry};
 use sha2::{Digest, Sha256};
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
-
-// Known malicious file hashes from Koi.ai incident report
-// https://www.koi.ai/incident/live-updates-sha1-hulud-the-second-coming
-const SETUP_BUN_HASHES: &[&str] =
-    &["a3894003ad1d293ba96d77881ccd2071446dc3f65f434669b49b3da92421901a"];
-
-const BUN_ENVIRONMENT_HASHES: &[&str] = &[
-    "62ee164b9b306250c1172583f138c9614139264f889fa99614903c12755468d0",
-    "f099c5d9ec417d4445a0328ac0ada9cde79fc37410914103ae9c609cbc0ee068",
-    "cbb9bc5a8496243e02f3cc080efbe3e4a1430ba0671f2e43a202bf45b05479cd",
-];
 
 // Function: check_bun_attack_files
 // Purpose: Detect November 2025 "Shai-Hulud: The Second Coming" Bun attack files
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
+//       setup_bun_hashes, bun_environment_hashes - known malicious hashes from Koi.ai incident report
+//       (https://www.koi.ai/incident/live-updates-sha1-hulud-the-second-coming, see data/iocs.toml)
 // Returns: Vec<Finding> with paths to suspicious Bun-related malicious files
-pub fn check_bun_attack_files(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_bun_attack_files(
+    index: &FileIndex,
+    setup_bun_hashes: &[String],
+    bun_environment_hashes: &[String],
+) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for November 2025 Bun attack files...",
@@ -35,18 +28,14 @@ pub fn check_bun_attack_files(scan_dir: &Path) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     // Look for setup_bun.js files (fake Bun runtime installation)
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.files() {
         let path = entry.path();
 
         // Check for setup_bun.js
-        if path.is_file() && path.file_name().and_then(|n| n.to_str()) == Some("setup_bun.js") {
+        if entry.file_name() == "setup_bun.js" {
             // Verify hash if possible
-            if let Ok(file_hash) = calculate_sha256(path) {
-                if SETUP_BUN_HASHES.contains(&file_hash.as_str()) {
+            if let Some(file_hash) = calculate_sha256(index, entry) {
+                if setup_bun_hashes.contains(&file_hash) {
                     findings.push(Finding::new(
                         path.to_path_buf(),
                         format!("SHA256={file_hash} (CONFIRMED MALICIOUS - Koi.ai IOC)"),
@@ -76,11 +65,10 @@ pub fn check_bun_attack_files(scan_dir: &Path) -> Vec<Finding> {
         }
 
         // Check for bun_environment.js (10MB+ obfuscated payload)
-        if path.is_file() && path.file_name().and_then(|n| n.to_str()) == Some("bun_environment.js")
-        {
+        if entry.file_name() == "bun_environment.js" {
             // Verify hash if possible
-            if let Ok(file_hash) = calculate_sha256(path) {
-                if BUN_ENVIRONMENT_HASHES.contains(&file_hash.as_str()) {
+            if let Some(file_hash) = calculate_sha256(index, entry) {
+                if bun_environment_hashes.contains(&file_hash) {
                     findings.push(Finding::new(
                         path.to_path_buf(),
                         format!("SHA256={file_hash} (CONFIRMED MALICIOUS - Koi.ai IOC)"),
@@ -115,11 +103,44 @@ pub fn check_bun_attack_files(scan_dir: &Path) -> Vec<Finding> {
     findings
 }
 
-// Helper function to calculate SHA256 hash of a file
-fn calculate_sha256(path: &Path) -> Result<String, std::io::Error> {
-    let contents = fs::read(path)?;
+/// Registry entry for `check_bun_attack_files()`
+pub struct BunAttackDetector;
+
+impl Detector for BunAttackDetector {
+    fn id(&self) -> &'static str {
+        "bun_attack_files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Fake Bun runtime installation files and payloads"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn results_key(&self, finding: &Finding) -> &'static str {
+        if finding.category == "bun_environment_files" {
+            "bun_environment_files"
+        } else {
+            "bun_setup_files"
+        }
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_bun_attack_files(
+            ctx.index,
+            &ctx.iocs.setup_bun_hashes,
+            &ctx.iocs.bun_environment_hashes,
+        )
+    }
+}
+
+// Helper function to calculate SHA256 hash of a file (content shared via the file index)
+fn calculate_sha256(index: &FileIndex, entry: &IndexEntry) -> Option<String> {
+    let contents = index.read(entry)?;
     let mut hasher = Sha256::new();
-    hasher.update(&contents);
+    hasher.update(contents.bytes());
     let result = hasher.finalize();
-    Ok(format!("{result:x}"))
+    Some(format!("{result:x}"))
 }
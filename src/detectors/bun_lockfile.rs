@@ -0,0 +1,310 @@
+// Bun Lockfile Parser - bun.lock (text, JSONC) and bun.lockb (binary)
+// Purpose: Resolve installed versions for projects using Bun as package manager
+//
+// bun.lock "packages" are keyed by install path, nested copies below their parent:
+//   "debug":         ["debug@4.3.4", "", { "dependencies": { "ms": "2.1.2" } }, "sha512-..."]
+//   "express/debug": ["debug@2.6.9", "", { ... }, "sha512-..."]
+//   "strip":         ["strip-ansi@6.0.1", "", {}, "sha512-..."]     (alias)
+//   "my-lib":        ["my-lib@workspace:packages/lib"]
+//
+// bun.lockb has no stable documented layout - it is decoded by bun itself
+// (`bun bun.lockb` prints the lockfile in yarn v1 format). Running an external binary
+// against the scanned tree is opt-in (--decode-bun-lockb); without it bun.lockb files
+// are reported as not verified. bun runs in an empty temporary directory so a
+// bunfig.toml or .env in the scanned project is never picked up.
+
+use anyhow::{bail, Context, Result};
+use serde_json::Value;
+use std::path::Path;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// bun.lockb files are only decoded with the bun CLI when explicitly enabled
+static DECODE_WITH_BUN: AtomicBool = AtomicBool::new(false);
+
+// Unique working directory per bun invocation
+static BUN_RUNS: AtomicUsize = AtomicUsize::new(0);
+
+/// First bytes of every bun.lockb
+const LOCKB_MAGIC: &[u8] = b"#!/usr/bin/env bun\nbun-lockfile-format-v0\n";
+
+/// One installed package of a bun.lock
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BunPackage {
+    /// Real package name (aliases resolve to the target)
+    pub name: String,
+    pub version: String,
+    /// Packages leading to this copy, outermost first (from the "packages" key)
+    pub chain: Vec<String>,
+    /// Original "packages" key, e.g. "express/debug"
+    pub key: String,
+    /// Registry the tarball came from (None for the default registry)
+    pub registry: Option<String>,
+    /// Subresource integrity, e.g. "sha512-..."
+    pub integrity: Option<String>,
+}
+
+impl BunPackage {
+    /// True for registry versions (false for workspace:, github:, file:, tarball URLs, ...)
+    pub fn is_registry_version(&self) -> bool {
+        !self.version.is_empty() && !self.version.contains(':')
+    }
+}
+
+// Function: parse_bun_lock
+// Purpose: Parse a text bun.lock (JSON with trailing commas / comments)
+// Args: content - bun.lock content
+// Returns: Installed registry packages (workspace, git, file and tarball entries skipped)
+pub fn parse_bun_lock(content: &str) -> Result<Vec<BunPackage>> {
+    let mut packages = parse_bun_lock_entries(content)?;
+    packages.retain(BunPackage::is_registry_version);
+    Ok(packages)
+}
+
+// Function: parse_bun_lock_entries
+// Purpose: Parse every "packages" entry of a bun.lock, whatever its source
+// Args: content - bun.lock content
+// Returns: Packages; non-registry entries keep their source as version ("github:x/y#abc")
+pub fn parse_bun_lock_entries(content: &str) -> Result<Vec<BunPackage>> {
+    let json: Value =
+        serde_json::from_str(&strip_jsonc(content)).context("Failed to parse bun.lock")?;
+
+    let mut packages = Vec::new();
+    let Some(entries) = json.get("packages").and_then(Value::as_object) else {
+        return Ok(packages);
+    };
+    for (key, entry) in entries {
+        let Some(resolved) = entry.get(0).and_then(Value::as_str) else {
+            continue;
+        };
+        let Some(at) = resolved.get(1..).and_then(|r| r.find('@')).map(|at| at + 1) else {
+            continue;
+        };
+        let (name, version) = (&resolved[..at], &resolved[at + 1..]);
+        // Registry entries: [resolved, registry URL ("" = default), metadata, integrity]
+        let registry_field = |index: usize| {
+            entry
+                .get(index)
+                .and_then(Value::as_str)
+                .filter(|value| !value.is_empty())
+                .map(str::to_string)
+        };
+        let registry_entry = entry.get(1).is_some_and(Value::is_string);
+        packages.push(BunPackage {
+            name: name.to_string(),
+            version: version.to_string(),
+            chain: key_chain(key),
+            key: key.clone(),
+            registry: registry_entry.then(|| registry_field(1)).flatten(),
+            integrity: registry_entry.then(|| registry_field(3)).flatten(),
+        });
+    }
+    Ok(packages)
+}
+
+// Function: set_decode_with_bun
+// Purpose: Allow decoding bun.lockb files with the bun CLI (--decode-bun-lockb)
+// Args: enabled - true to run bun on every bun.lockb found
+// Returns: None
+pub fn set_decode_with_bun(enabled: bool) {
+    DECODE_WITH_BUN.store(enabled, Ordering::Relaxed);
+}
+
+pub fn decode_with_bun() -> bool {
+    DECODE_WITH_BUN.load(Ordering::Relaxed)
+}
+
+// Function: decode_bun_lockb
+// Purpose: Convert a binary bun.lockb to yarn v1 lockfile text using the bun CLI
+// Args: path - bun.lockb on disk
+// Returns: yarn.lock formatted text, or an error if the file is not a bun lockfile,
+//          decoding is not enabled (--decode-bun-lockb) or bun is not installed
+pub fn decode_bun_lockb(path: &Path) -> Result<String> {
+    let bytes = std::fs::read(path).context("Failed to read bun.lockb")?;
+    if !bytes.starts_with(LOCKB_MAGIC) {
+        bail!("{} is not a bun lockfile", path.display());
+    }
+    if !decode_with_bun() {
+        bail!(
+            "binary bun.lockb not verified (decoding it runs bun, enable with --decode-bun-lockb)"
+        );
+    }
+
+    // Absolute path, bun itself runs outside the scanned tree
+    let path = path
+        .canonicalize()
+        .with_context(|| format!("Failed to resolve {}", path.display()))?;
+    let workdir = std::env::temp_dir().join(format!(
+        "shai-hulud-bun-{}-{}",
+        std::process::id(),
+        BUN_RUNS.fetch_add(1, Ordering::Relaxed)
+    ));
+    std::fs::create_dir_all(&workdir).context("Failed to create a directory for bun")?;
+    let output = Command::new("bun")
+        .arg(&path)
+        .current_dir(&workdir)
+        .output();
+    let _ = std::fs::remove_dir_all(&workdir);
+
+    let output = output.context(
+        "bun is required to decode bun.lockb (or run `bun install --save-text-lockfile`)",
+    )?;
+    if !output.status.success() {
+        bail!("bun could not decode {}", path.display());
+    }
+    String::from_utf8(output.stdout).context("bun printed invalid UTF-8")
+}
+
+// Helper: Package names along a bun.lock key ("express/@types/node" -> ["express", "@types/node"])
+fn key_chain(key: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut segments = key.split('/');
+    while let Some(segment) = segments.next() {
+        if segment.starts_with('@') {
+            if let Some(name) = segments.next() {
+                chain.push(format!("{segment}/{name}"));
+            }
+        } else if !segment.is_empty() {
+            chain.push(segment.to_string());
+        }
+    }
+    chain
+}
+
+// Function: strip_jsonc
+// Purpose: Turn JSONC into JSON by removing comments and trailing commas
+// Args: content - JSON with optional // and /* */ comments and trailing commas
+// Returns: Plain JSON (string contents are left untouched)
+pub fn strip_jsonc(content: &str) -> String {
+    // Pass 1: drop comments
+    let mut json = String::with_capacity(content.len());
+    let mut chars = content.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            json.push(c);
+            match c {
+                '\\' => json.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match (c, chars.peek()) {
+            ('/', Some('/')) => while chars.next_if(|&c| c != '\n').is_some() {},
+            ('/', Some('*')) => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            _ => {
+                in_string = c == '"';
+                json.push(c);
+            }
+        }
+    }
+
+    // Pass 2: drop commas directly (whitespace aside) before } or ]
+    let bytes = json.as_bytes();
+    let mut output = String::with_capacity(json.len());
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in json.char_indices() {
+        if in_string {
+            in_string = escaped || c != '"';
+            escaped = !escaped && c == '\\';
+        } else if c == '"' {
+            in_string = true;
+        } else if c == ',' {
+            let next = bytes[i + 1..].iter().find(|b| !b.is_ascii_whitespace());
+            if matches!(next, Some(b'}' | b']')) {
+                continue;
+            }
+        }
+        output.push(c);
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_bun_lock() {
+        let content = r#"{
+  "lockfileVersion": 1,
+  "workspaces": {
+    "": {
+      "name": "app",
+      "dependencies": {
+        "@ctrl/tinycolor": "^4.1.0",
+        "strip": "npm:strip-ansi@^6.0.0",
+      },
+    },
+  },
+  // nested copies are keyed by their parent
+  "packages": {
+    "@ctrl/tinycolor": ["@ctrl/tinycolor@4.1.1", "", {}, "sha512-aaa"],
+    "express": ["express@4.18.2", "", { "dependencies": { "debug": "2.6.9" } }, "sha512-bbb"],
+    "express/debug": ["debug@2.6.9", "", {}, "sha512-ccc"],
+    "my-lib": ["my-lib@workspace:packages/lib"],
+    "strip": ["strip-ansi@6.0.1", "", {}, "sha512-ddd"],
+    "left-pad": ["left-pad@github:x/left-pad#abc123", {}, "abc123"],
+  }
+}
+"#;
+        let packages = parse_bun_lock(content).unwrap();
+        let summary: Vec<_> = packages
+            .iter()
+            .map(|p| format!("{}@{} via {}", p.name, p.version, p.chain.join(" > ")))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "@ctrl/tinycolor@4.1.1 via @ctrl/tinycolor",
+                "express@4.18.2 via express",
+                "debug@2.6.9 via express > debug",
+                "strip-ansi@6.0.1 via strip",
+            ]
+        );
+        assert_eq!(packages[0].integrity.as_deref(), Some("sha512-aaa"));
+        assert_eq!(packages[0].registry, None);
+
+        let entries = parse_bun_lock_entries(content).unwrap();
+        let left_pad = entries.iter().find(|p| p.name == "left-pad").unwrap();
+        assert_eq!(left_pad.version, "github:x/left-pad#abc123");
+        assert_eq!(left_pad.integrity, None);
+    }
+
+    #[test]
+    fn test_strip_jsonc_keeps_strings() {
+        let content = r#"{"url": "https://x/*y*/", "a": [1, 2,], /* c */ "b": "//",}"#;
+        let json: Value = serde_json::from_str(&strip_jsonc(content)).unwrap();
+        assert_eq!(json["url"], "https://x/*y*/");
+        assert_eq!(json["a"], serde_json::json!([1, 2]));
+        assert_eq!(json["b"], "//");
+    }
+
+    #[test]
+    fn test_decode_rejects_non_bun_lockfile() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("bun.lockb");
+        std::fs::write(&path, b"not a lockfile").unwrap();
+        assert!(decode_bun_lockb(&path).is_err());
+    }
+
+    #[test]
+    fn test_decode_is_opt_in() {
+        let temp = tempfile::TempDir::new().unwrap();
+        let path = temp.path().join("bun.lockb");
+        std::fs::write(&path, LOCKB_MAGIC).unwrap();
+        let error = decode_bun_lockb(&path).unwrap_err().to_string();
+        assert!(error.contains("not verified"), "{error}");
+    }
+}
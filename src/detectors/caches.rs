@@ -0,0 +1,728 @@
+// Package Manager Caches - compromised tarballs kept in global caches and stores
+// Purpose: A fixed lockfile does not help while ~/.npm/_cacache, the pnpm store or the
+//          yarn cache still hold the compromised tarball - offline installs reuse it.
+//          With --scan-caches these caches are indexed locally and every cached package
+//          is matched against the compromised list, OSV advisories, malicious tarball
+//          integrity values and malicious file hashes (Rust-only, no Bash equivalent).
+//
+// Cache layouts:
+//   npm        _cacache/index-v5/xx/yy/<sha256>     "<hash>\t{key, integrity, ...}" lines,
+//              key "make-fetch-happen:request-cache:https://registry.../debug/-/debug-4.3.4.tgz"
+//   pnpm       store/v3/files/xx/<hex>-index.json  {name, version, files: {path: {integrity, mode}}}
+//              store/v10/index/xx/<hex>-<name>@<version>.json (content in store/v10/files/xx/<hex>)
+//   yarn v1    ~/.cache/yarn/v6/npm-<name>-<version>-<hash>-integrity/node_modules/<name>/
+//   yarn 2+    ~/.yarn/berry/cache/<name>-npm-<version>-<hash>.zip
+
+use crate::data::CompromisedPackage;
+use crate::detectors::inventory::installed_package_dir;
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index;
+use crate::osv::{self, Advisories};
+use base64::Engine;
+use serde_json::Value;
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+/// File extensions hashed against the malicious hash list (as in check_file_hashes)
+const HASHED_EXTENSIONS: [&str; 3] = ["js", "ts", "json"];
+
+/// Kind of package manager cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CacheKind {
+    Npm,
+    Pnpm,
+    Yarn,
+    YarnBerry,
+}
+
+impl CacheKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Npm => "npm cache",
+            Self::Pnpm => "pnpm store",
+            Self::Yarn => "yarn cache",
+            Self::YarnBerry => "yarn berry cache",
+        }
+    }
+}
+
+/// A cache directory to scan
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageCache {
+    pub kind: CacheKind,
+    pub path: PathBuf,
+}
+
+/// A package found in a cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct CachedPackage {
+    name: String,
+    version: String,
+    /// Cache entry to delete (npm/pnpm index file, yarn entry directory or zip)
+    entry: PathBuf,
+    integrity: Option<String>,
+    /// Hashable files: (path inside the package, where to read it)
+    files: Vec<(String, CachedFile)>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum CachedFile {
+    Disk(PathBuf),
+    ZipMember(PathBuf, String),
+}
+
+impl CachedFile {
+    fn read(&self) -> Option<Vec<u8>> {
+        match self {
+            Self::Disk(path) => fs::read(path).ok(),
+            Self::ZipMember(archive, name) => file_index::read_archive_member(archive, name),
+        }
+    }
+}
+
+// Function: default_caches
+// Purpose: Locate the package manager caches of the current user
+// Args: None (honours npm_config_cache, npm_config_store_dir and YARN_CACHE_FOLDER)
+// Returns: Existing cache directories
+pub fn default_caches() -> Vec<PackageCache> {
+    let home = dirs::home_dir().unwrap_or_default();
+    let env_dir = |name: &str| std::env::var_os(name).map(PathBuf::from);
+    let mut candidates = Vec::new();
+
+    let npm_cache = env_dir("npm_config_cache").unwrap_or_else(|| {
+        if cfg!(windows) {
+            dirs::data_local_dir().unwrap_or_default().join("npm-cache")
+        } else {
+            home.join(".npm")
+        }
+    });
+    candidates.push((CacheKind::Npm, npm_cache.join("_cacache")));
+
+    if let Some(store) = env_dir("npm_config_store_dir") {
+        candidates.push((CacheKind::Pnpm, store));
+    }
+    if let Some(data) = dirs::data_local_dir() {
+        candidates.push((CacheKind::Pnpm, data.join("pnpm").join("store")));
+    }
+    candidates.push((CacheKind::Pnpm, home.join("Library/pnpm/store")));
+    candidates.push((CacheKind::Pnpm, home.join(".pnpm-store")));
+
+    if let Some(folder) = env_dir("YARN_CACHE_FOLDER") {
+        candidates.push((CacheKind::Yarn, folder));
+    }
+    if let Some(cache) = dirs::cache_dir() {
+        candidates.push((CacheKind::Yarn, cache.join("yarn")));
+        candidates.push((CacheKind::Yarn, cache.join("Yarn")));
+    }
+    candidates.push((CacheKind::YarnBerry, home.join(".yarn/berry/cache")));
+
+    let mut caches: Vec<PackageCache> = Vec::new();
+    for (kind, path) in candidates {
+        let path = path.canonicalize().unwrap_or(path);
+        if path.is_dir() && !caches.iter().any(|cache| cache.path == path) {
+            caches.push(PackageCache { kind, path });
+        }
+    }
+    caches
+}
+
+// Function: check_package_caches
+// Purpose: Match every cached package against the compromised set and malicious hashes
+// Args: caches - cache directories, compromised_packages, advisories,
+//       malicious_hashes - SHA-256 of malicious files,
+//       malicious_integrity - lockfile-style integrity values of malicious tarballs
+// Returns: Findings located at the cache entry to purge
+pub fn check_package_caches(
+    caches: &[PackageCache],
+    compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
+    malicious_hashes: &HashSet<String>,
+    malicious_integrity: &[String],
+) -> Vec<Finding> {
+    if caches.is_empty() {
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            "No npm, pnpm or yarn caches found to scan",
+        );
+        return Vec::new();
+    }
+
+    let mut findings = Vec::new();
+    for cache in caches {
+        let packages = match cache.kind {
+            CacheKind::Npm => npm_cache_packages(&cache.path),
+            CacheKind::Pnpm => pnpm_store_packages(&cache.path),
+            CacheKind::Yarn => yarn_cache_packages(&cache.path),
+            CacheKind::YarnBerry => yarn_berry_cache_packages(&cache.path),
+        };
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!(
+                "Checking {} cached packages in {} ({})...",
+                packages.len(),
+                cache.kind.label(),
+                crate::utils::normalize_path(&cache.path)
+            ),
+        );
+
+        for package in &packages {
+            let purge = purge_hint(cache.kind, package);
+            let references = advisories.references(&package.name, &package.version);
+            let key = CompromisedPackage::new(package.name.clone(), package.version.clone());
+            if compromised_packages.contains(&key) || references.is_some() {
+                findings.push(Finding::new(
+                    package.entry.clone(),
+                    format!(
+                        "{} in {} - purge: {purge}",
+                        osv::with_references(
+                            format!("Compromised package {}@{}", package.name, package.version),
+                            references
+                        ),
+                        cache.kind.label()
+                    ),
+                    // One offline install away from node_modules - as severe as a declared one
+                    RiskLevel::High,
+                    "package_caches",
+                ));
+            }
+
+            let malicious_tarball = package.integrity.as_deref().is_some_and(|integrity| {
+                integrity
+                    .split_whitespace()
+                    .any(|hash| malicious_integrity.iter().any(|m| m == hash))
+            });
+            if malicious_tarball {
+                findings.push(Finding::new(
+                    package.entry.clone(),
+                    format!(
+                        "Cached tarball matches a known malicious package: {}@{} in {} - purge: {purge}",
+                        package.name,
+                        package.version,
+                        cache.kind.label()
+                    ),
+                    RiskLevel::High,
+                    "package_caches",
+                ));
+            }
+
+            if malicious_hashes.is_empty() {
+                continue;
+            }
+            for (file_name, file) in &package.files {
+                let Some(bytes) = file.read() else {
+                    continue;
+                };
+                let hash = format!("{:x}", Sha256::digest(&bytes));
+                if malicious_hashes.contains(&hash) {
+                    findings.push(Finding::new(
+                        package.entry.clone(),
+                        format!(
+                            "Cached file matches known malicious hash: {}@{} {file_name} (Hash: {hash}) in {} - purge: {purge}",
+                            package.name,
+                            package.version,
+                            cache.kind.label()
+                        ),
+                        RiskLevel::High,
+                        "package_caches",
+                    ));
+                }
+            }
+        }
+    }
+    findings
+}
+
+// Helper: How to remove one cache entry
+fn purge_hint(kind: CacheKind, package: &CachedPackage) -> String {
+    let entry = crate::utils::normalize_path(&package.entry);
+    match kind {
+        CacheKind::Npm => format!("delete {entry}, then run `npm cache verify`"),
+        CacheKind::Pnpm => format!("delete {entry}, then run `pnpm store prune`"),
+        CacheKind::Yarn => format!("delete {entry} (or `yarn cache clean {}`)", package.name),
+        CacheKind::YarnBerry => format!("delete {entry}"),
+    }
+}
+
+// Function: npm_cache_packages
+// Purpose: Read registry tarballs from an npm _cacache index
+// Args: cacache - the _cacache directory
+// Returns: One package per live tarball index entry (tarballs are not unpacked, so
+//          only name/version and integrity are checked)
+fn npm_cache_packages(cacache: &Path) -> Vec<CachedPackage> {
+    let mut packages = Vec::new();
+    let index_files = WalkDir::new(cacache.join("index-v5"))
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file());
+    for index_file in index_files {
+        let Ok(content) = fs::read_to_string(index_file.path()) else {
+            continue;
+        };
+        // Index buckets are append-only: the last line per key wins, null integrity = deleted
+        let mut live: BTreeMap<String, Option<String>> = BTreeMap::new();
+        for line in content.lines() {
+            let Some((_, json)) = line.split_once('\t') else {
+                continue;
+            };
+            let Ok(entry) = serde_json::from_str::<Value>(json) else {
+                continue;
+            };
+            if let Some(key) = entry.get("key").and_then(Value::as_str) {
+                let integrity = entry
+                    .get("integrity")
+                    .and_then(Value::as_str)
+                    .map(str::to_string);
+                live.insert(key.to_string(), integrity);
+            }
+        }
+        for (key, integrity) in live {
+            let Some(integrity) = integrity else {
+                continue;
+            };
+            let url = key
+                .strip_prefix("make-fetch-happen:request-cache:")
+                .unwrap_or(&key);
+            if let Some((name, version)) = tarball_package(url) {
+                packages.push(CachedPackage {
+                    name,
+                    version,
+                    entry: index_file.path().to_path_buf(),
+                    integrity: Some(integrity),
+                    files: Vec::new(),
+                });
+            }
+        }
+    }
+    packages
+}
+
+// Function: tarball_package
+// Purpose: Package name and version of a registry tarball URL
+// Args: url - e.g. "https://registry.npmjs.org/@scope/pkg/-/pkg-1.0.0.tgz"
+// Returns: (name, version), None for other URLs (packuments, non-registry tarballs)
+pub fn tarball_package(url: &str) -> Option<(String, String)> {
+    let path = url.split(['?', '#']).next()?;
+    let (package_path, file) = path.rsplit_once("/-/")?;
+    let package_path = package_path.replace("%2f", "/").replace("%2F", "/");
+    let mut segments = package_path.rsplit('/');
+    let basename = segments.next()?;
+    let name = match segments.next() {
+        Some(scope) if scope.starts_with('@') => format!("{scope}/{basename}"),
+        _ => basename.to_string(),
+    };
+    let version = file
+        .strip_prefix(basename)?
+        .strip_prefix('-')?
+        .strip_suffix(".tgz")?;
+    if basename.is_empty() || version.is_empty() {
+        return None;
+    }
+    Some((name, version.to_string()))
+}
+
+// Function: pnpm_store_packages
+// Purpose: Read package index files of a pnpm content-addressable store
+// Args: store - pnpm store directory (containing v3/ or v10/)
+// Returns: One package per index file, with its files resolved to content paths
+fn pnpm_store_packages(store: &Path) -> Vec<CachedPackage> {
+    let mut packages = Vec::new();
+    let index_files = WalkDir::new(store)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file() && is_pnpm_index_file(entry.path()));
+    for index_file in index_files {
+        let Some(json) = fs::read_to_string(index_file.path())
+            .ok()
+            .and_then(|content| serde_json::from_str::<Value>(&content).ok())
+        else {
+            continue;
+        };
+        let field = |name: &str| json.get(name).and_then(Value::as_str).map(str::to_string);
+        let (Some(name), Some(version)) = (field("name"), field("version")) else {
+            continue;
+        };
+        // Content lives in the "files" directory next to "index" (v10) or around it (v3)
+        let Some(files_dir) = index_file
+            .path()
+            .parent()
+            .and_then(Path::parent)
+            .and_then(Path::parent)
+            .map(|version_dir| version_dir.join("files"))
+        else {
+            continue;
+        };
+
+        let mut files = Vec::new();
+        if let Some(entries) = json.get("files").and_then(Value::as_object) {
+            for (file_name, info) in entries {
+                if !has_hashed_extension(file_name) {
+                    continue;
+                }
+                let Some(hex) = info
+                    .get("integrity")
+                    .and_then(Value::as_str)
+                    .and_then(integrity_hex)
+                else {
+                    continue;
+                };
+                let executable = info
+                    .get("mode")
+                    .and_then(Value::as_u64)
+                    .is_some_and(|mode| mode & 0o111 != 0);
+                let suffix = if executable { "-exec" } else { "" };
+                let content = files_dir
+                    .join(&hex[..2])
+                    .join(format!("{}{suffix}", &hex[2..]));
+                files.push((file_name.clone(), CachedFile::Disk(content)));
+            }
+        }
+        packages.push(CachedPackage {
+            name,
+            version,
+            entry: index_file.path().to_path_buf(),
+            integrity: None,
+            files,
+        });
+    }
+    packages
+}
+
+// Helper: pnpm index file (v3 "<hex>-index.json" in files/, v10 any .json below index/)
+fn is_pnpm_index_file(path: &Path) -> bool {
+    let name = path
+        .file_name()
+        .and_then(|n| n.to_str())
+        .unwrap_or_default();
+    let in_index_dir = path
+        .parent()
+        .and_then(Path::parent)
+        .and_then(Path::file_name)
+        .is_some_and(|dir| dir == "index");
+    name.ends_with("-index.json") || (in_index_dir && name.ends_with(".json"))
+}
+
+// Helper: Hex digest of an SRI integrity value ("sha512-<base64>")
+fn integrity_hex(integrity: &str) -> Option<String> {
+    let (_, digest) = integrity.split_once('-')?;
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(digest)
+        .ok()?;
+    (bytes.len() > 1).then(|| hex::encode(bytes))
+}
+
+// Helper: File is one of HASHED_EXTENSIONS
+fn has_hashed_extension(file_name: &str) -> bool {
+    Path::new(file_name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| HASHED_EXTENSIONS.contains(&ext))
+}
+
+// Function: yarn_cache_packages
+// Purpose: Read unpacked packages of a yarn v1 cache
+// Args: cache - yarn cache directory (containing v6/ or similar)
+// Returns: One package per "npm-*" cache entry
+fn yarn_cache_packages(cache: &Path) -> Vec<CachedPackage> {
+    let mut packages = Vec::new();
+    let entries = WalkDir::new(cache)
+        .min_depth(1)
+        .max_depth(2)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| {
+            entry.file_type().is_dir() && entry.file_name().to_string_lossy().starts_with("npm-")
+        });
+    for entry in entries {
+        let mut package = None;
+        let mut files = Vec::new();
+        for file in WalkDir::new(entry.path())
+            .into_iter()
+            .filter_map(Result::ok)
+            .filter(|file| file.file_type().is_file())
+        {
+            let relative = file
+                .path()
+                .strip_prefix(entry.path())
+                .unwrap_or(file.path());
+            if package.is_none() && is_package_manifest(relative) {
+                package = manifest_package(&fs::read_to_string(file.path()).unwrap_or_default());
+            }
+            let file_name = relative.to_string_lossy().replace('\\', "/");
+            if has_hashed_extension(&file_name) {
+                files.push((file_name, CachedFile::Disk(file.path().to_path_buf())));
+            }
+        }
+        if let Some((name, version)) = package {
+            packages.push(CachedPackage {
+                name,
+                version,
+                entry: entry.path().to_path_buf(),
+                integrity: None,
+                files,
+            });
+        }
+    }
+    packages
+}
+
+// Function: yarn_berry_cache_packages
+// Purpose: Read package zips of a Yarn 2+ global cache
+// Args: cache - e.g. ~/.yarn/berry/cache
+// Returns: One package per zip with a readable node_modules/<name>/package.json
+fn yarn_berry_cache_packages(cache: &Path) -> Vec<CachedPackage> {
+    let mut packages = Vec::new();
+    let Ok(entries) = fs::read_dir(cache) else {
+        return packages;
+    };
+    let mut archives: Vec<PathBuf> = entries
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .filter(|path| path.extension().is_some_and(|ext| ext == "zip"))
+        .collect();
+    archives.sort();
+
+    for archive in archives {
+        let Some(zip) = fs::File::open(&archive)
+            .ok()
+            .and_then(|file| zip::ZipArchive::new(file).ok())
+        else {
+            continue;
+        };
+        let names: Vec<String> = zip.file_names().map(str::to_string).collect();
+        let manifest = names
+            .iter()
+            .find(|name| is_package_manifest(Path::new(name)))
+            .and_then(|name| {
+                let bytes = file_index::read_archive_member(&archive, name)?;
+                manifest_package(&String::from_utf8(bytes).ok()?)
+            });
+        let Some((name, version)) = manifest else {
+            continue;
+        };
+        let files = names
+            .into_iter()
+            .filter(|member| has_hashed_extension(member))
+            .map(|member| {
+                (
+                    member.clone(),
+                    CachedFile::ZipMember(archive.clone(), member),
+                )
+            })
+            .collect();
+        packages.push(CachedPackage {
+            name,
+            version,
+            entry: archive,
+            integrity: None,
+            files,
+        });
+    }
+    packages
+}
+
+// Helper: package.json directly inside node_modules/<name> of a cache entry
+fn is_package_manifest(relative: &Path) -> bool {
+    relative
+        .file_name()
+        .is_some_and(|name| name == "package.json")
+        && relative.components().count() <= 4
+        && installed_package_dir(relative).is_some()
+}
+
+// Helper: name and version of a package.json
+fn manifest_package(content: &str) -> Option<(String, String)> {
+    let json: Value = serde_json::from_str(content).ok()?;
+    let field = |name: &str| json.get(name).and_then(Value::as_str).map(str::to_string);
+    Some((field("name")?, field("version")?))
+}
+
+/// Registry entry for `check_package_caches()` (only active with --scan-caches)
+pub struct PackageCachesDetector;
+
+impl Detector for PackageCachesDetector {
+    fn id(&self) -> &'static str {
+        "package_caches"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compromised packages in npm/pnpm/yarn caches (purge before the next offline install)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        if !ctx.scan_caches {
+            return Vec::new();
+        }
+        check_package_caches(
+            &default_caches(),
+            ctx.compromised_packages,
+            ctx.advisories,
+            ctx.malicious_hashes,
+            &ctx.iocs.malicious_tarball_integrity,
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+    use tempfile::TempDir;
+
+    const PAYLOAD: &str = "malicious payload";
+
+    #[test]
+    fn test_tarball_package() {
+        assert_eq!(
+            tarball_package("https://registry.npmjs.org/@ctrl/tinycolor/-/tinycolor-4.1.1.tgz"),
+            Some(("@ctrl/tinycolor".to_string(), "4.1.1".to_string()))
+        );
+        assert_eq!(
+            tarball_package(
+                "https://npm.corp.example/api/npm/@ctrl%2ftinycolor/-/tinycolor-4.1.1.tgz"
+            ),
+            Some(("@ctrl/tinycolor".to_string(), "4.1.1".to_string()))
+        );
+        assert_eq!(tarball_package("https://registry.npmjs.org/debug"), None);
+    }
+
+    #[test]
+    fn test_caches_report_entry_to_purge() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let payload_hash = format!("{:x}", Sha256::digest(PAYLOAD));
+
+        // npm: second line deletes the safe entry, the compromised one stays live
+        let npm = root.join("npm/_cacache");
+        fs::create_dir_all(npm.join("index-v5/ab/cd")).unwrap();
+        let line = |url: &str, integrity: &str| {
+            format!(
+                "x\t{{\"key\":\"make-fetch-happen:request-cache:{url}\",\"integrity\":{integrity}}}\n"
+            )
+        };
+        fs::write(
+            npm.join("index-v5/ab/cd/bucket"),
+            [
+                line(
+                    "https://registry.npmjs.org/@ctrl/tinycolor/-/tinycolor-4.1.1.tgz",
+                    "\"sha512-a\"",
+                ),
+                line(
+                    "https://registry.npmjs.org/debug/-/debug-4.3.4.tgz",
+                    "\"sha512-b\"",
+                ),
+                line("https://registry.npmjs.org/debug/-/debug-4.3.4.tgz", "null"),
+            ]
+            .concat(),
+        )
+        .unwrap();
+
+        // pnpm v3 store: index file maps index.js to its content file
+        let pnpm = root.join("pnpm/store");
+        let digest = Sha256::digest(PAYLOAD); // any digest works as content address
+        let integrity = format!(
+            "sha512-{}",
+            base64::engine::general_purpose::STANDARD.encode(digest)
+        );
+        let hex = hex::encode(digest);
+        fs::create_dir_all(pnpm.join("v3/files").join(&hex[..2])).unwrap();
+        fs::write(
+            pnpm.join("v3/files").join(&hex[..2]).join(&hex[2..]),
+            PAYLOAD,
+        )
+        .unwrap();
+        fs::create_dir_all(pnpm.join("v3/files/ff")).unwrap();
+        fs::write(
+            pnpm.join("v3/files/ff/0123-index.json"),
+            format!(
+                r#"{{"name": "evil", "version": "1.0.0", "files": {{"index.js": {{"integrity": "{integrity}", "mode": 420, "size": 17}}}}}}"#
+            ),
+        )
+        .unwrap();
+
+        // yarn v1 cache entry
+        let yarn_entry = root.join("yarn/v6/npm-ngx-bootstrap-19.0.3-abc-integrity");
+        fs::create_dir_all(yarn_entry.join("node_modules/ngx-bootstrap")).unwrap();
+        fs::write(
+            yarn_entry.join("node_modules/ngx-bootstrap/package.json"),
+            r#"{"name": "ngx-bootstrap", "version": "19.0.3"}"#,
+        )
+        .unwrap();
+
+        // yarn berry global cache zip
+        let berry = root.join("berry/cache");
+        fs::create_dir_all(&berry).unwrap();
+        let archive = berry.join("debug-npm-4.3.4-abc-10c0.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default();
+        zip.start_file("node_modules/debug/package.json", options)
+            .unwrap();
+        zip.write_all(br#"{"name": "debug", "version": "4.3.4"}"#)
+            .unwrap();
+        zip.start_file("node_modules/debug/src/index.js", options)
+            .unwrap();
+        zip.write_all(PAYLOAD.as_bytes()).unwrap();
+        zip.finish().unwrap();
+
+        let caches = [
+            PackageCache {
+                kind: CacheKind::Npm,
+                path: npm.clone(),
+            },
+            PackageCache {
+                kind: CacheKind::Pnpm,
+                path: pnpm.clone(),
+            },
+            PackageCache {
+                kind: CacheKind::Yarn,
+                path: root.join("yarn"),
+            },
+            PackageCache {
+                kind: CacheKind::YarnBerry,
+                path: berry.clone(),
+            },
+        ];
+        let compromised: HashSet<_> = ["@ctrl/tinycolor:4.1.1", "ngx-bootstrap:19.0.3"]
+            .into_iter()
+            .map(|line| CompromisedPackage::from_line(line).unwrap())
+            .collect();
+        let findings = check_package_caches(
+            &caches,
+            &compromised,
+            &Advisories::default(),
+            &[payload_hash.clone()].into_iter().collect(),
+            &[],
+        );
+
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| {
+                let path = f
+                    .file_path
+                    .strip_prefix(root)
+                    .unwrap()
+                    .display()
+                    .to_string();
+                let message = f.message.split(" - purge: ").next().unwrap().to_string();
+                format!("{path}: {message}")
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "npm/_cacache/index-v5/ab/cd/bucket: Compromised package @ctrl/tinycolor@4.1.1 in npm cache".to_string(),
+                format!("pnpm/store/v3/files/ff/0123-index.json: Cached file matches known malicious hash: evil@1.0.0 index.js (Hash: {payload_hash}) in pnpm store"),
+                "yarn/v6/npm-ngx-bootstrap-19.0.3-abc-integrity: Compromised package ngx-bootstrap@19.0.3 in yarn cache".to_string(),
+                format!("berry/cache/debug-npm-4.3.4-abc-10c0.zip: Cached file matches known malicious hash: debug@4.3.4 node_modules/debug/src/index.js (Hash: {payload_hash}) in yarn berry cache"),
+            ]
+        );
+        assert!(findings[0].message.ends_with("then run `npm cache verify`"));
+        assert!(findings.iter().all(|f| f.risk_level == RiskLevel::High));
+    }
+}
@@ -1,17 +1,15 @@
 // Content Detector
 // Rust port of: check_content()
 
-use crate::detectors::{Finding, RiskLevel};
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 
 // Function: check_content
 // Purpose: Search for suspicious content patterns like webhook.site and malicious endpoints
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: SUSPICIOUS_CONTENT (global array)
 // Returns: Populates SUSPICIOUS_CONTENT array with files containing suspicious patterns
-pub fn check_content<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_content(index: &FileIndex) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for suspicious content patterns...",
@@ -20,18 +18,8 @@ pub fn check_content<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
     let mut findings = Vec::new();
     let extensions = &["js", "ts", "json", "yml", "yaml"];
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-    {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+    for entry in index.files_with_extensions(extensions) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             // Search for webhook.site references
             if content.contains("webhook.site") {
                 findings.push(Finding::new(
@@ -56,3 +44,24 @@ pub fn check_content<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_content()`
+pub struct ContentDetector;
+
+impl Detector for ContentDetector {
+    fn id(&self) -> &'static str {
+        "suspicious_content"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suspicious content patterns (webhook.site, known endpoints)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_content(ctx.index)
+    }
+}
@@ -5,13 +5,12 @@
 // Each check writes to the same file, so a file can have multiple findings.
 // ONLY the last check (Ethereum wallet patterns) skips already-flagged files.
 
-use crate::detectors::{verification, Finding, RiskLevel};
+use crate::data::VerifiedFile;
+use crate::detectors::{verification, Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use regex::Regex;
 use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
 use std::sync::LazyLock;
-use walkdir::WalkDir;
 
 static ETH_WALLET: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"0x[a-fA-F0-9]{40}").unwrap());
 static KNOWN_WALLETS: LazyLock<Regex> = LazyLock::new(|| {
@@ -27,8 +26,13 @@ static MALICIOUS_FUNCTIONS: LazyLock<Regex> =
 /// Matches bash `check_crypto_theft_patterns()` exactly:
 /// - NO deduplication between checks (file can have multiple findings)
 /// - ONLY the last check (Ethereum wallet) skips already-flagged files
+///
+/// `verified_files` are reviewed safe files (data/iocs.toml) used to annotate XHR findings
 #[allow(clippy::too_many_lines)]
-pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_crypto_theft_patterns(
+    index: &FileIndex,
+    verified_files: &[VerifiedFile],
+) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "🔍 Checking for cryptocurrency theft patterns...",
@@ -39,25 +43,15 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
     let extensions = &["js", "ts", "json"];
 
     // Collect all code files first
-    let code_files: Vec<_> = WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-        .collect();
+    let code_files: Vec<_> = index.files_with_extensions(extensions).collect();
 
     // BASH ORDER: Check patterns in same order as bash scanner
     // NO DEDUPLICATION between checks 1-5!
 
     // 1. Check for specific malicious functions from chalk/debug attack (highest priority)
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if MALICIOUS_FUNCTIONS.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if MALICIOUS_FUNCTIONS.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Known crypto theft function names detected".to_string(),
@@ -72,8 +66,8 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
 
     // 2. Check for known attacker wallets (high priority)
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if KNOWN_WALLETS.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if KNOWN_WALLETS.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Known attacker wallet address detected - HIGH RISK".to_string(),
@@ -87,7 +81,7 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
 
     // 3. Check for npmjs.help phishing domain
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             if content.contains("npmjs.help") {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
@@ -102,7 +96,7 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
 
     // 4. Check for XMLHttpRequest hijacking (medium priority)
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             if content.contains("XMLHttpRequest.prototype.send") {
                 let path_str = entry.path().to_string_lossy().to_string();
                 let is_framework = path_str.contains("/react-native/Libraries/Network/")
@@ -110,7 +104,7 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
                     || path_str.contains("/next/dist/compiled/")
                     || path_str.contains("\\next\\dist\\compiled\\");
 
-                let has_crypto = ETH_WALLET.is_match(&content)
+                let has_crypto = ETH_WALLET.is_match(content)
                     || content.contains("checkethereumw")
                     || content.contains("runmask")
                     || content.contains("webhook.site")
@@ -150,7 +144,8 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
                         RiskLevel::Medium,
                         "crypto_xhr_simple",
                     );
-                    let hash_verification = verification::verify_file_by_hash(entry.path());
+                    let hash_verification =
+                        verification::verify_file_by_hash(entry.path(), verified_files);
                     if let verification::VerificationStatus::Verified { .. } = hash_verification {
                         finding.verification = Some(hash_verification);
                     }
@@ -163,7 +158,7 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
 
     // 5. Check for javascript obfuscation
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             if content.contains("javascript-obfuscator") {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
@@ -185,8 +180,8 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
         if flagged_files.contains(&path_key) {
             continue;
         }
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if ETH_WALLET.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if ETH_WALLET.is_match(content) {
                 // Check for crypto-related context keywords
                 let content_lower = content.to_lowercase();
                 if content_lower.contains("ethereum")
@@ -207,3 +202,24 @@ pub fn check_crypto_theft_patterns<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding>
 
     findings
 }
+
+/// Registry entry for `check_crypto_theft_patterns()`
+pub struct CryptoPatternsDetector;
+
+impl Detector for CryptoPatternsDetector {
+    fn id(&self) -> &'static str {
+        "crypto_patterns"
+    }
+
+    fn description(&self) -> &'static str {
+        "Cryptocurrency theft patterns"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_crypto_theft_patterns(ctx.index, &ctx.iocs.verified_files)
+    }
+}
@@ -9,19 +9,17 @@
 // - Removed standalone glob patterns ($HOME/*, ~/*) that matched path examples in comments
 // - Single-pass search to avoid catastrophic backtracking on minified files
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
+use crate::file_index::FileIndex;
 use regex::Regex;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: check_destructive_patterns
 // Purpose: Detect destructive patterns that can cause data loss when credential theft fails
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with paths to files containing destructive patterns
 #[allow(clippy::too_many_lines)]
-pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_destructive_patterns(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "   Checking for destructive payload patterns...",
@@ -127,17 +125,9 @@ pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
 
     let mut file_count = 0;
     for ext in &file_extensions {
-        for entry in WalkDir::new(scan_dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
+        for entry in index.files() {
             let path = entry.path();
 
-            if !path.is_file() {
-                continue;
-            }
-
             // Match file extension
             let path_str = path.to_string_lossy();
             let ext_pattern = ext.trim_start_matches('*');
@@ -152,14 +142,14 @@ pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
             }
 
             // Read file content
-            if let Ok(content) = fs::read_to_string(path) {
+            if let Some(content) = index.read_text(entry).as_deref() {
                 // BASH EXACT: Only report ONCE per file per category (basic or conditional)
                 // Bash uses `grep -l` which outputs filename once per match, not per pattern
 
                 // Check basic destructive patterns (targeting user directories only)
                 let mut found_basic = false;
                 for (regex, _desc) in &basic_regexes {
-                    if regex.is_match(&content) {
+                    if regex.is_match(content) {
                         found_basic = true;
                         break; // Stop after first match
                     }
@@ -182,7 +172,7 @@ pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
                 {
                     // Shell scripts: Use broader patterns
                     for (regex, _desc) in &conditional_shell_regexes {
-                        if regex.is_match(&content) {
+                        if regex.is_match(content) {
                             found_conditional = true;
                             break;
                         }
@@ -190,7 +180,7 @@ pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
                 } else if path_str.ends_with(".js") || path_str.ends_with(".py") {
                     // v3.0.2: JavaScript/Python - Use Shai-Hulud 2.0 wiper signatures
                     for (regex, _desc) in &wiper_regexes {
-                        if regex.is_match(&content) {
+                        if regex.is_match(content) {
                             found_conditional = true;
                             break;
                         }
@@ -217,3 +207,24 @@ pub fn check_destructive_patterns(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_destructive_patterns()`
+pub struct DestructivePatternsDetector;
+
+impl Detector for DestructivePatternsDetector {
+    fn id(&self) -> &'static str {
+        "destructive_patterns"
+    }
+
+    fn description(&self) -> &'static str {
+        "Destructive payload patterns"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_destructive_patterns(ctx.index)
+    }
+}
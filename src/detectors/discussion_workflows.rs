@@ -4,18 +4,16 @@
 // Corresponds to bash function:
 // - check_discussion_workflows() - Lines 373-401 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
+use crate::file_index::FileIndex;
 use regex::Regex;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: check_discussion_workflows
 // Purpose: Detect malicious GitHub Actions workflows with discussion triggers
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with paths to suspicious discussion-triggered workflows
-pub fn check_discussion_workflows(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_discussion_workflows(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for malicious discussion workflows...",
@@ -31,18 +29,10 @@ pub fn check_discussion_workflows(scan_dir: &Path) -> Vec<Finding> {
     let self_hosted_runner = Regex::new(r"runs-on:.*self-hosted").unwrap();
     let dynamic_payload = Regex::new(r"\$\{\{ github\.event\..*\.body \}\}").unwrap();
 
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.files() {
         let path = entry.path();
 
         // Look for .yml/.yaml files in .github/workflows/ directories
-        if !path.is_file() {
-            continue;
-        }
-
         let is_workflow = path
             .parent()
             .is_some_and(|p| p.ends_with(".github/workflows"));
@@ -61,9 +51,9 @@ pub fn check_discussion_workflows(scan_dir: &Path) -> Vec<Finding> {
         }
 
         // Read file content for pattern matching
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             // Check for discussion-based triggers
-            if discussion_trigger.is_match(&content) {
+            if discussion_trigger.is_match(content) {
                 findings.push(Finding::new(
                     path.to_path_buf(),
                     "Discussion trigger detected".to_string(),
@@ -73,7 +63,7 @@ pub fn check_discussion_workflows(scan_dir: &Path) -> Vec<Finding> {
             }
 
             // Check for self-hosted runners combined with dynamic payload execution
-            if self_hosted_runner.is_match(&content) && dynamic_payload.is_match(&content) {
+            if self_hosted_runner.is_match(content) && dynamic_payload.is_match(content) {
                 findings.push(Finding::new(
                     path.to_path_buf(),
                     "Self-hosted runner with dynamic payload execution".to_string(),
@@ -96,3 +86,24 @@ pub fn check_discussion_workflows(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_discussion_workflows()`
+pub struct DiscussionWorkflowsDetector;
+
+impl Detector for DiscussionWorkflowsDetector {
+    fn id(&self) -> &'static str {
+        "discussion_workflows"
+    }
+
+    fn description(&self) -> &'static str {
+        "Malicious discussion-triggered GitHub workflows"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_discussion_workflows(ctx.index)
+    }
+}
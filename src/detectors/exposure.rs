@@ -0,0 +1,326 @@
+// Range Exposure - which compromised versions a dependency specifier admits, and the fix
+// Purpose: For one package.json specifier, list the compromised versions it could install,
+//          whether the lockfile pins one of them, the lowest version above all of them and
+//          a safe range / overrides entry that excludes every compromised version
+//          (used by --check-semver-ranges; Rust-only, no Bash equivalent)
+//
+// Example (@ctrl/tinycolor 4.1.1 and 4.1.2 compromised):
+//   "^4.1.0" admits 4.1.1, 4.1.2 -> minimum safe 4.1.3, safe range "^4.1.3"
+//   "<4.1.5" admits 4.1.1, 4.1.2 -> safe range ">=4.1.3 <4.1.5"
+//   "latest" admits every version -> safe range ">=4.1.3"
+
+use crate::data::CompromisedPackage;
+use crate::detectors::lockfile_resolver::LockfileType;
+use crate::semver::{Comparator, Identifier, Operator, Range, Version};
+use std::collections::HashSet;
+use std::fmt;
+
+/// package.json field that forces a transitive version, per package manager
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OverrideField {
+    /// npm and bun: "overrides"
+    Overrides,
+    /// yarn: "resolutions"
+    Resolutions,
+    /// pnpm: "pnpm": { "overrides" }
+    PnpmOverrides,
+}
+
+impl OverrideField {
+    /// Field understood by the package manager owning the lockfile (npm without one)
+    pub fn for_lockfile(lockfile_type: Option<LockfileType>) -> Self {
+        match lockfile_type {
+            Some(LockfileType::Yarn | LockfileType::YarnBerry) => Self::Resolutions,
+            Some(LockfileType::Pnpm) => Self::PnpmOverrides,
+            Some(LockfileType::Npm | LockfileType::Bun) | None => Self::Overrides,
+        }
+    }
+
+    /// JSON path of the field inside package.json
+    pub fn path(self) -> &'static [&'static str] {
+        match self {
+            Self::Overrides => &["overrides"],
+            Self::Resolutions => &["resolutions"],
+            Self::PnpmOverrides => &["pnpm", "overrides"],
+        }
+    }
+}
+
+/// Exposure of one dependency specifier to the compromised versions of its package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RangeExposure {
+    pub name: String,
+    pub specifier: String,
+    /// Compromised versions the specifier admits, ascending
+    pub admitted: Vec<Version>,
+    /// Versions the project lockfile pins (None without a lockfile)
+    pub locked: Option<Vec<String>>,
+    /// Locked versions that are compromised
+    pub locked_compromised: Vec<String>,
+    /// Lowest version above every admitted compromised version that is not compromised itself
+    pub minimum_safe: Version,
+    /// Specifier admitting no compromised version ("^4.1.3")
+    pub safe_range: String,
+    /// Version for an overrides/resolutions entry: the locked safe version if the
+    /// specifier admits it, the safe range otherwise
+    pub override_version: String,
+}
+
+impl RangeExposure {
+    // Function: override_entry
+    // Purpose: package.json snippet that keeps every copy of the package off the compromised set
+    // Args: field - overrides field of the project's package manager
+    // Returns: e.g. "overrides": { "@ctrl/tinycolor": "4.1.0" }
+    pub fn override_entry(&self, field: OverrideField) -> String {
+        let entry = format!("{{ \"{}\": \"{}\" }}", self.name, self.override_version);
+        match field {
+            OverrideField::Overrides => format!("\"overrides\": {entry}"),
+            OverrideField::Resolutions => format!("\"resolutions\": {entry}"),
+            OverrideField::PnpmOverrides => format!("\"pnpm\": {{ \"overrides\": {entry} }}"),
+        }
+    }
+}
+
+impl fmt::Display for RangeExposure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let admitted: Vec<String> = self.admitted.iter().map(ToString::to_string).collect();
+        write!(
+            f,
+            "{}@{} (could match {})",
+            self.name,
+            self.specifier,
+            admitted.join(", ")
+        )?;
+        match &self.locked {
+            Some(locked) if !self.locked_compromised.is_empty() => write!(
+                f,
+                ", locked to {} (inside the compromised set)",
+                locked.join(", ")
+            )?,
+            Some(locked) => write!(
+                f,
+                ", locked to {} (outside the compromised set)",
+                locked.join(", ")
+            )?,
+            None => write!(
+                f,
+                " (no lockfile, could resolve to {})",
+                admitted.join(", ")
+            )?,
+        }
+        write!(
+            f,
+            "; minimum safe version {}, safe range \"{}\"",
+            self.minimum_safe, self.safe_range
+        )
+    }
+}
+
+// Function: analyze_specifier
+// Purpose: Exposure of a package.json specifier to the known compromised versions
+// Args: name - package name, specifier - node-semver range or dist-tag,
+//       compromised_packages - known bad versions, locked - versions pinned by the
+//       project lockfile (None without a lockfile)
+// Returns: RangeExposure, None if the specifier admits no compromised version or is not
+//          a range / dist-tag (git, file and alias specifiers)
+pub fn analyze_specifier(
+    name: &str,
+    specifier: &str,
+    compromised_packages: &HashSet<CompromisedPackage>,
+    locked: Option<Vec<String>>,
+) -> Option<RangeExposure> {
+    // Dist-tags can point at any published version
+    let range = match Range::parse(specifier) {
+        Some(range) => range,
+        None if crate::semver::is_dist_tag(specifier) => Range::parse("*")?,
+        None => return None,
+    };
+    let mut compromised: Vec<Version> = compromised_packages
+        .iter()
+        .filter(|c| c.name == name)
+        .filter_map(|c| Version::parse(&c.version))
+        .collect();
+    compromised.sort();
+
+    let admitted: Vec<Version> = compromised
+        .iter()
+        .filter(|version| range.satisfies(version))
+        .cloned()
+        .collect();
+    let highest = admitted.last()?;
+
+    // Next release above the highest admitted version, skipping compromised ones
+    let mut minimum_safe = if highest.is_prerelease() {
+        Version::new(highest.major, highest.minor, highest.patch)
+    } else {
+        Version::new(highest.major, highest.minor, highest.patch + 1)
+    };
+    while compromised.contains(&minimum_safe) {
+        minimum_safe.patch += 1;
+    }
+
+    // Keep the specifier's upper bound where possible, otherwise move past it
+    let safe_range = range
+        .at_least(&minimum_safe)
+        .map_or_else(|| format!("^{minimum_safe}"), |range| shorthand(&range));
+
+    let is_compromised = |version: &str| {
+        Version::parse(version).is_some_and(|version| compromised.contains(&version))
+    };
+    let locked_compromised: Vec<String> = locked
+        .iter()
+        .flatten()
+        .filter(|version| is_compromised(version))
+        .cloned()
+        .collect();
+    let locked_safe = locked
+        .iter()
+        .flatten()
+        .filter_map(|version| Version::parse(version))
+        .filter(|version| !compromised.contains(version) && range.satisfies(version))
+        .max();
+    let override_version = locked_safe.map_or_else(|| safe_range.clone(), |v| v.to_string());
+
+    Some(RangeExposure {
+        name: name.to_string(),
+        specifier: specifier.to_string(),
+        admitted,
+        locked,
+        locked_compromised,
+        minimum_safe,
+        safe_range,
+        override_version,
+    })
+}
+
+// Helper: Write a desugared range the way people write package.json
+// (">=4.1.3 <5.0.0-0" -> "^4.1.3", ">=4.1.3 <4.2.0-0" -> "~4.1.3")
+fn shorthand(range: &Range) -> String {
+    let sets: Vec<String> = range
+        .sets
+        .iter()
+        .map(|set| {
+            if let [Comparator {
+                operator: Operator::Ge,
+                version: low,
+            }, Comparator {
+                operator: Operator::Lt,
+                version: high,
+            }] = set.as_slice()
+            {
+                if !low.is_prerelease() && is_zero_prerelease(high) {
+                    let high = Version::new(high.major, high.minor, high.patch);
+                    let caret = match (low.major, low.minor) {
+                        (0, 0) => Version::new(0, 0, low.patch + 1),
+                        (0, minor) => Version::new(0, minor + 1, 0),
+                        (major, _) => Version::new(major + 1, 0, 0),
+                    };
+                    if high == caret {
+                        return format!("^{low}");
+                    }
+                    if high == Version::new(low.major, low.minor + 1, 0) {
+                        return format!("~{low}");
+                    }
+                }
+            }
+            let comparators: Vec<String> = set
+                .iter()
+                .map(|c| match c.operator {
+                    // "<2.0.0-0" reads as "<2.0.0" (2.0.0 prereleases are excluded anyway)
+                    Operator::Lt if is_zero_prerelease(&c.version) => {
+                        format!(
+                            "<{}",
+                            Version::new(c.version.major, c.version.minor, c.version.patch)
+                        )
+                    }
+                    _ => c.to_string(),
+                })
+                .collect();
+            if comparators.is_empty() {
+                "*".to_string()
+            } else {
+                comparators.join(" ")
+            }
+        })
+        .collect();
+    sets.join(" || ")
+}
+
+// Helper: "2.0.0-0", the lowest version of a release
+fn is_zero_prerelease(version: &Version) -> bool {
+    version.prerelease == [Identifier::Numeric(0)]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn compromised() -> HashSet<CompromisedPackage> {
+        [
+            "@ctrl/tinycolor:4.1.1",
+            "@ctrl/tinycolor:4.1.2",
+            "debug:4.4.2",
+        ]
+        .iter()
+        .filter_map(|line| CompromisedPackage::from_line(line))
+        .collect()
+    }
+
+    fn safe_range(specifier: &str) -> Option<String> {
+        analyze_specifier("@ctrl/tinycolor", specifier, &compromised(), None).map(|e| e.safe_range)
+    }
+
+    #[test]
+    fn test_safe_range_per_specifier_type() {
+        assert_eq!(safe_range("^4.1.0").as_deref(), Some("^4.1.3"));
+        assert_eq!(safe_range("~4.1.0").as_deref(), Some("~4.1.3"));
+        assert_eq!(safe_range("4.1.x").as_deref(), Some("~4.1.3"));
+        assert_eq!(
+            safe_range(">=4.0.0 <4.1.5").as_deref(),
+            Some(">=4.1.3 <4.1.5")
+        );
+        assert_eq!(safe_range(">=4.0.0 <4.1.2").as_deref(), Some("^4.1.3"));
+        assert_eq!(safe_range("4.1.1").as_deref(), Some("^4.1.3"));
+        assert_eq!(safe_range("latest").as_deref(), Some(">=4.1.3"));
+        assert_eq!(safe_range("3.x || ^4.1.1").as_deref(), Some("^4.1.3"));
+        assert_eq!(safe_range("^3.0.0"), None);
+        assert_eq!(safe_range("github:bahmutov/tinycolor"), None);
+    }
+
+    #[test]
+    fn test_exposure_with_lockfile() {
+        let locked = |versions: &[&str]| Some(versions.iter().map(|v| v.to_string()).collect());
+        let exposure = analyze_specifier(
+            "@ctrl/tinycolor",
+            "^4.0.0",
+            &compromised(),
+            locked(&["4.1.0"]),
+        )
+        .unwrap();
+        assert_eq!(exposure.admitted.len(), 2);
+        assert!(exposure.locked_compromised.is_empty());
+        assert_eq!(exposure.override_version, "4.1.0");
+        assert_eq!(
+            exposure.to_string(),
+            "@ctrl/tinycolor@^4.0.0 (could match 4.1.1, 4.1.2), locked to 4.1.0 (outside the compromised set); minimum safe version 4.1.3, safe range \"^4.1.3\""
+        );
+        assert_eq!(
+            exposure.override_entry(OverrideField::PnpmOverrides),
+            "\"pnpm\": { \"overrides\": { \"@ctrl/tinycolor\": \"4.1.0\" } }"
+        );
+
+        let exposure = analyze_specifier(
+            "@ctrl/tinycolor",
+            "^4.0.0",
+            &compromised(),
+            locked(&["4.1.2"]),
+        )
+        .unwrap();
+        assert_eq!(exposure.locked_compromised, vec!["4.1.2"]);
+        assert_eq!(exposure.override_version, "^4.1.3");
+        assert_eq!(
+            exposure.override_entry(OverrideField::Resolutions),
+            "\"resolutions\": { \"@ctrl/tinycolor\": \"^4.1.3\" }"
+        );
+    }
+}
@@ -1,17 +1,15 @@
 // Git Branches Detector
 // Rust port of: check_git_branches()
 
-use crate::detectors::{Finding, RiskLevel};
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 
 // Function: check_git_branches
 // Purpose: Search for suspicious git branches containing "shai-hulud" in their names
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: GIT_BRANCHES (global array)
 // Returns: Populates GIT_BRANCHES array with branch names and commit hashes
-pub fn check_git_branches<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_git_branches(index: &FileIndex) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for suspicious git branches...",
@@ -19,24 +17,20 @@ pub fn check_git_branches<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     let mut findings = Vec::new();
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_dir() && e.file_name() == ".git")
-    {
+    for entry in index.dirs_named(".git") {
         let repo_dir = entry.path().parent().unwrap_or(entry.path());
         let refs_heads = entry.path().join("refs/heads");
 
         if refs_heads.exists() {
-            for branch_entry in WalkDir::new(&refs_heads)
-                .max_depth(1)
-                .into_iter()
-                .filter_map(std::result::Result::ok)
-                .filter(|e| e.file_type().is_file())
+            for branch_entry in index
+                .files()
+                .filter(|e| e.path().parent() == Some(refs_heads.as_path()))
             {
-                let branch_name = branch_entry.file_name().to_string_lossy();
+                let branch_name = branch_entry.file_name();
                 if branch_name.to_lowercase().contains("shai-hulud") {
-                    let commit_hash = fs::read_to_string(branch_entry.path())
+                    let commit_hash = index
+                        .read_text(branch_entry)
+                        .as_deref()
                         .unwrap_or_default()
                         .trim()
                         .to_string();
@@ -58,3 +52,24 @@ pub fn check_git_branches<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_git_branches()`
+pub struct GitBranchesDetector;
+
+impl Detector for GitBranchesDetector {
+    fn id(&self) -> &'static str {
+        "git_branches"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suspicious shai-hulud git branches"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_git_branches(ctx.index)
+    }
+}
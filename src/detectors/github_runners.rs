@@ -4,17 +4,17 @@
 // Corresponds to bash function:
 // - check_github_runners() - Lines 403-457 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
+use crate::file_index::FileIndex;
 use std::env;
-use std::path::{Path, PathBuf};
-use walkdir::WalkDir;
+use std::path::PathBuf;
 
 // Function: check_github_runners
 // Purpose: Detect self-hosted GitHub Actions runners installed by malware
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with paths to suspicious runner installations
-pub fn check_github_runners(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_github_runners(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for malicious GitHub Actions runners...",
@@ -26,17 +26,9 @@ pub fn check_github_runners(scan_dir: &Path) -> Vec<Finding> {
     let runner_patterns = [".dev-env", "actions-runner", ".runner", "_work"];
 
     for pattern in &runner_patterns {
-        for entry in WalkDir::new(scan_dir)
-            .follow_links(false)
-            .into_iter()
-            .filter_map(std::result::Result::ok)
-        {
+        for entry in index.dirs() {
             let path = entry.path();
 
-            if !path.is_dir() {
-                continue;
-            }
-
             // Check if directory name matches pattern
             if let Some(dirname) = path.file_name().and_then(|n| n.to_str()) {
                 if dirname != *pattern {
@@ -101,3 +93,24 @@ pub fn check_github_runners(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_github_runners()`
+pub struct GithubRunnersDetector;
+
+impl Detector for GithubRunnersDetector {
+    fn id(&self) -> &'static str {
+        "github_runners"
+    }
+
+    fn description(&self) -> &'static str {
+        "Self-hosted GitHub Actions runners (persistent backdoors)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_github_runners(ctx.index)
+    }
+}
@@ -1,48 +1,33 @@
 // File Hashes Detector
 // Rust port of: check_file_hashes()
 
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use rayon::prelude::*;
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: check_file_hashes
 // Purpose: Scan files and compare SHA256 hashes against known malicious hash list
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: MALICIOUS_HASHES (global array)
 // Returns: Populates MALICIOUS_HASHES array with "file:hash" entries for matches
-pub fn check_file_hashes<P: AsRef<Path>>(
-    scan_dir: P,
+pub fn check_file_hashes(
+    index: &FileIndex,
     malicious_hashes: &HashSet<String>,
     parallelism: usize,
 ) -> Vec<Finding> {
-    let scan_dir = scan_dir.as_ref();
     let extensions = &["js", "ts", "json"];
 
-    let files_count = crate::utils::count_files(scan_dir, extensions);
+    // Collect all files to process
+    let files: Vec<_> = index.files_with_extensions(extensions).collect();
+    let files_count = files.len();
 
     crate::colors::print_status(
         crate::colors::Color::Blue,
         &format!("Checking {files_count} files for known malicious content..."),
     );
 
-    // Collect all files to process
-    let files: Vec<_> = WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-        .map(|e| e.path().to_path_buf())
-        .collect();
-
     // Configure rayon thread pool
     let pool = rayon::ThreadPoolBuilder::new()
         .num_threads(parallelism)
@@ -54,19 +39,19 @@ pub fn check_file_hashes<P: AsRef<Path>>(
         files
             .par_iter()
             .enumerate()
-            .filter_map(|(idx, path)| {
+            .filter_map(|(idx, entry)| {
                 // Show progress (not thread-safe, but close enough for user feedback)
                 if idx % 100 == 0 {
                     crate::utils::show_progress(idx, files_count);
                 }
 
-                if let Ok(content) = fs::read(path) {
-                    let hash = format!("{:x}", Sha256::digest(&content));
+                if let Some(content) = index.read(entry) {
+                    let hash = format!("{:x}", Sha256::digest(content.bytes()));
 
                     // Check for malicious files
                     if malicious_hashes.contains(&hash) {
                         return Some(Finding::new(
-                            path.clone(),
+                            entry.path().to_path_buf(),
                             format!("Hash: {hash}"),
                             RiskLevel::High,
                             "malicious_hash",
@@ -83,6 +68,27 @@ pub fn check_file_hashes<P: AsRef<Path>>(
     findings
 }
 
+/// Registry entry for `check_file_hashes()`
+pub struct FileHashesDetector;
+
+impl Detector for FileHashesDetector {
+    fn id(&self) -> &'static str {
+        "malicious_hashes"
+    }
+
+    fn description(&self) -> &'static str {
+        "Files matching known malicious SHA-256 hashes"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_file_hashes(ctx.index, ctx.malicious_hashes, ctx.parallelism)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -103,7 +109,7 @@ mod tests {
         let test_hash = "6ae8a75555209fd6c44157c0aed8016e763ff435a19cf186f76863140143ff72";
         hashes.insert(test_hash.to_string());
 
-        let findings = check_file_hashes(temp.path(), &hashes, 1);
+        let findings = check_file_hashes(&FileIndex::build(temp.path(), 1), &hashes, 1);
 
         assert_eq!(findings.len(), 1);
         assert_eq!(findings[0].risk_level, RiskLevel::High);
@@ -4,12 +4,19 @@
 #![allow(dead_code)]
 
 use crate::data::CompromisedPackage;
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::bun_lockfile::{decode_bun_lockb, decode_with_bun, parse_bun_lock};
+use crate::detectors::pnpm_lockfile::PnpmLockfile;
+use crate::detectors::yarn_lockfile::{
+    is_berry_lockfile, parse_berry_lockfile, parse_classic_lockfile,
+};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::{FileIndex, IndexEntry};
+use crate::osv::{self, Advisories};
+use anyhow::Context;
 use serde_json::Value;
 use std::collections::HashSet;
 use std::fs;
 use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: get_lockfile_version
 // Purpose: Get actual installed version of a package from lockfile
@@ -50,9 +57,10 @@ pub fn get_lockfile_version(package_name: &str, package_dir: &Path) -> Option<St
 
 /// Verify package lock files for compromised packages and version integrity
 /// Rust port of: `check_package_integrity()`
-pub fn check_package_integrity<P: AsRef<Path>>(
-    scan_dir: P,
+pub fn check_package_integrity(
+    index: &FileIndex,
     compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
 ) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
@@ -62,70 +70,253 @@ pub fn check_package_integrity<P: AsRef<Path>>(
     let mut findings = Vec::new();
 
     // Check package-lock.json, yarn.lock, pnpm-lock.yaml
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            let filename = e.file_name().to_string_lossy();
-            filename == "package-lock.json"
-                || filename == "yarn.lock"
-                || filename == "pnpm-lock.yaml"
-        })
-    {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+    for entry in index.files().filter(|e| {
+        let filename = e.file_name();
+        filename == "package-lock.json" || filename == "yarn.lock" || filename == "pnpm-lock.yaml"
+    }) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             // For JSON lockfiles
             if entry.file_name() == "package-lock.json" {
-                if let Ok(json) = serde_json::from_str::<Value>(&content) {
-                    check_json_lockfile(entry.path(), &json, compromised_packages, &mut findings);
+                if let Ok(json) = serde_json::from_str::<Value>(content) {
+                    check_json_lockfile(
+                        entry.path(),
+                        &json,
+                        compromised_packages,
+                        advisories,
+                        &mut findings,
+                    );
                 }
+            } else {
+                check_locked_packages(
+                    entry,
+                    locked_packages(entry.file_name(), content),
+                    compromised_packages,
+                    advisories,
+                    &mut findings,
+                );
             }
+        }
+    }
 
-            // Check for @ctrl packages (potential worm activity)
-            // BASH EXACT: No time check - just check if @ctrl is present
-            if content.contains("@ctrl") {
+    // Bun lockfiles (not checked by the Bash scanner): compromised versions only
+    for entry in index
+        .files()
+        .filter(|e| e.file_name() == "bun.lock" || e.file_name() == "bun.lockb")
+    {
+        let locked = if entry.file_name() == "bun.lock" {
+            index
+                .read_text(entry)
+                .context("not valid UTF-8")
+                .and_then(|content| locked_packages("bun.lock", &content))
+        } else if decode_with_bun() {
+            bun_lockb_packages(entry.path())
+        } else {
+            // bun prefers a text bun.lock next to it, which is checked above
+            if !entry.path().with_file_name("bun.lock").exists() {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
-                    "Lockfile contains @ctrl packages (potential worm activity)".to_string(),
-                    RiskLevel::Medium,
-                    "integrity",
+                    "Binary bun.lockb not verified - re-run with --decode-bun-lockb (runs bun) or convert it with `bun install --save-text-lockfile`".to_string(),
+                    RiskLevel::Low,
+                    "lockfile_not_verified",
                 ));
             }
-        }
+            continue;
+        };
+        check_locked_packages(
+            entry,
+            locked,
+            compromised_packages,
+            advisories,
+            &mut findings,
+        );
     }
 
     findings
 }
 
+/// Registry entry for `check_package_integrity()`
+pub struct PackageIntegrityDetector;
+
+impl Detector for PackageIntegrityDetector {
+    fn id(&self) -> &'static str {
+        "integrity_issues"
+    }
+
+    fn description(&self) -> &'static str {
+        "Package lockfile integrity issues"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn results_key(&self, finding: &Finding) -> &'static str {
+        if finding.category == "lockfile_not_verified" {
+            "unverified_lockfiles"
+        } else {
+            self.id()
+        }
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_package_integrity(ctx.index, ctx.compromised_packages, ctx.advisories)
+    }
+}
+
+/// A package version pinned by a lockfile, with the entry that pins it
+struct LockedPackage {
+    name: String,
+    version: String,
+    /// Lockfile entry as written, e.g. "debug@^4.3.4" (yarn) or "/debug@4.3.4" (pnpm)
+    entry: String,
+}
+
+// Function: locked_packages
+// Purpose: Structurally parse yarn.lock (v1/Berry), pnpm-lock.yaml and bun.lock content
+// Args: file_name - lockfile name, content - lockfile text
+// Returns: Every pinned package version with its lockfile entry
+fn locked_packages(file_name: &str, content: &str) -> anyhow::Result<Vec<LockedPackage>> {
+    let locked = match file_name {
+        "pnpm-lock.yaml" => PnpmLockfile::parse(content)?
+            .packages
+            .into_iter()
+            .map(|p| LockedPackage {
+                name: p.name,
+                version: p.version,
+                entry: p.key,
+            })
+            .collect(),
+        "yarn.lock" if is_berry_lockfile(content) => parse_berry_lockfile(content)?
+            .into_iter()
+            .filter(|p| !p.is_local())
+            .map(|p| LockedPackage {
+                entry: p.descriptors.join(", "),
+                name: p.name,
+                version: p.version,
+            })
+            .collect(),
+        "yarn.lock" => parse_classic_lockfile(content)
+            .into_iter()
+            .map(|p| LockedPackage {
+                entry: p.descriptors.join(", "),
+                name: p.name,
+                version: p.version,
+            })
+            .collect(),
+        "bun.lock" => parse_bun_lock(content)?
+            .into_iter()
+            .map(|p| LockedPackage {
+                name: p.name,
+                version: p.version,
+                entry: p.key,
+            })
+            .collect(),
+        _ => anyhow::bail!("unsupported lockfile {file_name}"),
+    };
+    Ok(locked)
+}
+
+// Function: check_locked_packages
+// Purpose: Report lockfile entries that pin a compromised version (or one an OSV advisory covers)
+// Args: entry - the lockfile, locked - parsed entries (errors print a warning),
+//       compromised_packages, advisories, findings - output
+fn check_locked_packages(
+    entry: &IndexEntry,
+    locked: anyhow::Result<Vec<LockedPackage>>,
+    compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
+    findings: &mut Vec<Finding>,
+) {
+    let locked = match locked {
+        Ok(locked) => locked,
+        Err(e) => {
+            crate::colors::print_status(
+                crate::colors::Color::Yellow,
+                &format!("⚠️  Skipping {}: {e}", entry.path().display()),
+            );
+            return;
+        }
+    };
+
+    for package in locked {
+        let references = advisories.references(&package.name, &package.version);
+        let key = CompromisedPackage::new(package.name.clone(), package.version.clone());
+        if compromised_packages.contains(&key) || references.is_some() {
+            findings.push(Finding::new(
+                entry.path().to_path_buf(),
+                osv::with_references(
+                    format!(
+                        "Compromised package in lockfile: {}@{} (lockfile entry: {})",
+                        package.name, package.version, package.entry
+                    ),
+                    references,
+                ),
+                RiskLevel::Medium,
+                "integrity",
+            ));
+        }
+    }
+}
+
+// Function: bun_lockb_packages
+// Purpose: Decode a binary bun.lockb (needs the bun CLI) into pinned packages
+// Args: path - bun.lockb on disk
+// Returns: Pinned packages with their yarn-style entries
+fn bun_lockb_packages(path: &Path) -> anyhow::Result<Vec<LockedPackage>> {
+    Ok(parse_classic_lockfile(&decode_bun_lockb(path)?)
+        .into_iter()
+        .map(|p| LockedPackage {
+            entry: p.descriptors.join(", "),
+            name: p.name,
+            version: p.version,
+        })
+        .collect())
+}
+
 fn check_json_lockfile(
     path: &Path,
     json: &Value,
     compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
     findings: &mut Vec<Finding>,
 ) {
+    let report = |pkg_name: &str, version: &str, entry: &str, findings: &mut Vec<Finding>| {
+        let references = advisories.references(pkg_name, version);
+        let listed = compromised_packages
+            .iter()
+            .any(|comp_pkg| comp_pkg.name == pkg_name && comp_pkg.version == version);
+        if listed || references.is_some() {
+            findings.push(Finding::new(
+                path.to_path_buf(),
+                osv::with_references(
+                    format!(
+                        "Compromised package in lockfile: {pkg_name}@{version} (lockfile entry: {entry})"
+                    ),
+                    references,
+                ),
+                RiskLevel::Medium,
+                "integrity",
+            ));
+        }
+    };
+
     // BASH EXACT: NO deduplication! Bash outputs once per section (packages + dependencies)
     // So if a package appears in both sections, it gets reported twice
 
     // Check "packages" section (npm lockfile v2+)
     if let Some(packages) = json.get("packages").and_then(|p| p.as_object()) {
         for (pkg_path, pkg_data) in packages {
-            // Extract package name from node_modules path
-            if let Some(pkg_name) = pkg_path.strip_prefix("node_modules/") {
-                if let Some(version) = pkg_data.get("version").and_then(|v| v.as_str()) {
-                    // Check against compromised packages
-                    for comp_pkg in compromised_packages {
-                        if comp_pkg.name == pkg_name && comp_pkg.version == version {
-                            let package_key = format!("{pkg_name}@{version}");
-                            findings.push(Finding::new(
-                                path.to_path_buf(),
-                                format!("Compromised package in lockfile: {package_key}"),
-                                RiskLevel::Medium,
-                                "integrity",
-                            ));
-                            break;
-                        }
-                    }
-                }
+            // Package name from the last node_modules/ segment, so nested copies
+            // ("node_modules/a/node_modules/@ctrl/tinycolor") match too
+            let Some(pkg_name) = pkg_path
+                .rfind("node_modules/")
+                .map(|at| &pkg_path[at + "node_modules/".len()..])
+            else {
+                continue;
+            };
+            if let Some(version) = pkg_data.get("version").and_then(|v| v.as_str()) {
+                // Check against compromised packages and OSV advisories
+                report(pkg_name, version, pkg_path, findings);
             }
         }
     }
@@ -140,20 +331,64 @@ fn check_json_lockfile(
                 continue;
             }
             if let Some(version) = pkg_data.get("version").and_then(|v| v.as_str()) {
-                // Check against compromised packages
-                for comp_pkg in compromised_packages {
-                    if &comp_pkg.name == pkg_name && comp_pkg.version == version {
-                        let package_key = format!("{pkg_name}@{version}");
-                        findings.push(Finding::new(
-                            path.to_path_buf(),
-                            format!("Compromised package in lockfile: {package_key}"),
-                            RiskLevel::Medium,
-                            "integrity",
-                        ));
-                        break;
-                    }
-                }
+                // Check against compromised packages and OSV advisories
+                report(pkg_name, version, pkg_name, findings);
             }
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_npm_lockfile_nested_entries() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package-lock.json"),
+            r#"{"lockfileVersion": 3, "packages": {
+                "": {"name": "app"},
+                "node_modules/a": {"version": "1.0.0"},
+                "node_modules/a/node_modules/@ctrl/tinycolor": {"version": "4.1.1"},
+                "node_modules/@ctrl/deluge": {"version": "1.0.0"}
+            }}"#,
+        )
+        .unwrap();
+        let compromised: HashSet<CompromisedPackage> = ["@ctrl/tinycolor:4.1.1"]
+            .iter()
+            .filter_map(|line| CompromisedPackage::from_line(line))
+            .collect();
+
+        let index = FileIndex::build(temp.path(), 1);
+        let findings = check_package_integrity(&index, &compromised, &Advisories::default());
+        let messages: Vec<&str> = findings.iter().map(|f| f.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            vec!["Compromised package in lockfile: @ctrl/tinycolor@4.1.1 (lockfile entry: node_modules/a/node_modules/@ctrl/tinycolor)"]
+        );
+    }
+
+    #[test]
+    fn test_bun_lockb_reported_as_not_verified() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a")).unwrap();
+        fs::create_dir_all(temp.path().join("b")).unwrap();
+        let lockb = b"#!/usr/bin/env bun\nbun-lockfile-format-v0\n";
+        fs::write(temp.path().join("a/bun.lockb"), lockb).unwrap();
+        // A text bun.lock next to it is what bun uses, and it is checked instead
+        fs::write(temp.path().join("b/bun.lockb"), lockb).unwrap();
+        fs::write(temp.path().join("b/bun.lock"), r#"{"packages": {}}"#).unwrap();
+
+        let index = FileIndex::build(temp.path(), 1);
+        let findings = check_package_integrity(&index, &HashSet::new(), &Advisories::default());
+        assert_eq!(findings.len(), 1);
+        assert_eq!(findings[0].file_path, temp.path().join("a/bun.lockb"));
+        assert_eq!(findings[0].category, "lockfile_not_verified");
+        assert_eq!(
+            PackageIntegrityDetector.results_key(&findings[0]),
+            "unverified_lockfiles"
+        );
+    }
+}
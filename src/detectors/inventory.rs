@@ -0,0 +1,251 @@
+// Installed Packages Inventory - compromised versions physically present in node_modules
+// Purpose: Answer "is the bad code actually on this disk" without npm/pnpm/yarn installed.
+//          Reads the name/version of every installed package from its own package.json
+//          (only active with --inventory; Rust-only, no Bash equivalent).
+//
+// Recognized layouts (every package directory sits directly below a node_modules):
+//   node_modules/debug                                            (npm/yarn hoisted)
+//   node_modules/express/node_modules/debug                       (nested copy)
+//   node_modules/@scope/pkg                                       (scoped)
+//   node_modules/.pnpm/debug@4.3.4/node_modules/debug             (pnpm store)
+//   node_modules/.pnpm/@scope+pkg@1.0.0_peer@2.0.0/node_modules/@scope/pkg
+//   .yarn/cache/debug-npm-4.3.4-abc.zip/node_modules/debug        (Yarn PnP cache)
+//
+// pnpm's node_modules/<name> entries are symlinks into the store and are not followed,
+// so every store copy is reported once.
+
+use crate::data::CompromisedPackage;
+use crate::detectors::pnpm_lockfile::strip_peer_suffix;
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
+use crate::osv::{self, Advisories};
+use serde_json::Value;
+use std::collections::HashSet;
+use std::path::{Component, Path};
+
+/// An installed package read from node_modules
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InstalledPackage {
+    pub name: String,
+    pub version: String,
+}
+
+// Function: installed_package_dir
+// Purpose: Check if a package.json is the manifest of an installed package
+// Args: path - path of a package.json
+// Returns: Package directory name below node_modules ("debug", "@scope/pkg"), None for
+//          project manifests and package.json files deeper inside a package
+pub fn installed_package_dir(path: &Path) -> Option<String> {
+    let components: Vec<&str> = path
+        .parent()?
+        .components()
+        .filter_map(|c| match c {
+            Component::Normal(name) => name.to_str(),
+            _ => None,
+        })
+        .collect();
+    match components.as_slice() {
+        [.., "node_modules", scope, name] if scope.starts_with('@') => {
+            Some(format!("{scope}/{name}"))
+        }
+        [.., "node_modules", name] if !name.starts_with(['.', '@']) => Some((*name).to_string()),
+        _ => None,
+    }
+}
+
+// Function: read_installed_package
+// Purpose: Name and version of an installed package
+// Args: path - package.json below node_modules, content - its text
+// Returns: InstalledPackage from the manifest; pnpm store copies with an unreadable
+//          manifest fall back to the store directory name (".pnpm/@scope+pkg@1.0.0")
+pub fn read_installed_package(path: &Path, content: &str) -> Option<InstalledPackage> {
+    let json = serde_json::from_str::<Value>(content).ok();
+    let field = |name: &str| {
+        json.as_ref()
+            .and_then(|json| json.get(name))
+            .and_then(Value::as_str)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    if let (Some(name), Some(version)) = (field("name"), field("version")) {
+        return Some(InstalledPackage { name, version });
+    }
+    pnpm_store_package(path)
+}
+
+// Helper: Package encoded in the pnpm store directory holding `path`
+fn pnpm_store_package(path: &Path) -> Option<InstalledPackage> {
+    let store_entry = path.ancestors().find(|dir| {
+        dir.parent()
+            .and_then(Path::file_name)
+            .is_some_and(|name| name == ".pnpm")
+    })?;
+    let entry = store_entry.file_name()?.to_str()?.replace('+', "/");
+    let at = entry.get(1..)?.find('@')? + 1;
+    let version = strip_peer_suffix(&entry[at + 1..]);
+    if version.is_empty() {
+        return None;
+    }
+    Some(InstalledPackage {
+        name: entry[..at].to_string(),
+        version: version.to_string(),
+    })
+}
+
+// Function: check_installed_packages
+// Purpose: Report every installed copy of a compromised package version
+// Args: index - file index of the scan directory, compromised_packages - known bad versions,
+//       advisories - OSV advisories (IDs appended to the finding message)
+// Returns: One finding per installed copy, located at the package directory
+pub fn check_installed_packages(
+    index: &FileIndex,
+    compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
+) -> Vec<Finding> {
+    let mut manifests: Vec<_> = index
+        .files_named("package.json")
+        .filter(|entry| installed_package_dir(entry.path()).is_some())
+        .collect();
+    manifests.sort_by(|a, b| a.path().cmp(b.path()));
+
+    crate::colors::print_status(
+        crate::colors::Color::Blue,
+        &format!(
+            "Checking {} installed packages in node_modules...",
+            manifests.len()
+        ),
+    );
+
+    let mut findings = Vec::new();
+    for entry in manifests {
+        let Some(package) = index
+            .read_text(entry)
+            .and_then(|content| read_installed_package(entry.path(), &content))
+        else {
+            continue;
+        };
+        let references = advisories.references(&package.name, &package.version);
+        let key = CompromisedPackage::new(package.name.clone(), package.version.clone());
+        if !compromised_packages.contains(&key) && references.is_none() {
+            continue;
+        }
+        let package_dir = entry.path().parent().unwrap_or(entry.path());
+        findings.push(Finding::new(
+            package_dir.to_path_buf(),
+            osv::with_references(
+                format!(
+                    "Installed compromised package: {}@{}",
+                    package.name, package.version
+                ),
+                references,
+            ),
+            RiskLevel::High,
+            "installed_packages",
+        ));
+    }
+    findings
+}
+
+/// Registry entry for `check_installed_packages()` (only active with --inventory)
+pub struct InstalledPackagesDetector;
+
+impl Detector for InstalledPackagesDetector {
+    fn id(&self) -> &'static str {
+        "installed_packages"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compromised package versions installed in node_modules"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        if !ctx.inventory {
+            return Vec::new();
+        }
+        check_installed_packages(ctx.index, ctx.compromised_packages, ctx.advisories)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_installed_package_dir() {
+        let dir = |path: &str| installed_package_dir(Path::new(path));
+        assert_eq!(
+            dir("app/node_modules/debug/package.json").as_deref(),
+            Some("debug")
+        );
+        assert_eq!(
+            dir("app/node_modules/@ctrl/tinycolor/package.json").as_deref(),
+            Some("@ctrl/tinycolor")
+        );
+        assert_eq!(
+            dir("app/node_modules/.pnpm/debug@4.3.4/node_modules/debug/package.json").as_deref(),
+            Some("debug")
+        );
+        assert_eq!(dir("app/package.json"), None);
+        assert_eq!(dir("app/node_modules/debug/lib/package.json"), None);
+        assert_eq!(dir("app/node_modules/.pnpm/package.json"), None);
+    }
+
+    #[test]
+    fn test_inventory_reports_every_installed_copy() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let install = |dir: &str, manifest: &str| {
+            fs::create_dir_all(root.join(dir)).unwrap();
+            fs::write(root.join(dir).join("package.json"), manifest).unwrap();
+        };
+        // Declared safe in package.json, but a nested copy is compromised
+        install("app", r#"{"dependencies": {"@ctrl/tinycolor": "^4.0.0"}}"#);
+        install(
+            "app/node_modules/@ctrl/tinycolor",
+            r#"{"name": "@ctrl/tinycolor", "version": "4.0.0"}"#,
+        );
+        install(
+            "app/node_modules/lib/node_modules/@ctrl/tinycolor",
+            r#"{"name": "@ctrl/tinycolor", "version": "4.1.1"}"#,
+        );
+        // pnpm store copy with a broken manifest
+        install(
+            "web/node_modules/.pnpm/@ctrl+tinycolor@4.1.1_peer@1.0.0/node_modules/@ctrl/tinycolor",
+            "{",
+        );
+
+        let compromised: HashSet<_> =
+            [CompromisedPackage::from_line("@ctrl/tinycolor:4.1.1").unwrap()]
+                .into_iter()
+                .collect();
+        let findings = check_installed_packages(
+            &FileIndex::build(root, 1),
+            &compromised,
+            &Advisories::default(),
+        );
+
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| {
+                format!(
+                    "{} at {}",
+                    f.message,
+                    f.file_path.strip_prefix(root).unwrap().display()
+                )
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "Installed compromised package: @ctrl/tinycolor@4.1.1 at app/node_modules/lib/node_modules/@ctrl/tinycolor",
+                "Installed compromised package: @ctrl/tinycolor@4.1.1 at web/node_modules/.pnpm/@ctrl+tinycolor@4.1.1_peer@1.0.0/node_modules/@ctrl/tinycolor",
+            ]
+        );
+    }
+}
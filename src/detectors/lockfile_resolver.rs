@@ -3,23 +3,112 @@
 
 #![allow(dead_code)]
 
+use crate::detectors::bun_lockfile::{decode_bun_lockb, parse_bun_lock};
+use crate::detectors::pnpm_lockfile::{PnpmDependency, PnpmLockfile};
+use crate::detectors::yarn_lockfile::{
+    is_berry_lockfile, parse_berry_lockfile, parse_classic_lockfile,
+};
 use anyhow::{Context, Result};
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::path::Path;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LockfileType {
-    Npm,  // package-lock.json
-    Yarn, // yarn.lock
-    Pnpm, // pnpm-lock.yaml
+    Npm,       // package-lock.json
+    Yarn,      // yarn.lock (classic v1)
+    YarnBerry, // yarn.lock (Yarn 2+, Plug'n'Play)
+    Bun,       // bun.lock / bun.lockb
+    Pnpm,      // pnpm-lock.yaml
+}
+
+/// One installed (or locked) copy of a package
+///
+/// A lockfile can hold several copies of the same package, e.g. `debug@4.3.4`
+/// hoisted to `node_modules/debug` and `debug@2.6.9` nested below `express`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PackageInstance {
+    pub name: String,
+    pub version: String,
+    /// Install path (e.g. "node_modules/express/node_modules/debug") or, for lockfiles
+    /// without install paths, the lockfile entry (e.g. "debug@^2.6.9")
+    pub path: String,
+    /// Packages leading to this copy, outermost first (e.g. ["express", "debug"]);
+    /// just [name] for direct or hoisted dependencies
+    pub chain: Vec<String>,
+}
+
+impl PackageInstance {
+    pub fn new(name: &str, version: &str, path: &str, chain: Vec<String>) -> Self {
+        Self {
+            name: name.to_string(),
+            version: version.to_string(),
+            path: path.to_string(),
+            chain,
+        }
+    }
+
+    /// Dependency chain for messages, e.g. "express > debug"
+    pub fn chain_display(&self) -> String {
+        self.chain.join(" > ")
+    }
+}
+
+/// All instances of every package, keyed by package name
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageInstances {
+    by_name: BTreeMap<String, Vec<PackageInstance>>,
+}
+
+impl PackageInstances {
+    /// Add an instance (a second entry with the same name and path is ignored)
+    pub fn add(&mut self, instance: PackageInstance) {
+        let instances = self.by_name.entry(instance.name.clone()).or_default();
+        if !instances.iter().any(|i| i.path == instance.path) {
+            instances.push(instance);
+        }
+    }
+
+    /// Every copy of a package, shortest install path (hoisted copy) first
+    pub fn get(&self, package_name: &str) -> &[PackageInstance] {
+        self.by_name.get(package_name).map_or(&[], Vec::as_slice)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &PackageInstance> {
+        self.by_name.values().flatten()
+    }
+
+    /// Number of distinct package names
+    pub fn len(&self) -> usize {
+        self.by_name.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.by_name.is_empty()
+    }
+
+    /// Number of installed copies across all packages
+    pub fn instance_count(&self) -> usize {
+        self.by_name.values().map(Vec::len).sum()
+    }
+
+    // Keep the hoisted copy first so get_version() reports what a bare require() loads
+    fn sort(&mut self) {
+        for instances in self.by_name.values_mut() {
+            instances.sort_by(|a, b| {
+                (a.chain.len(), a.path.len(), &a.path).cmp(&(b.chain.len(), b.path.len(), &b.path))
+            });
+        }
+    }
 }
 
 /// Main resolver - tries all lockfile formats
 pub struct LockfileResolver {
-    pub packages: HashMap<String, String>, // package_name -> version
+    pub packages: PackageInstances,
     pub lockfile_type: Option<LockfileType>,
+    /// Direct dependencies per workspace importer ("." is the root) - pnpm only
+    pub importers: BTreeMap<String, Vec<PnpmDependency>>,
 }
 
 impl LockfileResolver {
@@ -43,18 +132,35 @@ impl LockfileResolver {
             return Ok(resolver);
         }
 
+        // Try bun.lock / bun.lockb
+        if let Ok(resolver) = Self::load_bun_lockfile(dir) {
+            return Ok(resolver);
+        }
+
         // No lockfile found
         Ok(Self {
-            packages: HashMap::new(),
+            packages: PackageInstances::default(),
             lockfile_type: None,
+            importers: BTreeMap::new(),
         })
     }
 
-    /// Get resolved version for a package
+    /// Get resolved version of the hoisted (top-level) copy of a package
     pub fn get_version(&self, package_name: &str) -> Option<&str> {
         self.packages
             .get(package_name)
-            .map(std::string::String::as_str)
+            .first()
+            .map(|instance| instance.version.as_str())
+    }
+
+    /// Every locked copy of a package (hoisted and nested)
+    pub fn instances(&self, package_name: &str) -> &[PackageInstance] {
+        self.packages.get(package_name)
+    }
+
+    /// Direct dependencies of one workspace importer (pnpm), e.g. "." or "packages/app"
+    pub fn importer_dependencies(&self, importer: &str) -> &[PnpmDependency] {
+        self.importers.get(importer).map_or(&[], Vec::as_slice)
     }
 
     /// Check if lockfile was found
@@ -75,27 +181,33 @@ impl LockfileResolver {
         let lockfile: NpmLockfile =
             serde_json::from_str(&content).context("Failed to parse package-lock.json")?;
 
-        let mut packages = HashMap::new();
+        let mut packages = PackageInstances::default();
 
-        // NPM v1-v2 format
+        // NPM v1-v2 format (nested "dependencies" tree)
         if let Some(deps) = lockfile.dependencies {
-            extract_npm_packages(&deps, &mut packages);
+            extract_npm_packages(&deps, "", &[], &mut packages);
         }
 
-        // NPM v3+ format
+        // NPM v2+ format ("packages" keyed by install path)
         if let Some(pkgs) = lockfile.packages {
             for (path, pkg_info) in pkgs {
-                if let Some(name) = extract_package_name(&path) {
-                    if let Some(version) = pkg_info.version {
-                        packages.insert(name, version);
-                    }
+                let Some(version) = pkg_info.version else {
+                    continue;
+                };
+                let chain = install_chain(&path);
+                // Aliased installs ("npm:real@1.0.0") record the real name
+                let name = pkg_info.name.or_else(|| chain.last().cloned());
+                if let Some(name) = name {
+                    packages.add(PackageInstance::new(&name, &version, &path, chain));
                 }
             }
         }
+        packages.sort();
 
         Ok(Self {
             packages,
             lockfile_type: Some(LockfileType::Npm),
+            importers: BTreeMap::new(),
         })
     }
 
@@ -109,70 +221,47 @@ impl LockfileResolver {
         let content =
             fs::read_to_string(&lockfile_path).context("Failed to read pnpm-lock.yaml")?;
 
-        let mut packages = HashMap::new();
-
-        // Parse YAML - two patterns:
-        // 1. Dependencies section: "  debug:\n    version: 4.3.4"
-        // 2. Packages section: "/debug@4.3.4:"
-
-        let mut current_package: Option<String> = None;
-        let mut in_dependencies = false;
-
-        for line in content.lines() {
-            let trimmed = line.trim();
-
-            // Track if we're in dependencies section
-            if line.starts_with("dependencies:") || line.starts_with("devDependencies:") {
-                in_dependencies = true;
-                continue;
-            }
-            if !line.starts_with(' ') && !line.is_empty() && line.contains(':') {
-                in_dependencies = false;
-            }
-
-            // Pattern 1: Dependencies section
-            if in_dependencies && line.starts_with("  ") && !line.starts_with("    ") {
-                // Package name line: "  debug:"
-                if let Some(pkg_name) = line.trim().strip_suffix(':') {
-                    current_package = Some(pkg_name.to_string());
-                }
-            } else if in_dependencies && line.starts_with("    version: ") {
-                // Version line: "    version: 4.3.4"
-                if let Some(pkg_name) = &current_package {
-                    let version = line
-                        .trim()
-                        .strip_prefix("version: ")
-                        .unwrap_or("")
-                        .to_string();
-                    packages.insert(pkg_name.clone(), version);
-                    current_package = None;
-                }
-            }
-
-            // Pattern 2: Packages section
-            // PNPM v6/v7: "/debug@4.3.4:"
-            // PNPM v9:   "'@alloc/quick-lru@5.2.0':"
-            if (trimmed.starts_with('/') || trimmed.starts_with('\''))
-                && trimmed.contains('@')
-                && trimmed.ends_with(':')
-            {
-                let pkg_line = trimmed
-                    .trim_start_matches('/')
-                    .trim_start_matches('\'')
-                    .trim_end_matches('\'')
-                    .trim_end_matches(':');
-
-                if let Some(at_pos) = pkg_line.rfind('@') {
-                    let name = pkg_line[..at_pos].to_string();
-                    let version = pkg_line[at_pos + 1..].to_string();
-                    packages.insert(name, version);
-                }
+        let lockfile = PnpmLockfile::parse(&content)?;
+
+        let mut packages = PackageInstances::default();
+        // Every resolved package lives in the virtual store
+        for package in &lockfile.packages {
+            let path = format!(
+                "node_modules/.pnpm/{}@{}/node_modules/{}",
+                package.name.replace('/', "+"),
+                package.version,
+                package.name
+            );
+            packages.add(PackageInstance::new(
+                &package.name,
+                &package.version,
+                &path,
+                vec![package.name.clone()],
+            ));
+        }
+        // Direct dependencies are linked into each importer's node_modules
+        for (importer, dependencies) in &lockfile.importers {
+            for dependency in dependencies.iter().filter(|d| d.is_registry_version()) {
+                let path = if importer == "." {
+                    format!("node_modules/{}", dependency.name)
+                } else {
+                    format!("{importer}/node_modules/{}", dependency.name)
+                };
+                let name = dependency.package_name();
+                packages.add(PackageInstance::new(
+                    name,
+                    &dependency.version,
+                    &path,
+                    vec![name.to_string()],
+                ));
             }
         }
+        packages.sort();
 
         Ok(Self {
             packages,
             lockfile_type: Some(LockfileType::Pnpm),
+            importers: lockfile.importers,
         })
     }
 
@@ -184,49 +273,99 @@ impl LockfileResolver {
         }
 
         let content = fs::read_to_string(&lockfile_path).context("Failed to read yarn.lock")?;
+        if is_berry_lockfile(&content) {
+            return Self::load_yarn_berry_lockfile(&content);
+        }
+        Ok(Self::from_yarn_v1(&content, LockfileType::Yarn))
+    }
 
-        let mut packages = HashMap::new();
-        let mut current_package: Option<String> = None;
-
-        for line in content.lines() {
-            let line = line.trim();
-
-            // Package declaration: "package@version", package@version:
-            if !line.starts_with(' ') && line.contains('@') && line.ends_with(':') {
-                let pkg_line = line.trim_end_matches(':');
-                // Extract first package name from "pkg@^1.0.0", "@scope/pkg@^2.0.0"
-                if let Some(at_pos) = pkg_line.find('@') {
-                    let name = if pkg_line.starts_with('"') {
-                        pkg_line[1..at_pos].to_string()
-                    } else if let Some(stripped) = pkg_line.strip_prefix('@') {
-                        // Scoped package: @scope/name@version
-                        if let Some(second_at) = stripped.find('@') {
-                            pkg_line[..=second_at].to_string()
-                        } else {
-                            continue;
-                        }
-                    } else {
-                        pkg_line[..at_pos].to_string()
-                    };
-                    current_package = Some(name);
-                }
-            }
-            // Version line: version "1.2.3"
-            else if line.starts_with("version ") {
-                if let Some(pkg_name) = &current_package {
-                    let version = line
-                        .trim_start_matches("version ")
-                        .trim_matches('"')
-                        .to_string();
-                    packages.insert(pkg_name.clone(), version);
-                    current_package = None;
-                }
+    // Classic yarn v1 lockfile text (also what `bun bun.lockb` prints)
+    fn from_yarn_v1(content: &str, lockfile_type: LockfileType) -> Self {
+        let mut packages = PackageInstances::default();
+        for package in parse_classic_lockfile(content) {
+            // yarn.lock has no install paths - locate the copy by its first descriptor
+            let entry = package.descriptors.first().map_or("", String::as_str);
+            packages.add(PackageInstance::new(
+                &package.name,
+                &package.version,
+                entry,
+                vec![package.name.clone()],
+            ));
+        }
+        packages.sort();
+
+        Self {
+            packages,
+            lockfile_type: Some(lockfile_type),
+            importers: BTreeMap::new(),
+        }
+    }
+
+    // Bun lockfile parser - text bun.lock preferred, binary bun.lockb decoded by bun
+    fn load_bun_lockfile<P: AsRef<Path>>(dir: P) -> Result<Self> {
+        let dir = dir.as_ref();
+        let text_path = dir.join("bun.lock");
+        if text_path.exists() {
+            let content = fs::read_to_string(&text_path).context("Failed to read bun.lock")?;
+            return Self::from_bun_lock(&content);
+        }
+        let binary_path = dir.join("bun.lockb");
+        if binary_path.exists() {
+            return Self::from_bun_lockb(&binary_path);
+        }
+        anyhow::bail!("bun.lock not found");
+    }
+
+    /// Resolver for text bun.lock content
+    pub fn from_bun_lock(content: &str) -> Result<Self> {
+        let mut packages = PackageInstances::default();
+        for package in parse_bun_lock(content)? {
+            let path = chain_path(&package.chain);
+            packages.add(PackageInstance::new(
+                &package.name,
+                &package.version,
+                &path,
+                package.chain,
+            ));
+        }
+        packages.sort();
+
+        Ok(Self {
+            packages,
+            lockfile_type: Some(LockfileType::Bun),
+            importers: BTreeMap::new(),
+        })
+    }
+
+    /// Resolver for a binary bun.lockb (requires the bun CLI to decode)
+    pub fn from_bun_lockb(path: &Path) -> Result<Self> {
+        let yarn_text = decode_bun_lockb(path)?;
+        Ok(Self::from_yarn_v1(&yarn_text, LockfileType::Bun))
+    }
+
+    // Yarn 2+ yarn.lock parser (YAML with __metadata, npm:/patch: resolutions)
+    fn load_yarn_berry_lockfile(content: &str) -> Result<Self> {
+        let mut packages = PackageInstances::default();
+        for package in parse_berry_lockfile(content)? {
+            // Workspaces and links are project code, not installed packages
+            if package.is_local() {
+                continue;
             }
+            // No install paths with Plug'n'Play - locate the copy by its first descriptor
+            let entry = package.descriptors.first().unwrap_or(&package.resolution);
+            packages.add(PackageInstance::new(
+                &package.name,
+                &package.version,
+                entry,
+                vec![package.name.clone()],
+            ));
         }
+        packages.sort();
 
         Ok(Self {
             packages,
-            lockfile_type: Some(LockfileType::Yarn),
+            lockfile_type: Some(LockfileType::YarnBerry),
+            importers: BTreeMap::new(),
         })
     }
 }
@@ -246,42 +385,71 @@ struct NpmPackage {
 
 #[derive(Debug, Deserialize)]
 struct NpmPackageInfo {
+    name: Option<String>,
     version: Option<String>,
 }
 
 // Helper: Extract package name from node_modules path
+// ("node_modules/a/node_modules/@scope/b" -> "@scope/b")
 fn extract_package_name(path: &str) -> Option<String> {
-    if path.is_empty() || path == "node_modules" {
-        return None;
-    }
-
-    let path = path.trim_start_matches("node_modules/");
+    install_chain(path).pop()
+}
 
-    if path.starts_with('@') {
-        // @scope/package
-        let parts: Vec<&str> = path.splitn(3, '/').collect();
-        if parts.len() >= 2 {
-            Some(format!("{}/{}", parts[0], parts[1]))
-        } else {
-            None
+// Helper: Package names along a node_modules install path
+// ("node_modules/a/node_modules/@scope/b" -> ["a", "@scope/b"]; workspace folders
+// and the pnpm virtual store are skipped)
+pub fn install_chain(path: &str) -> Vec<String> {
+    let mut chain = Vec::new();
+    let mut segments = path.split('/');
+    while let Some(segment) = segments.next() {
+        if segment != "node_modules" {
+            continue;
+        }
+        match segments.next() {
+            Some(scope) if scope.starts_with('@') => {
+                if let Some(name) = segments.next() {
+                    chain.push(format!("{scope}/{name}"));
+                }
+            }
+            Some(name) if !name.is_empty() && name != ".pnpm" => chain.push(name.to_string()),
+            _ => {}
         }
-    } else {
-        // package
-        path.split('/').next().map(std::string::ToString::to_string)
     }
+    chain
+}
+
+// Helper: node_modules path of a dependency chain
+// (["a", "@scope/b"] -> "node_modules/a/node_modules/@scope/b")
+pub fn chain_path(chain: &[String]) -> String {
+    chain
+        .iter()
+        .map(|name| format!("node_modules/{name}"))
+        .collect::<Vec<_>>()
+        .join("/")
 }
 
 // Helper: Recursively extract packages from NPM v1-v2 format
+// (nested "dependencies" mirror the node_modules tree)
 fn extract_npm_packages(
     deps: &HashMap<String, NpmPackage>,
-    packages: &mut HashMap<String, String>,
+    parent_path: &str,
+    parent_chain: &[String],
+    packages: &mut PackageInstances,
 ) {
     for (name, pkg) in deps {
+        let path = if parent_path.is_empty() {
+            format!("node_modules/{name}")
+        } else {
+            format!("{parent_path}/node_modules/{name}")
+        };
+        let mut chain = parent_chain.to_vec();
+        chain.push(name.clone());
+
         if let Some(version) = &pkg.version {
-            packages.insert(name.clone(), version.clone());
+            packages.add(PackageInstance::new(name, version, &path, chain.clone()));
         }
         if let Some(subdeps) = &pkg.dependencies {
-            extract_npm_packages(subdeps, packages);
+            extract_npm_packages(subdeps, &path, &chain, packages);
         }
     }
 }
@@ -342,6 +510,59 @@ packages:
         assert_eq!(resolver.get_version("ansi-regex"), Some("6.1.0"));
     }
 
+    #[test]
+    fn test_pnpm_workspace_lockfile_strips_peer_suffixes() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockfile_content = r"
+lockfileVersion: '9.0'
+
+importers:
+
+  .:
+    dependencies:
+      react-dom:
+        specifier: ^18.2.0
+        version: 18.2.0(react@18.2.0)
+
+  packages/app:
+    dependencies:
+      react-dom:
+        specifier: ^17.0.0
+        version: 17.0.2(react@17.0.2)
+
+packages:
+
+  react-dom@17.0.2:
+    resolution: {integrity: sha512-aaa}
+
+  react-dom@18.2.0:
+    resolution: {integrity: sha512-bbb}
+
+snapshots:
+
+  react-dom@17.0.2(react@17.0.2): {}
+
+  react-dom@18.2.0(react@18.2.0): {}
+";
+        fs::write(temp_dir.path().join("pnpm-lock.yaml"), lockfile_content).unwrap();
+
+        let resolver = LockfileResolver::load_from_dir(temp_dir.path()).unwrap();
+        assert_eq!(resolver.get_version("react-dom"), Some("18.2.0"));
+        let app = resolver
+            .instances("react-dom")
+            .iter()
+            .find(|i| i.path == "packages/app/node_modules/react-dom")
+            .unwrap();
+        assert_eq!(app.version, "17.0.2");
+        assert_eq!(resolver.packages.instance_count(), 4);
+        assert_eq!(
+            resolver.importer_dependencies("packages/app")[0]
+                .specifier
+                .as_deref(),
+            Some("^17.0.0")
+        );
+    }
+
     #[test]
     fn test_extract_package_name() {
         assert_eq!(
@@ -352,7 +573,121 @@ packages:
             extract_package_name("node_modules/@scope/package"),
             Some("@scope/package".to_string())
         );
+        assert_eq!(
+            extract_package_name("node_modules/express/node_modules/@scope/package"),
+            Some("@scope/package".to_string())
+        );
         assert_eq!(extract_package_name(""), None);
+        assert_eq!(extract_package_name("packages/app"), None);
+    }
+
+    #[test]
+    fn test_npm_lockfile_keeps_every_instance() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockfile_content = r#"{
+            "lockfileVersion": 2,
+            "packages": {
+                "": {},
+                "node_modules/debug": {"version": "4.3.4"},
+                "node_modules/express": {"version": "4.18.2"},
+                "node_modules/express/node_modules/debug": {"version": "2.6.9"},
+                "packages/app/node_modules/debug": {"version": "3.2.7"}
+            },
+            "dependencies": {
+                "debug": {"version": "4.3.4"},
+                "express": {
+                    "version": "4.18.2",
+                    "dependencies": {"debug": {"version": "2.6.9"}}
+                }
+            }
+        }"#;
+        fs::write(temp_dir.path().join("package-lock.json"), lockfile_content).unwrap();
+
+        let resolver = LockfileResolver::load_from_dir(temp_dir.path()).unwrap();
+        let debug = resolver.instances("debug");
+        assert_eq!(debug.len(), 3);
+        assert_eq!(resolver.get_version("debug"), Some("4.3.4"));
+
+        let nested = debug.iter().find(|i| i.version == "2.6.9").unwrap();
+        assert_eq!(nested.path, "node_modules/express/node_modules/debug");
+        assert_eq!(nested.chain_display(), "express > debug");
+        assert_eq!(resolver.packages.instance_count(), 4);
+    }
+
+    #[test]
+    fn test_yarn_lockfile_keeps_every_version() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockfile_content = r#"
+debug@^2.6.9:
+  version "2.6.9"
+
+debug@^4.3.4, debug@^4.1.0:
+  version "4.3.4"
+"#;
+        fs::write(temp_dir.path().join("yarn.lock"), lockfile_content).unwrap();
+
+        let resolver = LockfileResolver::load_from_dir(temp_dir.path()).unwrap();
+        let versions: Vec<_> = resolver
+            .instances("debug")
+            .iter()
+            .map(|i| i.version.as_str())
+            .collect();
+        assert_eq!(versions, vec!["2.6.9", "4.3.4"]);
+        assert_eq!(resolver.instances("debug")[1].path, "debug@^4.3.4");
+    }
+
+    #[test]
+    fn test_yarn_berry_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockfile_content = r#"
+__metadata:
+  version: 8
+  cacheKey: 10c0
+
+"debug@npm:^4.3.4":
+  version: 4.3.4
+  resolution: "debug@npm:4.3.4"
+
+"my-app@workspace:.":
+  version: 0.0.0-use.local
+  resolution: "my-app@workspace:."
+
+"strip@npm:strip-ansi@^6.0.0":
+  version: 6.0.1
+  resolution: "strip-ansi@npm:6.0.1"
+"#;
+        fs::write(temp_dir.path().join("yarn.lock"), lockfile_content).unwrap();
+
+        let resolver = LockfileResolver::load_from_dir(temp_dir.path()).unwrap();
+        assert_eq!(resolver.lockfile_type, Some(LockfileType::YarnBerry));
+        assert_eq!(resolver.get_version("debug"), Some("4.3.4"));
+        assert_eq!(resolver.get_version("strip-ansi"), Some("6.0.1"));
+        assert_eq!(
+            resolver.instances("strip-ansi")[0].path,
+            "strip@npm:strip-ansi@^6.0.0"
+        );
+        assert_eq!(resolver.get_version("my-app"), None);
+    }
+
+    #[test]
+    fn test_bun_lockfile() {
+        let temp_dir = TempDir::new().unwrap();
+        let lockfile_content = r#"{
+  "lockfileVersion": 1,
+  "packages": {
+    "debug": ["debug@4.3.4", "", {}, "sha512-aaa"],
+    "express/debug": ["debug@2.6.9", "", {}, "sha512-bbb"],
+  },
+}
+"#;
+        fs::write(temp_dir.path().join("bun.lock"), lockfile_content).unwrap();
+
+        let resolver = LockfileResolver::load_from_dir(temp_dir.path()).unwrap();
+        assert_eq!(resolver.lockfile_type, Some(LockfileType::Bun));
+        assert_eq!(resolver.get_version("debug"), Some("4.3.4"));
+        let nested = &resolver.instances("debug")[1];
+        assert_eq!(nested.path, "node_modules/express/node_modules/debug");
+        assert_eq!(nested.chain_display(), "express > debug");
     }
 
     #[test]
@@ -364,6 +699,10 @@ packages:
             if resolver.has_lockfile() {
                 println!("\n✅ Lockfile type: {:?}", resolver.lockfile_type);
                 println!("✅ Total packages: {}", resolver.packages.len());
+                println!(
+                    "✅ Installed instances: {}",
+                    resolver.packages.instance_count()
+                );
 
                 // Test the 4 "NEEDS REVIEW" packages
                 if let Some(v) = resolver.get_version("ansi-regex") {
@@ -0,0 +1,664 @@
+// Lockfile Sources Detector - where lockfiles say each tarball came from
+// Purpose: Flag dependencies resolved from git, raw tarball URLs, local paths or
+//          registries the project does not configure, tarballs whose integrity matches
+//          known malicious packages, and registry entries without an integrity hash.
+//          Rust-only check (the Bash scanner ignores "resolved" and "integrity").
+//
+// Recorded sources per format:
+//   package-lock.json  "resolved" / "integrity" ("link": true entries are workspaces)
+//   yarn.lock (v1)     resolved "https://...tgz#sha1" / integrity sha512-...
+//   yarn.lock (Berry)  resolution "name@git:..." (checksums are Yarn's own, not SRI)
+//   pnpm-lock.yaml     resolution: {integrity} | {tarball} | {type: git, repo} | {directory}
+//   bun.lock           ["name@version", "registry URL", {...}, "sha512-..."]
+//   bun.lockb          decoded to yarn v1 text with the bun CLI
+
+use crate::detectors::bun_lockfile::{decode_bun_lockb, parse_bun_lock_entries};
+use crate::detectors::pnpm_lockfile::PnpmLockfile;
+use crate::detectors::projects::{in_node_modules, LOCKFILE_NAMES};
+use crate::detectors::yarn_lockfile::{
+    is_berry_lockfile, parse_berry_lockfile, parse_classic_lockfile,
+};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::{FileIndex, IndexEntry};
+use anyhow::Result;
+use serde_json::Value;
+use std::fs;
+use std::path::Path;
+
+/// Registries every project may use without configuring them
+pub const DEFAULT_REGISTRIES: [&str; 2] = ["registry.npmjs.org", "registry.yarnpkg.com"];
+
+/// Where a lockfile entry was fetched from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SourceKind {
+    /// Default or configured registry
+    Registry,
+    /// Registry tarball URL on a host the project does not configure
+    UnconfiguredRegistry,
+    /// Any other http(s) tarball URL
+    Tarball,
+    Git,
+    /// file: dependency or a directory outside the project
+    LocalPath,
+}
+
+/// One lockfile entry with the source it records
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct LockedSource {
+    name: String,
+    version: String,
+    /// Lockfile entry as written (package-lock.json path, yarn descriptors, pnpm key)
+    entry: String,
+    /// URL or path the package came from (None for the default registry)
+    resolved: Option<String>,
+    integrity: Option<String>,
+    /// The format records integrity for this entry (absence means it was removed)
+    integrity_expected: bool,
+}
+
+// Function: classify_source
+// Purpose: Decide where a resolved URL or path points
+// Args: resolved - lockfile "resolved" value, registries - configured registry prefixes
+// Returns: SourceKind
+pub fn classify_source(resolved: &str, registries: &[String]) -> SourceKind {
+    const GIT_PREFIXES: [&str; 8] = [
+        "git+",
+        "git:",
+        "git@",
+        "ssh://",
+        "github:",
+        "gitlab:",
+        "bitbucket:",
+        "gist:",
+    ];
+    if GIT_PREFIXES
+        .iter()
+        .any(|prefix| resolved.starts_with(prefix))
+    {
+        return SourceKind::Git;
+    }
+    if resolved.starts_with("file:") || resolved.starts_with('.') || resolved.starts_with('/') {
+        return SourceKind::LocalPath;
+    }
+    let Some(location) = resolved
+        .strip_prefix("https://")
+        .or_else(|| resolved.strip_prefix("http://"))
+    else {
+        // Bare "owner/repo#ref" is a GitHub shorthand
+        return if resolved.contains('/') {
+            SourceKind::Git
+        } else {
+            SourceKind::Registry
+        };
+    };
+
+    let location = location.split(['#', '?']).next().unwrap_or(location);
+    let host = location.split('/').next().unwrap_or(location);
+    if host == "codeload.github.com" || location.ends_with(".git") {
+        return SourceKind::Git;
+    }
+    let configured = registries.iter().any(|registry| {
+        location
+            .strip_prefix(registry.as_str())
+            .is_some_and(|rest| rest.starts_with('/'))
+    });
+    if configured {
+        SourceKind::Registry
+    } else if location.contains("/-/") && location.ends_with(".tgz") {
+        SourceKind::UnconfiguredRegistry
+    } else {
+        SourceKind::Tarball
+    }
+}
+
+// Function: configured_registries
+// Purpose: Registries a project is set up to install from
+// Args: project_dir - directory holding the lockfile
+// Returns: Registry prefixes without scheme or trailing slash ("npm.corp.example/api/npm")
+//          from .npmrc, .yarnrc and .yarnrc.yml, plus DEFAULT_REGISTRIES
+pub fn configured_registries(project_dir: &Path) -> Vec<String> {
+    let mut registries: Vec<String> = DEFAULT_REGISTRIES.iter().map(|r| r.to_string()).collect();
+    let mut add = |url: &str| {
+        let url = url.trim().trim_matches(['"', '\'']);
+        let url = url
+            .strip_prefix("https://")
+            .or_else(|| url.strip_prefix("http://"))
+            .unwrap_or(url)
+            .trim_end_matches('/');
+        if !url.is_empty() && !registries.iter().any(|r| r == url) {
+            registries.push(url.to_string());
+        }
+    };
+
+    let read = |name: &str| fs::read_to_string(project_dir.join(name)).unwrap_or_default();
+    // .npmrc: registry=URL, @scope:registry=URL
+    for line in read(".npmrc").lines() {
+        if let Some((key, value)) = line.split_once('=') {
+            let key = key.trim();
+            if key == "registry" || key.ends_with(":registry") {
+                add(value);
+            }
+        }
+    }
+    // .yarnrc (v1): registry "URL"
+    for line in read(".yarnrc").lines() {
+        if let Some(value) = line.trim().strip_prefix("registry ") {
+            add(value);
+        }
+    }
+    // .yarnrc.yml: npmRegistryServer (top level and under npmScopes)
+    for line in read(".yarnrc.yml").lines() {
+        if let Some(value) = line.trim().strip_prefix("npmRegistryServer:") {
+            add(value);
+        }
+    }
+    registries
+}
+
+// Function: check_lockfile_sources
+// Purpose: Check resolved URLs and integrity hashes of every project lockfile
+// Args: index - file index of the scan directory,
+//       malicious_integrity - known malicious tarball integrity values (IOC data)
+// Returns: Findings (category "lockfile_sources")
+pub fn check_lockfile_sources(index: &FileIndex, malicious_integrity: &[String]) -> Vec<Finding> {
+    crate::colors::print_status(
+        crate::colors::Color::Blue,
+        "Checking lockfile sources (resolved URLs and integrity hashes)...",
+    );
+
+    let mut findings = Vec::new();
+    // Lockfiles published inside packages are never installed from
+    for entry in index.files().filter(|e| {
+        LOCKFILE_NAMES.contains(&e.file_name())
+            && e.archive().is_none()
+            && !in_node_modules(e.path())
+    }) {
+        let sources = match locked_sources(index, entry) {
+            Ok(sources) => sources,
+            // Unparseable lockfiles are reported by the integrity check
+            Err(_) => continue,
+        };
+        let registries = entry
+            .path()
+            .parent()
+            .map(configured_registries)
+            .unwrap_or_default();
+
+        for source in sources {
+            check_source(
+                entry,
+                &source,
+                &registries,
+                malicious_integrity,
+                &mut findings,
+            );
+        }
+    }
+    findings
+}
+
+// Helper: Findings for one lockfile entry
+fn check_source(
+    lockfile: &IndexEntry,
+    source: &LockedSource,
+    registries: &[String],
+    malicious_integrity: &[String],
+    findings: &mut Vec<Finding>,
+) {
+    let mut report = |message: String, risk_level: RiskLevel| {
+        findings.push(Finding::new(
+            lockfile.path().to_path_buf(),
+            format!("{message} (lockfile entry: {})", source.entry),
+            risk_level,
+            "lockfile_sources",
+        ));
+    };
+
+    // An integrity field may list several hashes ("sha1-... sha512-...")
+    if let Some(integrity) = &source.integrity {
+        if let Some(hash) = integrity
+            .split_whitespace()
+            .find(|hash| malicious_integrity.iter().any(|m| m == hash))
+        {
+            report(
+                format!(
+                    "Tarball integrity matches a known malicious package: {}@{} ({hash})",
+                    source.name, source.version
+                ),
+                RiskLevel::High,
+            );
+        }
+    }
+
+    let kind = source
+        .resolved
+        .as_deref()
+        .map_or(SourceKind::Registry, |resolved| {
+            classify_source(resolved, registries)
+        });
+    let resolved = source.resolved.as_deref().unwrap_or_default();
+    match kind {
+        SourceKind::Git => report(
+            format!("Dependency resolved from git: {} ({resolved})", source.name),
+            RiskLevel::Medium,
+        ),
+        SourceKind::Tarball => report(
+            format!(
+                "Dependency resolved from a tarball URL: {} ({resolved})",
+                source.name
+            ),
+            RiskLevel::Medium,
+        ),
+        SourceKind::UnconfiguredRegistry => report(
+            format!(
+                "Dependency resolved from a registry not configured for this project: {} ({resolved})",
+                source.name
+            ),
+            RiskLevel::Medium,
+        ),
+        SourceKind::LocalPath => report(
+            format!(
+                "Dependency resolved from a local path: {} ({resolved})",
+                source.name
+            ),
+            RiskLevel::Low,
+        ),
+        SourceKind::Registry => {}
+    }
+
+    let from_registry = matches!(
+        kind,
+        SourceKind::Registry | SourceKind::UnconfiguredRegistry
+    );
+    if from_registry && source.integrity_expected && source.integrity.is_none() {
+        report(
+            format!(
+                "Lockfile entry has no integrity hash: {}@{}",
+                source.name, source.version
+            ),
+            RiskLevel::Low,
+        );
+    }
+}
+
+// Function: locked_sources
+// Purpose: Read the recorded source of every entry of one lockfile
+// Args: index - file index, entry - lockfile
+// Returns: Entries in lockfile order, or an error for unreadable lockfiles
+fn locked_sources(index: &FileIndex, entry: &IndexEntry) -> Result<Vec<LockedSource>> {
+    if entry.file_name() == "bun.lockb" {
+        return Ok(yarn_classic_sources(&decode_bun_lockb(entry.path())?));
+    }
+    let content = index
+        .read_text(entry)
+        .ok_or_else(|| anyhow::anyhow!("lockfile is not valid UTF-8"))?;
+    let content: &str = &content;
+    match entry.file_name() {
+        "package-lock.json" => npm_sources(content),
+        "yarn.lock" if is_berry_lockfile(content) => yarn_berry_sources(content),
+        "yarn.lock" => Ok(yarn_classic_sources(content)),
+        "pnpm-lock.yaml" => pnpm_sources(content),
+        _ => bun_sources(content),
+    }
+}
+
+// Helper: package-lock.json "packages" (v2+) or nested "dependencies" (v1)
+fn npm_sources(content: &str) -> Result<Vec<LockedSource>> {
+    let json: Value = serde_json::from_str(content)?;
+    let field =
+        |data: &Value, name: &str| data.get(name).and_then(Value::as_str).map(str::to_string);
+    let mut sources = Vec::new();
+
+    if let Some(packages) = json.get("packages").and_then(Value::as_object) {
+        for (path, data) in packages {
+            let Some((_, installed_name)) = path.rsplit_once("node_modules/") else {
+                continue;
+            };
+            let mut resolved = field(data, "resolved");
+            // Workspaces and `npm link` are symlinks - only directories outside the project matter
+            if data.get("link").and_then(Value::as_bool) == Some(true) {
+                match resolved {
+                    Some(target) if target.starts_with("..") => {
+                        resolved = Some(format!("file:{target}"));
+                    }
+                    _ => continue,
+                }
+            }
+            sources.push(LockedSource {
+                name: field(data, "name").unwrap_or_else(|| installed_name.to_string()),
+                version: field(data, "version").unwrap_or_default(),
+                entry: path.clone(),
+                integrity_expected: resolved.is_some(),
+                resolved,
+                integrity: field(data, "integrity"),
+            });
+        }
+        return Ok(sources);
+    }
+
+    // v1: git and file dependencies keep their source in "version"
+    fn walk(
+        dependencies: &serde_json::Map<String, Value>,
+        prefix: &str,
+        sources: &mut Vec<LockedSource>,
+    ) {
+        for (name, data) in dependencies {
+            let field = |name: &str| data.get(name).and_then(Value::as_str).map(str::to_string);
+            let version = field("version").unwrap_or_default();
+            let resolved =
+                field("resolved").or_else(|| version.contains(':').then(|| version.clone()));
+            let entry = format!("{prefix}{name}");
+            sources.push(LockedSource {
+                name: name.clone(),
+                version,
+                entry: entry.clone(),
+                integrity_expected: field("resolved").is_some(),
+                resolved,
+                integrity: field("integrity"),
+            });
+            if let Some(nested) = data.get("dependencies").and_then(Value::as_object) {
+                walk(nested, &format!("{entry} > "), sources);
+            }
+        }
+    }
+    if let Some(dependencies) = json.get("dependencies").and_then(Value::as_object) {
+        walk(dependencies, "", &mut sources);
+    }
+    Ok(sources)
+}
+
+// Helper: yarn.lock v1 (and decoded bun.lockb); local directories have no "resolved"
+fn yarn_classic_sources(content: &str) -> Vec<LockedSource> {
+    parse_classic_lockfile(content)
+        .into_iter()
+        .map(|package| {
+            let local = package.descriptors.iter().find_map(|descriptor| {
+                let reference = &descriptor[descriptor.get(1..)?.find('@')? + 2..];
+                reference
+                    .starts_with("file:")
+                    .then(|| reference.to_string())
+            });
+            LockedSource {
+                entry: package.descriptors.join(", "),
+                integrity_expected: package.resolved.is_some(),
+                resolved: package.resolved.or(local),
+                integrity: package.integrity,
+                name: package.name,
+                version: package.version,
+            }
+        })
+        .collect()
+}
+
+// Helper: Yarn Berry resolutions (workspace/link/portal packages are project code)
+fn yarn_berry_sources(content: &str) -> Result<Vec<LockedSource>> {
+    Ok(parse_berry_lockfile(content)?
+        .into_iter()
+        .filter(|package| package.protocol == "file" || !package.is_local())
+        .map(|package| {
+            let reference = &package.resolution[package.name.len() + 1..];
+            let resolved = match package.protocol.as_str() {
+                "npm" | "patch" => None,
+                _ => Some(reference.to_string()),
+            };
+            LockedSource {
+                entry: package.descriptors.join(", "),
+                resolved,
+                integrity: None,
+                integrity_expected: false,
+                name: package.name,
+                version: package.version,
+            }
+        })
+        .collect())
+}
+
+// Helper: pnpm-lock.yaml packages: resolutions (registry packages record only integrity)
+fn pnpm_sources(content: &str) -> Result<Vec<LockedSource>> {
+    Ok(PnpmLockfile::parse(content)?
+        .packages
+        .into_iter()
+        .map(|package| {
+            let resolution = package.resolution;
+            let recorded = resolution != Default::default();
+            let resolved = resolution
+                .repo
+                .map(|repo| {
+                    if classify_source(&repo, &[]) == SourceKind::Git {
+                        repo
+                    } else {
+                        format!("git+{repo}")
+                    }
+                })
+                .or(resolution.tarball)
+                .or(resolution.directory.map(|dir| format!("file:{dir}")));
+            LockedSource {
+                name: package.name,
+                version: package.version,
+                entry: package.key,
+                integrity_expected: recorded && resolved.is_none(),
+                resolved,
+                integrity: resolution.integrity,
+            }
+        })
+        .collect())
+}
+
+// Helper: bun.lock entries (non-registry sources are kept in the version)
+fn bun_sources(content: &str) -> Result<Vec<LockedSource>> {
+    Ok(parse_bun_lock_entries(content)?
+        .into_iter()
+        .filter(|package| {
+            !package.version.starts_with("workspace:") && !package.version.starts_with("link:")
+        })
+        .map(|package| {
+            let registry_version = package.is_registry_version();
+            // Registry entries name the registry, not the tarball - rebuild the tarball URL
+            let resolved = if registry_version {
+                package.registry.as_deref().map(|registry| {
+                    let basename = package.name.rsplit('/').next().unwrap_or(&package.name);
+                    format!(
+                        "{}/{}/-/{basename}-{}.tgz",
+                        registry.trim_end_matches('/'),
+                        package.name,
+                        package.version
+                    )
+                })
+            } else {
+                Some(package.version.clone())
+            };
+            LockedSource {
+                entry: package.key,
+                integrity_expected: registry_version,
+                resolved,
+                integrity: package.integrity,
+                name: package.name,
+                version: package.version,
+            }
+        })
+        .collect())
+}
+
+/// Registry entry for `check_lockfile_sources()`
+pub struct LockfileSourcesDetector;
+
+impl Detector for LockfileSourcesDetector {
+    fn id(&self) -> &'static str {
+        "lockfile_sources"
+    }
+
+    fn description(&self) -> &'static str {
+        "Lockfile dependencies from git, tarball URLs, local paths or unconfigured registries, or with malicious/missing integrity"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_lockfile_sources(ctx.index, &ctx.iocs.malicious_tarball_integrity)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_classify_source() {
+        let registries = vec![
+            "registry.npmjs.org".to_string(),
+            "npm.corp.example/api/npm".to_string(),
+        ];
+        let kind = |resolved: &str| classify_source(resolved, &registries);
+
+        assert_eq!(
+            kind("https://registry.npmjs.org/debug/-/debug-4.3.4.tgz"),
+            SourceKind::Registry
+        );
+        assert_eq!(
+            kind("https://npm.corp.example/api/npm/debug/-/debug-4.3.4.tgz"),
+            SourceKind::Registry
+        );
+        assert_eq!(
+            kind("https://npm.evil.example/debug/-/debug-4.3.4.tgz"),
+            SourceKind::UnconfiguredRegistry
+        );
+        // Same host, but not below the configured path
+        assert_eq!(
+            kind("https://npm.corp.example/other/debug/-/debug-4.3.4.tgz"),
+            SourceKind::UnconfiguredRegistry
+        );
+        assert_eq!(
+            kind("https://evil.example/payload.tgz"),
+            SourceKind::Tarball
+        );
+        assert_eq!(
+            kind("git+ssh://git@github.com/x/y.git#abc123"),
+            SourceKind::Git
+        );
+        assert_eq!(
+            kind("https://codeload.github.com/x/y/tar.gz/abc123"),
+            SourceKind::Git
+        );
+        assert_eq!(kind("github:x/y#abc123"), SourceKind::Git);
+        assert_eq!(kind("file:../local-lib"), SourceKind::LocalPath);
+    }
+
+    #[test]
+    fn test_lockfile_sources_findings() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        fs::write(
+            root.join(".npmrc"),
+            "registry=https://npm.corp.example/api/npm/\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("package-lock.json"),
+            r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "": {"name": "app"},
+    "node_modules/ok": {"version": "1.0.0", "resolved": "https://npm.corp.example/api/npm/ok/-/ok-1.0.0.tgz", "integrity": "sha512-ok"},
+    "node_modules/evil": {"version": "1.0.0", "resolved": "https://registry.npmjs.org/evil/-/evil-1.0.0.tgz", "integrity": "sha1-x sha512-bad"},
+    "node_modules/stripped": {"version": "2.0.0", "resolved": "https://registry.npmjs.org/stripped/-/stripped-2.0.0.tgz"},
+    "node_modules/mirror": {"version": "1.0.0", "resolved": "https://npm.evil.example/mirror/-/mirror-1.0.0.tgz", "integrity": "sha512-m"},
+    "node_modules/from-git": {"version": "1.0.0", "resolved": "git+ssh://git@github.com/x/from-git.git#abc123"},
+    "node_modules/raw": {"version": "1.0.0", "resolved": "https://evil.example/raw.tgz", "integrity": "sha512-r"},
+    "node_modules/ws-lib": {"resolved": "packages/ws-lib", "link": true},
+    "node_modules/outside": {"resolved": "../outside", "link": true}
+  }
+}"#,
+        )
+        .unwrap();
+
+        let findings =
+            check_lockfile_sources(&FileIndex::build(root, 1), &["sha512-bad".to_string()]);
+        let summary: Vec<_> = findings
+            .iter()
+            .map(|f| format!("{:?}: {}", f.risk_level, f.message))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "High: Tarball integrity matches a known malicious package: evil@1.0.0 (sha512-bad) (lockfile entry: node_modules/evil)",
+                "Low: Lockfile entry has no integrity hash: stripped@2.0.0 (lockfile entry: node_modules/stripped)",
+                "Medium: Dependency resolved from a registry not configured for this project: mirror (https://npm.evil.example/mirror/-/mirror-1.0.0.tgz) (lockfile entry: node_modules/mirror)",
+                "Medium: Dependency resolved from git: from-git (git+ssh://git@github.com/x/from-git.git#abc123) (lockfile entry: node_modules/from-git)",
+                "Medium: Dependency resolved from a tarball URL: raw (https://evil.example/raw.tgz) (lockfile entry: node_modules/raw)",
+                "Low: Dependency resolved from a local path: outside (file:../outside) (lockfile entry: node_modules/outside)",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_yarn_pnpm_and_bun_sources() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for dir in ["yarn", "pnpm", "bun"] {
+            fs::create_dir(root.join(dir)).unwrap();
+        }
+        fs::write(
+            root.join("yarn/yarn.lock"),
+            r#"# yarn lockfile v1
+
+debug@^4.3.4:
+  version "4.3.4"
+  resolved "https://registry.yarnpkg.com/debug/-/debug-4.3.4.tgz#1319f6"
+
+"left-pad@git+https://github.com/x/left-pad.git":
+  version "1.3.0"
+  resolved "git+https://github.com/x/left-pad.git#5d1b4e"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("pnpm/pnpm-lock.yaml"),
+            r#"lockfileVersion: '9.0'
+packages:
+  debug@4.3.4:
+    resolution: {integrity: sha512-aaa}
+  raw@https://evil.example/raw.tgz:
+    resolution: {tarball: https://evil.example/raw.tgz}
+    version: 1.0.0
+    name: raw
+  lib@file:../lib:
+    resolution: {directory: ../lib, type: directory}
+    version: 0.0.0
+    name: lib
+"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("bun/bun.lock"),
+            r#"{
+  "lockfileVersion": 1,
+  "packages": {
+    "debug": ["debug@4.3.4", "", {}, "sha512-aaa"],
+    "private": ["private@1.0.0", "https://npm.evil.example/", {}, "sha512-bbb"],
+    "left-pad": ["left-pad@github:x/left-pad#abc123", {}, "abc123"],
+    "my-lib": ["my-lib@workspace:packages/lib"],
+  }
+}"#,
+        )
+        .unwrap();
+
+        let findings = check_lockfile_sources(&FileIndex::build(root, 1), &[]);
+        let mut messages: Vec<_> = findings.iter().map(|f| f.message.as_str()).collect();
+        messages.sort_unstable();
+        assert_eq!(
+            messages,
+            vec![
+                "Dependency resolved from a local path: lib (file:../lib) (lockfile entry: lib@file:../lib)",
+                "Dependency resolved from a registry not configured for this project: private (https://npm.evil.example/private/-/private-1.0.0.tgz) (lockfile entry: private)",
+                "Dependency resolved from a tarball URL: raw (https://evil.example/raw.tgz) (lockfile entry: raw@https://evil.example/raw.tgz)",
+                "Dependency resolved from git: left-pad (git+https://github.com/x/left-pad.git#5d1b4e) (lockfile entry: left-pad@git+https://github.com/x/left-pad.git)",
+                "Dependency resolved from git: left-pad (github:x/left-pad#abc123) (lockfile entry: left-pad)",
+                "Lockfile entry has no integrity hash: debug@4.3.4 (lockfile entry: debug@^4.3.4)",
+            ]
+        );
+    }
+}
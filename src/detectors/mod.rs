@@ -2,19 +2,27 @@
 // Each detector corresponds to a bash function in shai-hulud-detector.sh
 
 pub mod bun_attack;
+pub mod bun_lockfile;
+pub mod caches;
 pub mod content;
 pub mod crypto;
 pub mod destructive_patterns;
 pub mod discussion_workflows;
+pub mod exposure;
 pub mod git;
 pub mod github_runners;
 pub mod hashes;
 pub mod integrity;
+pub mod inventory;
 pub mod lockfile_resolver;
+pub mod lockfile_sources;
 pub mod network;
 pub mod packages;
+pub mod pnpm_lockfile;
 pub mod postinstall;
 pub mod preinstall_bun;
+pub mod projects;
+pub mod registry;
 pub mod repos;
 pub mod runtime_resolver;
 pub mod second_coming;
@@ -24,9 +32,13 @@ pub mod typosquatting;
 pub mod verification;
 pub mod workflow;
 pub mod workflows_new;
+pub mod yarn_lockfile;
+
+pub use registry::{Detector, ScanContext};
 
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
 
 /// Finding severity levels
 /// Corresponds to bash risk level prefixes (HIGH RISK, MEDIUM RISK, LOW RISK)
@@ -47,6 +59,9 @@ pub struct Finding {
     pub category: String,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub verification: Option<verification::VerificationStatus>,
+    /// Project the file belongs to (nearest lockfile or workspace root), if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub project_root: Option<PathBuf>,
 }
 
 // Custom serialization to normalize Windows UNC paths (\\?\C:\...)
@@ -57,18 +72,21 @@ impl Serialize for Finding {
     {
         use serde::ser::SerializeStruct;
 
-        // Normalize file_path: remove \\?\ prefix and convert to forward slashes
-        let path_str = self.file_path.to_string_lossy();
-        let normalized = path_str
-            .strip_prefix(r"\\?\")
-            .unwrap_or(&path_str)
-            .replace('\\', "/");
+        // Normalize paths: remove \\?\ prefix and convert to forward slashes
+        let normalize = |path: &Path| {
+            let path_str = path.to_string_lossy();
+            path_str
+                .strip_prefix(r"\\?\")
+                .unwrap_or(&path_str)
+                .replace('\\', "/")
+        };
 
-        // Count fields: 4 base fields + 1 optional verification field
-        let field_count = if self.verification.is_some() { 5 } else { 4 };
+        // Count fields: 4 base fields + optional verification and project_root fields
+        let field_count =
+            4 + usize::from(self.verification.is_some()) + usize::from(self.project_root.is_some());
 
         let mut state = serializer.serialize_struct("Finding", field_count)?;
-        state.serialize_field("file_path", &normalized)?;
+        state.serialize_field("file_path", &normalize(&self.file_path))?;
         state.serialize_field("message", &self.message)?;
         state.serialize_field("risk_level", &self.risk_level)?;
         state.serialize_field("category", &self.category)?;
@@ -77,6 +95,9 @@ impl Serialize for Finding {
         if let Some(ref verification) = self.verification {
             state.serialize_field("verification", verification)?;
         }
+        if let Some(ref project_root) = self.project_root {
+            state.serialize_field("project_root", &normalize(project_root))?;
+        }
 
         state.end()
     }
@@ -90,45 +111,122 @@ impl Finding {
             risk_level,
             category: category.to_string(),
             verification: None,
+            project_root: None,
         }
     }
 }
 
-/// Collection of all findings from a scan
-/// Corresponds to bash global arrays: `WORKFLOW_FILES`, `MALICIOUS_HASHES`, etc.
-#[derive(Debug, Default, Clone, Serialize, Deserialize)]
-pub struct ScanResults {
-    pub workflow_files: Vec<Finding>,
-    pub malicious_hashes: Vec<Finding>,
-    pub compromised_found: Vec<Finding>,
-    pub suspicious_found: Vec<Finding>,
-    pub lockfile_safe_versions: Vec<Finding>, // NEW: Packages safe due to lockfile
-    pub suspicious_content: Vec<Finding>,
-    pub crypto_patterns: Vec<Finding>,
-    pub git_branches: Vec<Finding>,
-    pub postinstall_hooks: Vec<Finding>,
-    pub trufflehog_activity: Vec<Finding>,
-    pub shai_hulud_repos: Vec<Finding>,
-    pub namespace_warnings: Vec<Finding>,
-    pub integrity_issues: Vec<Finding>,
-    pub typosquatting_warnings: Vec<Finding>,
-    pub network_exfiltration_warnings: Vec<Finding>,
-
-    // November 2025 "Shai-Hulud: The Second Coming" Attack detectors
-    pub bun_setup_files: Vec<Finding>,
-    pub bun_environment_files: Vec<Finding>,
-    pub new_workflow_files: Vec<Finding>,
-    pub actions_secrets_files: Vec<Finding>,
-    pub discussion_workflows: Vec<Finding>,
-    pub github_runners: Vec<Finding>,
-    pub destructive_patterns: Vec<Finding>,
-    pub preinstall_bun_patterns: Vec<Finding>,
-    pub github_sha1hulud_runners: Vec<Finding>,
-    pub second_coming_repos: Vec<Finding>,
-
-    // BASH COMPATIBILITY: Track counts for suppressed low risk findings
-    #[serde(skip)] // Don't include in JSON
-    pub suppressed_namespace_count: usize,
+/// How the findings of a results bucket enter the risk counts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Counting {
+    /// Every finding counts as HIGH / MEDIUM / LOW RISK
+    High,
+    Medium,
+    Low,
+    /// MEDIUM RISK, only in paranoid mode and at most 5 (BASH EXACT LINE 1523/1545)
+    ParanoidMedium,
+    /// Every finding counts at its own risk level
+    ByLevel,
+    /// Informational, not counted
+    Informational,
+}
+
+// Helper: Declare ScanResults with one field per dedicated results bucket, and the
+// bucket lookup, iteration and risk counting over the same list, so a new bucket
+// can't be left out of any of them
+macro_rules! scan_results {
+    ($($(#[$meta:meta])* $field:ident: $counting:ident,)*) => {
+        /// Collection of all findings from a scan
+        /// Corresponds to bash global arrays: `WORKFLOW_FILES`, `MALICIOUS_HASHES`, etc.
+        #[derive(Debug, Default, Clone, Serialize, Deserialize)]
+        pub struct ScanResults {
+            $($(#[$meta])* pub $field: Vec<Finding>,)*
+
+            // Findings from registered detectors without a dedicated field, keyed by detector id
+            #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+            pub additional_findings: BTreeMap<String, Vec<Finding>>,
+
+            // Findings suppressed by .shai-hulud-ignore.toml (excluded from risk counts)
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub suppressed: Vec<crate::suppressions::SuppressedFinding>,
+
+            // IOC feeds used for this scan (source, fetch time, ETag/Last-Modified, SHA-256)
+            #[serde(default, skip_serializing_if = "Vec::is_empty")]
+            pub feeds: Vec<crate::feeds::FeedMetadata>,
+
+            // BASH COMPATIBILITY: Track counts for suppressed low risk findings
+            #[serde(skip)] // Don't include in JSON
+            pub suppressed_namespace_count: usize,
+        }
+
+        impl ScanResults {
+            /// Get the findings bucket for a results key (field name or detector id)
+            pub fn bucket_mut(&mut self, key: &str) -> &mut Vec<Finding> {
+                match key {
+                    $(stringify!($field) => &mut self.$field,)*
+                    _ => self.additional_findings.entry(key.to_string()).or_default(),
+                }
+            }
+
+            /// All results buckets as (key, findings), in JSON field order
+            pub fn buckets(&self) -> Vec<(&str, &Vec<Finding>)> {
+                let mut buckets: Vec<(&str, &Vec<Finding>)> =
+                    vec![$((stringify!($field), &self.$field),)*];
+                buckets.extend(
+                    self.additional_findings
+                        .iter()
+                        .map(|(key, findings)| (key.as_str(), findings)),
+                );
+                buckets
+            }
+
+            /// Mutable access to every results bucket (dedicated fields and additional findings)
+            fn buckets_mut(&mut self) -> Vec<&mut Vec<Finding>> {
+                let mut buckets = vec![$(&mut self.$field,)*];
+                buckets.extend(self.additional_findings.values_mut());
+                buckets
+            }
+
+            /// Risk counting of a results bucket (additional findings count at their own level)
+            fn counting(key: &str) -> Counting {
+                match key {
+                    $(stringify!($field) => Counting::$counting,)*
+                    _ => Counting::ByLevel,
+                }
+            }
+        }
+    };
+}
+
+scan_results! {
+    workflow_files: High,
+    malicious_hashes: High,
+    compromised_found: High,
+    suspicious_found: Medium,
+    /// NEW: Packages safe due to lockfile
+    lockfile_safe_versions: Informational,
+    suspicious_content: Medium,
+    crypto_patterns: ByLevel,
+    git_branches: Medium,
+    postinstall_hooks: High,
+    trufflehog_activity: ByLevel,
+    shai_hulud_repos: High,
+    namespace_warnings: Low,
+    integrity_issues: Medium,
+    typosquatting_warnings: ParanoidMedium,
+    network_exfiltration_warnings: ParanoidMedium,
+
+    // November 2025 "Shai-Hulud: The Second Coming" Attack detectors (all HIGH RISK)
+    bun_setup_files: High,
+    bun_environment_files: High,
+    new_workflow_files: High,
+    actions_secrets_files: High,
+    discussion_workflows: High,
+    github_runners: High,
+    destructive_patterns: High,
+    preinstall_bun_patterns: High,
+    github_sha1hulud_runners: High,
+    second_coming_repos: High,
 }
 
 impl ScanResults {
@@ -136,89 +234,108 @@ impl ScanResults {
         Self::default()
     }
 
-    pub fn high_risk_count(&self) -> usize {
-        let arrays = [
-            &self.workflow_files,
-            &self.malicious_hashes,
-            &self.compromised_found,
-            &self.postinstall_hooks,
-            &self.shai_hulud_repos,
-            // November 2025 "Second Coming" detectors (all HIGH RISK)
-            &self.bun_setup_files,
-            &self.bun_environment_files,
-            &self.new_workflow_files,
-            &self.actions_secrets_files,
-            &self.discussion_workflows,
-            &self.github_runners,
-            &self.destructive_patterns,
-            &self.preinstall_bun_patterns,
-            &self.github_sha1hulud_runners,
-            &self.second_coming_repos,
-        ];
-
-        arrays.iter().map(|arr| arr.len()).sum::<usize>()
-            + self
-                .crypto_patterns
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::High)
-                .count()
-            + self
-                .trufflehog_activity
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::High)
-                .count()
+    /// Store findings produced by a registered detector in their results bucket
+    pub fn record(&mut self, detector: &dyn Detector, findings: Vec<Finding>) {
+        for finding in findings {
+            let key = detector.results_key(&finding);
+            self.bucket_mut(key).push(finding);
+        }
     }
 
-    pub fn medium_risk_count(&self, paranoid_mode: bool) -> usize {
-        let arrays = [
-            &self.suspicious_found,
-            &self.suspicious_content,
-            &self.git_branches,
-            &self.integrity_issues,
-        ];
-
-        // BASH EXACT LINE 1523/1545: Only count first 5 typo/network IN paranoid mode
-        let typo_count = if paranoid_mode {
-            self.typosquatting_warnings.len().min(5)
-        } else {
-            0 // Not counted in normal mode
-        };
+    /// Keep only findings matching `keep` in every bucket
+    /// Returns: Removed findings (in bucket order)
+    pub fn retain_findings(&mut self, mut keep: impl FnMut(&Finding) -> bool) -> Vec<Finding> {
+        let mut removed = Vec::new();
+        for bucket in self.buckets_mut() {
+            let (kept, dropped): (Vec<Finding>, Vec<Finding>) =
+                std::mem::take(bucket).into_iter().partition(|f| keep(f));
+            *bucket = kept;
+            removed.extend(dropped);
+        }
+        self.additional_findings
+            .retain(|_, findings| !findings.is_empty());
+        removed
+    }
 
-        let network_count = if paranoid_mode {
-            self.network_exfiltration_warnings.len().min(5)
-        } else {
-            0 // Not counted in normal mode
-        };
+    /// Apply `f` to every finding in every bucket
+    pub fn for_each_finding_mut(&mut self, mut f: impl FnMut(&mut Finding)) {
+        for bucket in self.buckets_mut() {
+            bucket.iter_mut().for_each(&mut f);
+        }
+    }
+
+    /// Every finding across all buckets
+    pub fn all_findings(&self) -> impl Iterator<Item = &Finding> {
+        self.buckets()
+            .into_iter()
+            .flat_map(|(_, findings)| findings)
+    }
 
-        arrays.iter().map(|arr| arr.len()).sum::<usize>()
-            + typo_count
-            + network_count
-            + self
-                .crypto_patterns
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::Medium)
-                .count()
-            + self
-                .trufflehog_activity
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::Medium)
-                .count()
+    // Function: risk_count
+    // Purpose: Count the findings entering the risk summary at one level
+    // Args: level - risk level, paranoid_mode - count typosquatting/network warnings
+    // Returns: Number of findings across all buckets
+    fn risk_count(&self, level: &RiskLevel, paranoid_mode: bool) -> usize {
+        self.buckets()
+            .into_iter()
+            .map(|(key, findings)| match (Self::counting(key), level) {
+                (Counting::High, RiskLevel::High)
+                | (Counting::Medium, RiskLevel::Medium)
+                | (Counting::Low, RiskLevel::Low) => findings.len(),
+                (Counting::ParanoidMedium, RiskLevel::Medium) if paranoid_mode => {
+                    findings.len().min(5)
+                }
+                (Counting::ByLevel, _) => {
+                    findings.iter().filter(|f| f.risk_level == *level).count()
+                }
+                _ => 0,
+            })
+            .sum()
+    }
+
+    pub fn high_risk_count(&self) -> usize {
+        self.risk_count(&RiskLevel::High, false)
+    }
+
+    pub fn medium_risk_count(&self, paranoid_mode: bool) -> usize {
+        self.risk_count(&RiskLevel::Medium, paranoid_mode)
     }
 
     pub fn low_risk_count(&self) -> usize {
         // NOTE: lockfile_safe_versions are NOT counted in low_risk (they're informational only)
         // Include both actual namespace warnings AND suppressed ones (for bash compatibility)
-        self.namespace_warnings.len()
-            + self.suppressed_namespace_count
-            + self
-                .crypto_patterns
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::Low)
-                .count()
-            + self
-                .trufflehog_activity
-                .iter()
-                .filter(|f| f.risk_level == RiskLevel::Low)
-                .count()
+        self.risk_count(&RiskLevel::Low, false) + self.suppressed_namespace_count
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finding(level: RiskLevel) -> Finding {
+        Finding::new(PathBuf::from("/p"), String::new(), level, "test")
+    }
+
+    #[test]
+    fn test_risk_counts_per_bucket() {
+        let mut results = ScanResults::new();
+        results.compromised_found.push(finding(RiskLevel::Medium));
+        results.integrity_issues.push(finding(RiskLevel::High));
+        results.lockfile_safe_versions.push(finding(RiskLevel::Low));
+        results.crypto_patterns.push(finding(RiskLevel::Low));
+        for _ in 0..7 {
+            results
+                .typosquatting_warnings
+                .push(finding(RiskLevel::Medium));
+        }
+        results
+            .bucket_mut("future_detector")
+            .push(finding(RiskLevel::High));
+
+        assert_eq!(results.high_risk_count(), 2);
+        assert_eq!(results.medium_risk_count(false), 1);
+        assert_eq!(results.medium_risk_count(true), 6);
+        assert_eq!(results.low_risk_count(), 1);
+        assert_eq!(results.buckets().len(), 26);
     }
 }
@@ -1,12 +1,10 @@
 // Network Exfiltration Detector
 // Rust port of: check_network_exfiltration()
 
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use regex::Regex;
-use std::fs;
-use std::path::Path;
 use std::sync::LazyLock;
-use walkdir::WalkDir;
 
 static IP_PATTERN: LazyLock<Regex> =
     LazyLock::new(|| Regex::new(r"\b(?:[0-9]{1,3}\.){3}[0-9]{1,3}\b").unwrap());
@@ -38,25 +36,15 @@ const SUSPICIOUS_DOMAINS: &[&str] = &[
 
 // Function: check_network_exfiltration
 // Purpose: Detect network exfiltration patterns including suspicious domains and IPs
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: NETWORK_EXFILTRATION_WARNINGS (global array)
 // Returns: Populates array with hardcoded IPs and suspicious domains
 #[allow(clippy::too_many_lines)]
-pub fn check_network_exfiltration<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_network_exfiltration(index: &FileIndex) -> Vec<Finding> {
     let mut findings = Vec::new();
     let extensions = &["js", "ts", "json", "mjs"];
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-    {
+    for entry in index.files_with_extensions(extensions) {
         let path_str = entry.path().to_string_lossy();
 
         // BASH LINE 1102, 1116, 1162, 1219: Skip vendor/library files AND node_modules
@@ -68,14 +56,14 @@ pub fn check_network_exfiltration<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
             continue;
         }
 
-        if let Ok(content) = fs::read_to_string(entry.path()) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             // BASH LINE 1102-1112: Check for hardcoded IP addresses (skip vendor/node_modules)
-            if let Some(captures) = IP_PATTERN.find_iter(&content).next() {
+            if let Some(captures) = IP_PATTERN.find_iter(content).next() {
                 let ip = captures.as_str();
                 // BASH LINE 1108: Skip common safe IPs
                 if ip != "127.0.0.1" && ip != "0.0.0.0" && ip != "255.255.255.255" {
                     let ips: Vec<_> = IP_PATTERN
-                        .find_iter(&content)
+                        .find_iter(content)
                         .take(3)
                         .map(|m| m.as_str())
                         .collect();
@@ -241,7 +229,7 @@ pub fn check_network_exfiltration<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
             // BASH LINE 1194-1209: Check for WebSocket connections to unusual endpoints
             if content.contains("ws://") || content.contains("wss://") {
                 // BASH extracts all ws:// endpoints first, then filters
-                for cap in WS_REGEX.find_iter(&content) {
+                for cap in WS_REGEX.find_iter(content) {
                     let endpoint = cap.as_str();
                     // BASH LINE 1202: Skip localhost/127.0.0.1
                     if !endpoint.contains("localhost") && !endpoint.contains("127.0.0.1") {
@@ -327,3 +315,32 @@ pub fn check_network_exfiltration<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_network_exfiltration()` (paranoid mode only)
+pub struct NetworkExfiltrationDetector;
+
+impl Detector for NetworkExfiltrationDetector {
+    fn id(&self) -> &'static str {
+        "network_exfiltration_warnings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Network exfiltration patterns (paranoid)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn paranoid_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            "Checking for network exfiltration patterns...",
+        );
+        check_network_exfiltration(ctx.index)
+    }
+}
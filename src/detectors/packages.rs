@@ -3,32 +3,52 @@
 // Updated to match PR #84 changes: only exact matches, no semver matching for package.json
 
 use crate::data::CompromisedPackage;
-use crate::detectors::{lockfile_resolver::LockfileResolver, Finding, RiskLevel};
+use crate::detectors::{
+    exposure::OverrideField,
+    projects::{in_node_modules, ProjectIndex, ProjectResolvers},
+    verification::{verify_via_lockfile, VerificationStatus},
+    Detector, Finding, RiskLevel, ScanContext,
+};
+use crate::file_index::FileIndex;
+use crate::osv::{self, Advisories};
 use serde_json::Value;
 use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+
+/// `check_packages()` results: compromised_found, suspicious_found, lockfile_safe_versions,
+/// namespace_warnings, dependency_sources
+pub type PackageFindings = (
+    Vec<Finding>,
+    Vec<Finding>,
+    Vec<Finding>,
+    Vec<Finding>,
+    Vec<Finding>,
+);
 
 // Function: check_packages
 // Purpose: Scan package.json files for compromised packages and suspicious namespaces
-// Args: $1 = scan_dir (directory to scan), compromised_packages - set of known bad packages
-//       lockfile_resolver - optional lockfile for verification (unused after PR #84)
+// Args: index - file index of scan_dir, compromised_packages - set of known bad packages
+//       advisories - OSV advisories (ranges, IDs appended to the finding message)
+//       compromised_namespaces - namespaces to warn about (in IOC file order)
+//       resolvers - per-project resolvers (--verify); every installed copy of a compromised
+//       package in the package.json's own project is checked and attached as verification
 // Modifies: COMPROMISED_FOUND, NAMESPACE_WARNINGS (global arrays)
-// Returns: Populates arrays with exact matches only (no semver matching per PR #84)
+// Returns: Populates arrays with exact matches only (no semver matching per PR #84), plus
+//          dependency_sources - git, tarball and local path dependencies (Rust-only)
+//
+// Dependency values are decoded first (parse_dependency_spec): npm: aliases and Yarn
+// patch: specifiers are matched as the real package, workspace: packages are skipped.
 //
 // PR #84 CHANGE: The bash scanner now uses comm -12 for O(n) set intersection
 // This means ONLY exact "package_name:version" matches are found - no semver matching.
 // The old semver matching logic was removed for performance.
-#[allow(clippy::needless_pass_by_value)]
-pub fn check_packages<P: AsRef<Path>>(
-    scan_dir: P,
+pub fn check_packages(
+    index: &FileIndex,
     compromised_packages: &HashSet<CompromisedPackage>,
-    _lockfile_resolver: Option<&LockfileResolver>,
-    _runtime_resolver: Option<&mut crate::detectors::runtime_resolver::RuntimeResolver>,
-) -> (Vec<Finding>, Vec<Finding>, Vec<Finding>, Vec<Finding>) {
-    let scan_dir = scan_dir.as_ref();
-    let files_count = crate::utils::count_files_by_name(scan_dir, "package.json");
+    advisories: &Advisories,
+    compromised_namespaces: &[String],
+    mut resolvers: Option<&mut ProjectResolvers>,
+) -> PackageFindings {
+    let files_count = index.files_named("package.json").count();
 
     crate::colors::print_status(
         crate::colors::Color::Blue,
@@ -39,22 +59,19 @@ pub fn check_packages<P: AsRef<Path>>(
     let suspicious_found = Vec::new(); // No longer used after PR #84
     let lockfile_safe_versions = Vec::new(); // No longer used after PR #84
     let mut namespace_warnings = Vec::new();
+    let mut dependency_sources = Vec::new();
 
     let mut processed = 0;
 
     // Collect and sort package.json files for consistent order
-    let mut package_files: Vec<_> = WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file() && e.file_name() == "package.json")
-        .collect();
+    let mut package_files: Vec<_> = index.files_named("package.json").collect();
 
     // Sort by path for deterministic order matching Bash's find
     package_files.sort_by(|a, b| a.path().cmp(b.path()));
 
     for entry in package_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if let Ok(json) = serde_json::from_str::<Value>(content) {
                 // Check dependencies sections
                 for section in &[
                     "dependencies",
@@ -65,23 +82,68 @@ pub fn check_packages<P: AsRef<Path>>(
                     if let Some(deps) = json.get(section).and_then(|v| v.as_object()) {
                         // PR #84: Iterate through dependencies and check for EXACT matches only
                         // No semver matching - just check if "package_name:version" exists in compromised set
-                        for (package_name, package_version) in deps {
-                            let version_str = package_version.as_str().unwrap_or("");
+                        for (dependency_name, package_version) in deps {
+                            let specifier = package_version.as_str().unwrap_or("");
+
+                            // Aliases and patches resolve to the package actually installed;
+                            // git, tarball and local sources have no registry version
+                            let (package_name, version_str) =
+                                match parse_dependency_spec(dependency_name, specifier) {
+                                    DependencySpec::Registry { name, range } => (name, range),
+                                    DependencySpec::Workspace => continue,
+                                    source => {
+                                        if !in_node_modules(entry.path()) {
+                                            dependency_sources.push(dependency_source_finding(
+                                                entry.path(),
+                                                dependency_name,
+                                                specifier,
+                                                &source,
+                                            ));
+                                        }
+                                        continue;
+                                    }
+                                };
+                            let (package_name, version_str) =
+                                (package_name.as_str(), version_str.as_str());
 
                             // PR #84: Exact match only - no semver
                             // Check if this exact package:version is in the compromised list
                             let lookup_key = CompromisedPackage {
-                                name: package_name.clone(),
+                                name: package_name.to_string(),
                                 version: version_str.to_string(),
                             };
 
-                            if compromised_packages.contains(&lookup_key) {
-                                compromised_found.push(Finding::new(
+                            // OSV advisories also match exact versions inside their ranges
+                            let references = advisories.references(package_name, version_str);
+
+                            if compromised_packages.contains(&lookup_key) || references.is_some() {
+                                let message = if package_name == dependency_name {
+                                    format!("{package_name}@{version_str}")
+                                } else {
+                                    format!(
+                                        "{package_name}@{version_str} (alias {dependency_name})"
+                                    )
+                                };
+                                let mut finding = Finding::new(
                                     entry.path().to_path_buf(),
-                                    format!("{package_name}@{version_str}"),
+                                    osv::with_references(message, references),
                                     RiskLevel::High,
                                     "compromised_package",
-                                ));
+                                );
+                                // --verify: evaluate every installed copy, not just the declared one
+                                if let Some(resolvers) = resolvers.as_deref_mut() {
+                                    let (lockfile, runtime) = resolvers.resolvers_for(entry.path());
+                                    let status = verify_via_lockfile(
+                                        package_name,
+                                        lockfile,
+                                        runtime,
+                                        compromised_packages,
+                                    );
+                                    if !matches!(status, VerificationStatus::Unknown) {
+                                        finding.verification = Some(status);
+                                    }
+                                }
+                                compromised_found.push(finding);
                             }
                         }
                     }
@@ -90,7 +152,7 @@ pub fn check_packages<P: AsRef<Path>>(
                 // Check for suspicious namespaces - BASH EXACT: warn for EACH namespace found
                 // Bash script: warns once per namespace per file
                 let package_str = serde_json::to_string(&json).unwrap_or_default();
-                for namespace in crate::data::COMPROMISED_NAMESPACES {
+                for namespace in compromised_namespaces {
                     if package_str.contains(&format!("\"{namespace}/")) {
                         namespace_warnings.push(Finding::new(
                             // BASH EXACT: Use "Namespace warning" as file_path for compatibility
@@ -123,44 +185,228 @@ pub fn check_packages<P: AsRef<Path>>(
         suspicious_found,
         lockfile_safe_versions,
         namespace_warnings,
+        dependency_sources,
     )
 }
 
+/// A package.json dependency value, decoded
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DependencySpec {
+    /// Registry version or range of `name` (npm: aliases and patch: resolve to the real package)
+    Registry { name: String, range: String },
+    /// workspace: protocol - a package of the same monorepo
+    Workspace,
+    /// git URL or hosted git shorthand ("github:user/repo", "user/repo#v1")
+    Git(String),
+    /// http(s) tarball URL
+    Tarball(String),
+    /// file:, link:, portal: or a relative / absolute path
+    LocalPath(String),
+}
+
+// Function: parse_dependency_spec
+// Purpose: Decode the protocol of a package.json dependency value
+// Args: name - dependency key, spec - its value, e.g. "^1.0.0", "npm:@evil/lodash@1.0.0",
+//       "workspace:*", "link:../lib", "file:../lib.tgz", "git+https://...", "patch:..."
+// Returns: DependencySpec (plain versions and ranges: Registry with the same name)
+pub fn parse_dependency_spec(name: &str, spec: &str) -> DependencySpec {
+    let registry = |name: &str, range: &str| DependencySpec::Registry {
+        name: name.to_string(),
+        range: range.to_string(),
+    };
+
+    if let Some(target) = spec.strip_prefix("npm:") {
+        // npm:lodash@^4 / npm:@scope/pkg@1.0.0 / npm:lodash (latest) / npm:^4 (Yarn Berry)
+        return match split_descriptor(target) {
+            Some((target_name, range)) => registry(target_name, range),
+            None if crate::semver::Range::parse(target).is_some() => registry(name, target),
+            None => registry(target, "latest"),
+        };
+    }
+    if let Some(patch) = spec.strip_prefix("patch:") {
+        // Yarn Berry: patch:lodash@npm%3A4.17.21#./.yarn/patches/lodash.patch
+        let descriptor = percent_decode(patch.split('#').next().unwrap_or_default());
+        return match split_descriptor(&descriptor) {
+            Some((target_name, range)) => parse_dependency_spec(target_name, range),
+            None => registry(name, &descriptor),
+        };
+    }
+    if spec.starts_with("workspace:") {
+        return DependencySpec::Workspace;
+    }
+
+    let source = spec.to_string();
+    let lower = spec.to_ascii_lowercase();
+    if ["file:", "link:", "portal:", "./", "../", "/", "~/"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+    {
+        return DependencySpec::LocalPath(source);
+    }
+    if ["git:", "git+", "github:", "gitlab:", "bitbucket:", "gist:"]
+        .iter()
+        .any(|prefix| lower.starts_with(prefix))
+    {
+        return DependencySpec::Git(source);
+    }
+    if lower.starts_with("http://") || lower.starts_with("https://") {
+        let path = lower.split('#').next().unwrap_or_default();
+        return if path.ends_with(".git") {
+            DependencySpec::Git(source)
+        } else {
+            DependencySpec::Tarball(source)
+        };
+    }
+    // GitHub shorthand: user/repo, user/repo#ref
+    let repo = spec.split('#').next().unwrap_or_default();
+    if !spec.starts_with('@')
+        && crate::semver::Range::parse(spec).is_none()
+        && repo.split('/').count() == 2
+        && repo
+            .split('/')
+            .all(|part| !part.is_empty() && !part.contains(' '))
+    {
+        return DependencySpec::Git(source);
+    }
+    registry(name, spec)
+}
+
+// Helper: "@scope/pkg@^1.0.0" -> ("@scope/pkg", "^1.0.0")
+fn split_descriptor(descriptor: &str) -> Option<(&str, &str)> {
+    let at = descriptor.get(1..)?.find('@')? + 1;
+    Some((&descriptor[..at], &descriptor[at + 1..]))
+}
+
+// Helper: Decode %XX escapes ("npm%3A4.17.21" -> "npm:4.17.21")
+fn percent_decode(text: &str) -> String {
+    let mut bytes = Vec::with_capacity(text.len());
+    let mut rest = text.as_bytes();
+    while let Some((&byte, tail)) = rest.split_first() {
+        let escaped = (byte == b'%')
+            .then(|| tail.get(..2))
+            .flatten()
+            .and_then(|hex| u8::from_str_radix(std::str::from_utf8(hex).ok()?, 16).ok());
+        match escaped {
+            Some(decoded) => {
+                bytes.push(decoded);
+                rest = &tail[2..];
+            }
+            None => {
+                bytes.push(byte);
+                rest = tail;
+            }
+        }
+    }
+    String::from_utf8_lossy(&bytes).into_owned()
+}
+
+// Helper: Finding for a dependency that bypasses the registry
+// NOTE: LOW (informational) - a github: dependency is not a compromise and must not
+//       fail CI on its own
+fn dependency_source_finding(
+    path: &std::path::Path,
+    name: &str,
+    spec: &str,
+    source: &DependencySpec,
+) -> Finding {
+    let kind = match source {
+        DependencySpec::Git(_) => "git",
+        DependencySpec::Tarball(_) => "a tarball URL",
+        _ => "a local path",
+    };
+    Finding::new(
+        path.to_path_buf(),
+        format!("Dependency from {kind}: {name} ({spec})"),
+        RiskLevel::Low,
+        "dependency_source",
+    )
+}
+
+/// Registry entry for `check_packages()`
+pub struct PackagesDetector;
+
+impl Detector for PackagesDetector {
+    fn id(&self) -> &'static str {
+        "packages"
+    }
+
+    fn description(&self) -> &'static str {
+        "Compromised package versions and compromised namespaces in package.json"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn results_key(&self, finding: &Finding) -> &'static str {
+        match finding.category.as_str() {
+            "compromised_package" => "compromised_found",
+            "namespace_warning" => "namespace_warnings",
+            "lockfile_safe_version" => "lockfile_safe_versions",
+            "dependency_source" => "dependency_sources",
+            _ => "suspicious_found",
+        }
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        let (comp, susp, lockfile_safe, ns, sources) = check_packages(
+            ctx.index,
+            ctx.compromised_packages,
+            ctx.advisories,
+            &ctx.iocs.compromised_namespaces,
+            ctx.resolvers.as_deref_mut(),
+        );
+        comp.into_iter()
+            .chain(susp)
+            .chain(lockfile_safe)
+            .chain(ns)
+            .chain(sources)
+            .collect()
+    }
+}
+
 // Function: check_semver_ranges
-// Purpose: Check if package.json semver ranges (^, ~) could resolve to compromised versions
-// Args: scan_dir, compromised_packages, lockfile_resolver
-// Returns: lockfile_safe_versions - LOW risk findings
-#[allow(clippy::needless_pass_by_value)]
-pub fn check_semver_ranges<P: AsRef<Path>>(
-    scan_dir: P,
+// Purpose: Exposure analysis of every package.json specifier (any node-semver range, exact
+//          version or dist-tag) against the compromised versions of its package
+// Args: index, compromised_packages, resolvers - per-project lockfiles (--verify; without
+//       it each project's lockfile is loaded here)
+// Returns: lockfile_safe_versions - LOW risk findings listing the compromised versions a
+//          specifier admits, the lockfile pin, a safe range and an overrides/resolutions entry
+pub fn check_semver_ranges(
+    index: &FileIndex,
     compromised_packages: &HashSet<CompromisedPackage>,
-    lockfile_resolver: Option<&LockfileResolver>,
+    resolvers: Option<&mut ProjectResolvers>,
 ) -> Vec<Finding> {
-    let scan_dir = scan_dir.as_ref();
-    let files_count = crate::utils::count_files_by_name(scan_dir, "package.json");
+    let files_count = index.files_named("package.json").count();
 
     crate::colors::print_status(
         crate::colors::Color::Blue,
         &format!("Checking {files_count} package.json files for semver ranges that could resolve to compromised versions..."),
     );
 
+    // The pin status needs the project lockfile even without --verify
+    let mut local_resolvers = None;
+    let resolvers = match resolvers {
+        Some(resolvers) => resolvers,
+        None => local_resolvers.insert(ProjectResolvers::new(
+            ProjectIndex::build(index),
+            index.root(),
+        )),
+    };
+
     let mut lockfile_safe_versions = Vec::new();
 
     let mut processed = 0;
 
     // Collect and sort package.json files for consistent order
-    let mut package_files: Vec<_> = WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file() && e.file_name() == "package.json")
-        .collect();
+    let mut package_files: Vec<_> = index.files_named("package.json").collect();
 
     // Sort by path for deterministic order matching Bash's find
     package_files.sort_by(|a, b| a.path().cmp(b.path()));
 
     for entry in package_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if let Ok(json) = serde_json::from_str::<Value>(content) {
                 // Check dependencies sections
                 for section in &[
                     "dependencies",
@@ -169,63 +415,61 @@ pub fn check_semver_ranges<P: AsRef<Path>>(
                     "optionalDependencies",
                 ] {
                     if let Some(deps) = json.get(section).and_then(|v| v.as_object()) {
-                        for (package_name, package_version) in deps {
-                            let version_range = package_version.as_str().unwrap_or("");
-
-                            // Only check ranges that start with ^ or ~
-                            if !version_range.starts_with('^') && !version_range.starts_with('~') {
+                        for (dependency_name, package_version) in deps {
+                            // Aliases are analyzed as the package they install
+                            let DependencySpec::Registry {
+                                name: package_name,
+                                range: specifier,
+                            } = parse_dependency_spec(
+                                dependency_name,
+                                package_version.as_str().unwrap_or(""),
+                            )
+                            else {
+                                continue;
+                            };
+                            let (package_name, specifier) = (&package_name, specifier.as_str());
+                            if !compromised_packages.iter().any(|c| c.name == *package_name) {
                                 continue;
                             }
 
-                            // Find compromised versions for this package
-                            for comp in compromised_packages
-                                .iter()
-                                .filter(|c| c.name == *package_name)
-                            {
-                                if crate::semver::semver_match(&comp.version, version_range) {
-                                    let file_path = entry.path().to_path_buf();
-                                    let message = format!("{package_name}@{version_range} (could match {comp_version})", comp_version = comp.version);
-
-                                    // Check if lockfile protects against this
-                                    let locked_version = lockfile_resolver
-                                        .and_then(|lr| lr.get_version(package_name));
-
-                                    if let Some(locked) = locked_version {
-                                        // Lockfile exists - check if locked version is safe
-                                        if locked == comp.version {
-                                            // Lockfile pins to compromised version - still LOW risk (informational)
-                                            lockfile_safe_versions.push(Finding::new(
-                                                file_path,
-                                                format!("{message}, locked to {locked}"),
-                                                RiskLevel::Low,
-                                                "lockfile_safe_version",
-                                            ));
-                                        } else {
-                                            // Safe due to lockfile
-                                            lockfile_safe_versions.push(Finding::new(
-                                                file_path,
-                                                format!("{message}, locked to {locked}"),
-                                                RiskLevel::Low,
-                                                "lockfile_safe_version",
-                                            ));
-                                        }
-                                    } else {
-                                        // No lockfile - LOW risk (packages largely unpublished, only matters with stale caches)
-                                        lockfile_safe_versions.push(Finding::new(
-                                            file_path,
-                                            format!(
-                                                "{message} (no lockfile, could resolve to {})",
-                                                comp.version
-                                            ),
-                                            RiskLevel::Low,
-                                            "lockfile_safe_version",
-                                        ));
-                                    }
+                            // Every locked copy counts, a compromised nested copy is listed first
+                            let lockfile_resolver = resolvers.lockfile_for(entry.path());
+                            let lockfile_type = lockfile_resolver.and_then(|lr| lr.lockfile_type);
+                            let locked = lockfile_resolver.map(|lr| {
+                                let instances = lr.instances(package_name);
+                                let mut versions: Vec<String> = instances
+                                    .iter()
+                                    .filter(|i| {
+                                        compromised_packages.contains(&CompromisedPackage::new(
+                                            package_name.clone(),
+                                            i.version.clone(),
+                                        ))
+                                    })
+                                    .chain(instances.iter())
+                                    .map(|i| i.version.clone())
+                                    .collect();
+                                let mut seen = HashSet::new();
+                                versions.retain(|v| seen.insert(v.clone()));
+                                versions
+                            });
+                            // A lockfile without the package pins nothing for it
+                            let locked = locked.filter(|versions| !versions.is_empty());
 
-                                    // Found a match, no need to check other versions for this package
-                                    break;
-                                }
-                            }
+                            let Some(exposure) = crate::detectors::exposure::analyze_specifier(
+                                package_name,
+                                specifier,
+                                compromised_packages,
+                                locked,
+                            ) else {
+                                continue;
+                            };
+                            let field = OverrideField::for_lockfile(lockfile_type);
+                            lockfile_safe_versions.push(Finding::new(
+                                entry.path().to_path_buf(),
+                                format!("{exposure}, override: {}", exposure.override_entry(field)),
+                                RiskLevel::Low,
+                                "lockfile_safe_version",
+                            ));
                         }
                     }
                 }
@@ -240,3 +484,125 @@ pub fn check_semver_ranges<P: AsRef<Path>>(
 
     lockfile_safe_versions
 }
+
+/// Registry entry for `check_semver_ranges()` (only active with --check-semver-ranges)
+pub struct SemverRangesDetector;
+
+impl Detector for SemverRangesDetector {
+    fn id(&self) -> &'static str {
+        "semver_ranges"
+    }
+
+    fn description(&self) -> &'static str {
+        "package.json semver ranges that could resolve to compromised versions"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Low
+    }
+
+    fn results_key(&self, _finding: &Finding) -> &'static str {
+        "lockfile_safe_versions"
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        if !ctx.check_semver_ranges {
+            return Vec::new();
+        }
+        check_semver_ranges(
+            ctx.index,
+            ctx.compromised_packages,
+            ctx.resolvers.as_deref_mut(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_parse_dependency_spec() {
+        let registry = |name: &str, range: &str| DependencySpec::Registry {
+            name: name.to_string(),
+            range: range.to_string(),
+        };
+        let spec = |value: &str| parse_dependency_spec("lodash", value);
+
+        assert_eq!(spec("^4.17.21"), registry("lodash", "^4.17.21"));
+        assert_eq!(spec("latest"), registry("lodash", "latest"));
+        assert_eq!(
+            spec("npm:@evil/lodash@1.0.0"),
+            registry("@evil/lodash", "1.0.0")
+        );
+        assert_eq!(spec("npm:lodash-es"), registry("lodash-es", "latest"));
+        assert_eq!(
+            spec("patch:lodash@npm%3A4.17.21#./.yarn/patches/lodash.patch"),
+            registry("lodash", "4.17.21")
+        );
+        assert_eq!(spec("workspace:*"), DependencySpec::Workspace);
+        for local in ["link:../lodash", "file:../lodash.tgz", "./vendor/lodash"] {
+            assert_eq!(spec(local), DependencySpec::LocalPath(local.to_string()));
+        }
+        for git in [
+            "git+https://github.com/lodash/lodash.git#4.17.21",
+            "github:lodash/lodash",
+            "lodash/lodash#main",
+            "https://github.com/lodash/lodash.git",
+        ] {
+            assert_eq!(spec(git), DependencySpec::Git(git.to_string()));
+        }
+        assert_eq!(
+            spec("https://example.com/lodash-4.17.21.tgz"),
+            DependencySpec::Tarball("https://example.com/lodash-4.17.21.tgz".to_string())
+        );
+    }
+
+    #[test]
+    fn test_check_packages_decodes_specifiers() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{
+  "dependencies": {
+    "tinycolor": "npm:@ctrl/tinycolor@4.1.1",
+    "lib": "workspace:*",
+    "left-pad": "git+https://github.com/left-pad/left-pad.git",
+    "vendored": "file:../vendored"
+  }
+}"#,
+        )
+        .unwrap();
+        let compromised: HashSet<_> =
+            [CompromisedPackage::from_line("@ctrl/tinycolor:4.1.1").unwrap()]
+                .into_iter()
+                .collect();
+
+        let (found, _, _, _, sources) = check_packages(
+            &FileIndex::build(temp.path(), 1),
+            &compromised,
+            &Advisories::default(),
+            &[],
+            None,
+        );
+        let messages = |findings: &[Finding]| {
+            findings
+                .iter()
+                .map(|f| format!("{:?} {}", f.risk_level, f.message))
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(
+            messages(&found),
+            vec!["High @ctrl/tinycolor@4.1.1 (alias tinycolor)"]
+        );
+        assert_eq!(
+            messages(&sources),
+            vec![
+                "Low Dependency from git: left-pad (git+https://github.com/left-pad/left-pad.git)",
+                "Low Dependency from a local path: vendored (file:../vendored)",
+            ]
+        );
+    }
+}
@@ -0,0 +1,588 @@
+// pnpm Lockfile Parser - pnpm-lock.yaml v5.x through v9.x
+// Purpose: Exact resolved versions (peer suffixes stripped) and per-importer dependencies
+//
+// Key formats by lockfile version:
+//   5.x: "/react-dom/18.2.0_react@18.2.0"      (name/version, "_" peer suffix)
+//   6.x: "/react-dom@18.2.0(react@18.2.0)"      (name@version, "(...)" peer suffix)
+//   9.x: "react-dom@18.2.0(react@18.2.0)"       (no leading slash, peers only in snapshots:)
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_yaml::{Mapping, Value};
+use std::collections::{BTreeMap, HashSet};
+
+/// Dependency sections of an importer (a workspace project)
+const DEPENDENCY_SECTIONS: [&str; 3] = ["dependencies", "devDependencies", "optionalDependencies"];
+
+/// A direct dependency of one importer
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnpmDependency {
+    /// Name as written in package.json (the alias for "npm:" aliases)
+    pub name: String,
+    /// Real package name when installed under an alias
+    pub alias_of: Option<String>,
+    /// Range from package.json (None in v5 lockfiles without "specifiers:")
+    pub specifier: Option<String>,
+    /// Exact version without peer suffix, or the raw reference ("link:../lib", tarball URL)
+    pub version: String,
+    pub section: &'static str,
+}
+
+impl PnpmDependency {
+    /// Name of the package actually installed
+    pub fn package_name(&self) -> &str {
+        self.alias_of.as_deref().unwrap_or(&self.name)
+    }
+
+    /// True for registry versions (false for link:, file:, git and tarball references)
+    pub fn is_registry_version(&self) -> bool {
+        self.version.starts_with(|c: char| c.is_ascii_digit())
+    }
+}
+
+/// A resolved package from "packages:" / "snapshots:"
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PnpmPackage {
+    pub name: String,
+    pub version: String,
+    /// Original lockfile key (including any peer suffix)
+    pub key: String,
+    /// Where the package was fetched from (empty for snapshots: entries)
+    pub resolution: PnpmResolution,
+}
+
+/// "resolution:" of a packages: entry
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PnpmResolution {
+    /// Subresource integrity, e.g. "sha512-..." (registry packages and tarballs)
+    pub integrity: Option<String>,
+    /// Tarball URL (set for tarball dependencies, not for registry packages)
+    pub tarball: Option<String>,
+    /// Git repository URL ({type: git, repo, commit})
+    pub repo: Option<String>,
+    /// Local directory (file: dependencies)
+    pub directory: Option<String>,
+}
+
+/// Parsed pnpm-lock.yaml
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PnpmLockfile {
+    /// e.g. "5.4", "6.0", "9.0"
+    pub lockfile_version: String,
+    /// Direct dependencies per importer path ("." is the root project)
+    pub importers: BTreeMap<String, Vec<PnpmDependency>>,
+    /// Every resolved package, one entry per (name, version)
+    pub packages: Vec<PnpmPackage>,
+}
+
+impl PnpmLockfile {
+    /// Parse pnpm-lock.yaml content
+    ///
+    /// pnpm 9 may prepend a second YAML document (the env lockfile) - the last
+    /// document is the project lockfile.
+    pub fn parse(content: &str) -> Result<Self> {
+        let mut root = None;
+        for document in serde_yaml::Deserializer::from_str(content) {
+            root = Some(Value::deserialize(document).context("Failed to parse pnpm-lock.yaml")?);
+        }
+        let root = root.context("pnpm-lock.yaml is empty")?;
+        let root = root
+            .as_mapping()
+            .context("pnpm-lock.yaml is not a YAML mapping")?;
+
+        let lockfile_version = root
+            .get("lockfileVersion")
+            .and_then(scalar_string)
+            .context("pnpm-lock.yaml has no lockfileVersion")?;
+        let major = major_version(&lockfile_version);
+
+        let mut importers = BTreeMap::new();
+        if let Some(map) = root.get("importers").and_then(Value::as_mapping) {
+            for (path, importer) in map {
+                if let (Some(path), Some(importer)) = (scalar_string(path), importer.as_mapping()) {
+                    importers.insert(path, parse_importer(importer, major));
+                }
+            }
+        }
+        // Single-project lockfiles (v5/v6) keep the root importer at the top level
+        if !importers.contains_key(".") {
+            let root_dependencies = parse_importer(root, major);
+            if !root_dependencies.is_empty() {
+                importers.insert(".".to_string(), root_dependencies);
+            }
+        }
+
+        let mut packages = Vec::new();
+        let mut seen = HashSet::new();
+        for section in ["packages", "snapshots"] {
+            let Some(map) = root.get(section).and_then(Value::as_mapping) else {
+                continue;
+            };
+            for (key, info) in map {
+                let Some(key) = scalar_string(key) else {
+                    continue;
+                };
+                // Git/tarball packages carry their name and version explicitly
+                let explicit = info.as_mapping().and_then(|info| {
+                    Some((
+                        info.get("name").and_then(scalar_string)?,
+                        info.get("version").and_then(scalar_string)?,
+                    ))
+                });
+                let Some((name, version)) = explicit.or_else(|| parse_package_key(&key, major))
+                else {
+                    continue;
+                };
+                if seen.insert((name.clone(), version.clone())) {
+                    let resolution = info
+                        .get("resolution")
+                        .and_then(Value::as_mapping)
+                        .map(parse_resolution)
+                        .unwrap_or_default();
+                    packages.push(PnpmPackage {
+                        name,
+                        version,
+                        key,
+                        resolution,
+                    });
+                }
+            }
+        }
+
+        Ok(Self {
+            lockfile_version,
+            importers,
+            packages,
+        })
+    }
+
+    /// Major lockfile format version (5, 6, 9)
+    pub fn major_version(&self) -> u32 {
+        major_version(&self.lockfile_version)
+    }
+}
+
+// Function: parse_package_key
+// Purpose: Split a packages:/snapshots: key into name and exact version
+// Args: key - e.g. "/@scope/pkg/1.0.0_peer@2.0.0" (v5) or "@scope/pkg@1.0.0(peer@2.0.0)" (v6+)
+//       major - lockfile major version
+// Returns: (name, version) without peer suffix, None for unparseable keys
+pub fn parse_package_key(key: &str, major: u32) -> Option<(String, String)> {
+    let key = key.strip_prefix('/').unwrap_or(key);
+
+    let (name, version) = if major < 6 {
+        // name/version - scoped names span two segments
+        let segments = if key.starts_with('@') { 3 } else { 2 };
+        let mut parts = key.splitn(segments, '/');
+        let name = if segments == 3 {
+            format!("{}/{}", parts.next()?, parts.next()?)
+        } else {
+            parts.next()?.to_string()
+        };
+        (name, parts.next()?)
+    } else {
+        let at = key.get(1..)?.find('@')? + 1;
+        (key[..at].to_string(), &key[at + 1..])
+    };
+
+    let version = strip_peer_suffix(version);
+    if name.is_empty() || version.is_empty() {
+        return None;
+    }
+    Some((name, version.to_string()))
+}
+
+// Function: strip_peer_suffix
+// Purpose: Remove peer-dependency suffixes from a resolved version
+// Args: version - "18.2.0(react@18.2.0)" (v6+) or "18.2.0_react@18.2.0" (v5)
+// Returns: "18.2.0"
+pub fn strip_peer_suffix(version: &str) -> &str {
+    version.split(['(', '_']).next().unwrap_or(version)
+}
+
+// Helper: Fields of a "resolution:" mapping
+fn parse_resolution(resolution: &Mapping) -> PnpmResolution {
+    let field = |name: &str| resolution.get(name).and_then(scalar_string);
+    PnpmResolution {
+        integrity: field("integrity"),
+        tarball: field("tarball"),
+        repo: field("repo"),
+        directory: field("directory"),
+    }
+}
+
+// Helper: Direct dependencies of one importer mapping
+fn parse_importer(importer: &Mapping, major: u32) -> Vec<PnpmDependency> {
+    // v5 keeps specifiers in a separate map
+    let specifiers = importer.get("specifiers").and_then(Value::as_mapping);
+
+    let mut dependencies = Vec::new();
+    for section in DEPENDENCY_SECTIONS {
+        let Some(deps) = importer.get(section).and_then(Value::as_mapping) else {
+            continue;
+        };
+        for (name, value) in deps {
+            let Some(name) = scalar_string(name) else {
+                continue;
+            };
+            // v6+: {specifier, version}; v5: bare version string
+            let (specifier, reference) = match value.as_mapping() {
+                Some(entry) => (
+                    entry.get("specifier").and_then(scalar_string),
+                    entry.get("version").and_then(scalar_string),
+                ),
+                None => (
+                    specifiers
+                        .and_then(|s| s.get(name.as_str()))
+                        .and_then(scalar_string),
+                    scalar_string(value),
+                ),
+            };
+            let Some(reference) = reference else {
+                continue;
+            };
+            let (alias_of, version) = resolve_reference(&reference, major);
+            dependencies.push(PnpmDependency {
+                name,
+                alias_of,
+                specifier,
+                version,
+                section,
+            });
+        }
+    }
+    dependencies
+}
+
+// Helper: Resolve an importer version reference
+// ("18.2.0(react@18.2.0)" -> "18.2.0"; alias "/string-width@4.2.3" or "string-width@4.2.3"
+// -> real name + version; "link:../lib" and URLs are kept as-is)
+fn resolve_reference(reference: &str, major: u32) -> (Option<String>, String) {
+    let is_alias = reference.starts_with('/')
+        || (major >= 9
+            && !reference.contains(':')
+            && strip_peer_suffix(reference)
+                .get(1..)
+                .is_some_and(|r| r.contains('@')));
+    if is_alias {
+        if let Some((name, version)) = parse_package_key(reference, major) {
+            return (Some(name), version);
+        }
+    }
+    if reference.starts_with(|c: char| c.is_ascii_digit()) {
+        return (None, strip_peer_suffix(reference).to_string());
+    }
+    (None, reference.to_string())
+}
+
+// Helper: Leading integer of a lockfile version ("5.4" -> 5, "9.0" -> 9)
+fn major_version(lockfile_version: &str) -> u32 {
+    lockfile_version
+        .split('.')
+        .next()
+        .and_then(|major| major.parse().ok())
+        .unwrap_or(0)
+}
+
+// Helper: YAML scalar as string (lockfileVersion 5.4 is a number, '6.0' a string)
+pub(crate) fn scalar_string(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn versions(lockfile: &PnpmLockfile) -> Vec<(&str, &str)> {
+        let mut versions: Vec<_> = lockfile
+            .packages
+            .iter()
+            .map(|p| (p.name.as_str(), p.version.as_str()))
+            .collect();
+        versions.sort_unstable();
+        versions
+    }
+
+    #[test]
+    fn test_parse_v5_lockfile() {
+        let lockfile = PnpmLockfile::parse(
+            r"
+lockfileVersion: 5.4
+
+specifiers:
+  '@ctrl/tinycolor': ^4.1.0
+  react-dom: ^18.2.0
+  strip: npm:strip-ansi@^6.0.0
+
+dependencies:
+  '@ctrl/tinycolor': 4.1.1
+  react-dom: 18.2.0_react@18.2.0
+  strip: /strip-ansi/6.0.1
+
+devDependencies:
+  react: 18.2.0
+
+packages:
+
+  /@ctrl/tinycolor/4.1.1:
+    resolution: {integrity: sha512-aaa}
+
+  /react-dom/18.2.0_react@18.2.0:
+    resolution: {integrity: sha512-bbb}
+
+  /react/18.2.0:
+    resolution: {integrity: sha512-ccc}
+
+  /strip-ansi/6.0.1:
+    resolution: {integrity: sha512-ddd}
+",
+        )
+        .unwrap();
+
+        assert_eq!(lockfile.major_version(), 5);
+        assert_eq!(
+            versions(&lockfile),
+            vec![
+                ("@ctrl/tinycolor", "4.1.1"),
+                ("react", "18.2.0"),
+                ("react-dom", "18.2.0"),
+                ("strip-ansi", "6.0.1"),
+            ]
+        );
+
+        let root = &lockfile.importers["."];
+        let react_dom = root.iter().find(|d| d.name == "react-dom").unwrap();
+        assert_eq!(react_dom.version, "18.2.0");
+        assert_eq!(react_dom.specifier.as_deref(), Some("^18.2.0"));
+        let strip = root.iter().find(|d| d.name == "strip").unwrap();
+        assert_eq!(strip.package_name(), "strip-ansi");
+        assert_eq!(strip.version, "6.0.1");
+        assert_eq!(
+            root.iter().find(|d| d.name == "react").unwrap().section,
+            "devDependencies"
+        );
+    }
+
+    #[test]
+    fn test_parse_v5_workspace_importers() {
+        let lockfile = PnpmLockfile::parse(
+            r"
+lockfileVersion: 5.3
+
+importers:
+
+  .:
+    specifiers:
+      debug: ^4.3.4
+    devDependencies:
+      debug: 4.3.4
+
+  packages/app:
+    specifiers:
+      '@scope/lib': workspace:*
+      debug: ^2.6.0
+    dependencies:
+      '@scope/lib': link:../lib
+      debug: 2.6.9
+
+packages:
+
+  /debug/2.6.9:
+    resolution: {integrity: sha512-aaa}
+
+  /debug/4.3.4:
+    resolution: {integrity: sha512-bbb}
+",
+        )
+        .unwrap();
+
+        assert_eq!(lockfile.importers.len(), 2);
+        let app = &lockfile.importers["packages/app"];
+        let lib = app.iter().find(|d| d.name == "@scope/lib").unwrap();
+        assert_eq!(lib.version, "link:../lib");
+        assert!(!lib.is_registry_version());
+        let debug = app.iter().find(|d| d.name == "debug").unwrap();
+        assert_eq!(debug.version, "2.6.9");
+        assert_eq!(debug.specifier.as_deref(), Some("^2.6.0"));
+        assert_eq!(lockfile.importers["."][0].version, "4.3.4");
+    }
+
+    #[test]
+    fn test_parse_v6_lockfile() {
+        let lockfile = PnpmLockfile::parse(
+            r"
+lockfileVersion: '6.0'
+
+settings:
+  autoInstallPeers: true
+
+importers:
+
+  .:
+    dependencies:
+      react-dom:
+        specifier: ^18.2.0
+        version: 18.2.0(react@18.2.0)
+      strip:
+        specifier: npm:strip-ansi@^6.0.0
+        version: /strip-ansi@6.0.1
+
+  packages/web:
+    dependencies:
+      '@ctrl/tinycolor':
+        specifier: 4.1.1
+        version: 4.1.1
+
+packages:
+
+  /@ctrl/tinycolor@4.1.1:
+    resolution: {integrity: sha512-aaa}
+
+  /react-dom@18.2.0(react@18.2.0):
+    resolution: {integrity: sha512-bbb}
+    peerDependencies:
+      react: ^18.2.0
+
+  /react@18.2.0:
+    resolution: {integrity: sha512-ccc}
+
+  /strip-ansi@6.0.1:
+    resolution: {integrity: sha512-ddd}
+",
+        )
+        .unwrap();
+
+        assert_eq!(lockfile.major_version(), 6);
+        assert_eq!(
+            versions(&lockfile),
+            vec![
+                ("@ctrl/tinycolor", "4.1.1"),
+                ("react", "18.2.0"),
+                ("react-dom", "18.2.0"),
+                ("strip-ansi", "6.0.1"),
+            ]
+        );
+        let root = &lockfile.importers["."];
+        assert_eq!(root[0].version, "18.2.0");
+        assert_eq!(root[1].package_name(), "strip-ansi");
+        assert_eq!(
+            lockfile.importers["packages/web"][0].package_name(),
+            "@ctrl/tinycolor"
+        );
+    }
+
+    #[test]
+    fn test_parse_v9_lockfile_with_snapshots() {
+        let lockfile = PnpmLockfile::parse(
+            r"
+---
+lockfileVersion: '9.0'
+
+importers:
+  .:
+    configDependencies: {}
+
+---
+lockfileVersion: '9.0'
+
+settings:
+  autoInstallPeers: true
+  excludeLinksFromLockfile: false
+
+importers:
+
+  .:
+    dependencies:
+      react-dom:
+        specifier: ^18.2.0
+        version: 18.2.0(react@18.2.0)
+      strip:
+        specifier: npm:strip-ansi@^6.0.0
+        version: strip-ansi@6.0.1
+
+  packages/lib:
+    devDependencies:
+      '@ctrl/tinycolor':
+        specifier: ~4.1.0
+        version: 4.1.1
+
+packages:
+
+  '@ctrl/tinycolor@4.1.1':
+    resolution: {integrity: sha512-aaa}
+
+  react-dom@18.2.0:
+    resolution: {integrity: sha512-bbb}
+    peerDependencies:
+      react: ^18.2.0
+
+  react@18.2.0:
+    resolution: {integrity: sha512-ccc}
+
+  strip-ansi@6.0.1:
+    resolution: {integrity: sha512-ddd}
+
+  left-pad@git+https://github.com/x/left-pad.git#abc123:
+    name: left-pad
+    version: 1.3.0
+    resolution: {type: git, repo: https://github.com/x/left-pad.git, commit: abc123}
+
+snapshots:
+
+  '@ctrl/tinycolor@4.1.1': {}
+
+  react-dom@18.2.0(react@18.2.0):
+    dependencies:
+      react: 18.2.0
+
+  react@18.2.0: {}
+
+  strip-ansi@6.0.1: {}
+",
+        )
+        .unwrap();
+
+        assert_eq!(lockfile.major_version(), 9);
+        assert_eq!(
+            versions(&lockfile),
+            vec![
+                ("@ctrl/tinycolor", "4.1.1"),
+                ("left-pad", "1.3.0"),
+                ("react", "18.2.0"),
+                ("react-dom", "18.2.0"),
+                ("strip-ansi", "6.0.1"),
+            ]
+        );
+        let root = &lockfile.importers["."];
+        assert_eq!(root[0].version, "18.2.0");
+        assert_eq!(root[1].alias_of.as_deref(), Some("strip-ansi"));
+        let lib = &lockfile.importers["packages/lib"][0];
+        assert_eq!(lib.section, "devDependencies");
+        assert_eq!(lib.specifier.as_deref(), Some("~4.1.0"));
+    }
+
+    #[test]
+    fn test_parse_package_key() {
+        let key = |k, major| parse_package_key(k, major).map(|(n, v)| format!("{n}@{v}"));
+        assert_eq!(key("/debug/4.3.4", 5).as_deref(), Some("debug@4.3.4"));
+        assert_eq!(
+            key("/@types/react-dom/18.0.0_@types+react@18.0.0", 5).as_deref(),
+            Some("@types/react-dom@18.0.0")
+        );
+        assert_eq!(
+            key("/@scope/pkg@1.0.0(react@18.2.0)(react-dom@18.2.0)", 6).as_deref(),
+            Some("@scope/pkg@1.0.0")
+        );
+        assert_eq!(
+            key("react-dom@18.2.0(react@18.2.0)", 9).as_deref(),
+            Some("react-dom@18.2.0")
+        );
+        assert_eq!(key("/debug", 5), None);
+        assert_eq!(key("debug", 9), None);
+    }
+}
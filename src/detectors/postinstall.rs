@@ -1,18 +1,17 @@
 // Postinstall Hooks Detector
 // Rust port of: check_postinstall_hooks()
 
-use crate::detectors::{verification, Finding, RiskLevel};
+use crate::data::VerifiedFile;
+use crate::detectors::{verification, Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use serde_json::Value;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: check_postinstall_hooks
 // Purpose: Detect suspicious postinstall scripts that may execute malicious code
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan), verified_files - reviewed safe files
 // Modifies: POSTINSTALL_HOOKS (global array)
 // Returns: Populates POSTINSTALL_HOOKS array with package.json files containing hooks
-pub fn check_postinstall_hooks<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_postinstall_hooks(index: &FileIndex, verified_files: &[VerifiedFile]) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for suspicious postinstall hooks...",
@@ -20,13 +19,9 @@ pub fn check_postinstall_hooks<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     let mut findings = Vec::new();
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file() && e.file_name() == "package.json")
-    {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
+    for entry in index.files_named("package.json") {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if let Ok(json) = serde_json::from_str::<Value>(content) {
                 // Look for postinstall scripts
                 if let Some(scripts) = json.get("scripts").and_then(|v| v.as_object()) {
                     if let Some(postinstall) = scripts.get("postinstall").and_then(|v| v.as_str()) {
@@ -42,7 +37,8 @@ pub fn check_postinstall_hooks<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
                             );
 
                             // Try to verify via file hash (AI-reviewed files)
-                            let hash_verification = verification::verify_file_by_hash(entry.path());
+                            let hash_verification =
+                                verification::verify_file_by_hash(entry.path(), verified_files);
                             if let verification::VerificationStatus::Verified { .. } =
                                 hash_verification
                             {
@@ -59,3 +55,24 @@ pub fn check_postinstall_hooks<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_postinstall_hooks()`
+pub struct PostinstallHooksDetector;
+
+impl Detector for PostinstallHooksDetector {
+    fn id(&self) -> &'static str {
+        "postinstall_hooks"
+    }
+
+    fn description(&self) -> &'static str {
+        "Suspicious postinstall hooks in package.json"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_postinstall_hooks(ctx.index, &ctx.iocs.verified_files)
+    }
+}
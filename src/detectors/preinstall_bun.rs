@@ -4,17 +4,15 @@
 // Corresponds to bash function:
 // - check_preinstall_bun_patterns() - Lines 549-563 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::file_index::FileIndex;
 
 // Function: check_preinstall_bun_patterns
 // Purpose: Detect fake Bun runtime preinstall patterns in package.json files
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with files containing suspicious preinstall patterns
-pub fn check_preinstall_bun_patterns(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_preinstall_bun_patterns(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for fake Bun preinstall patterns...",
@@ -23,23 +21,15 @@ pub fn check_preinstall_bun_patterns(scan_dir: &Path) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     // Look for package.json files with suspicious "preinstall": "node setup_bun.js" pattern
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.files() {
         let path = entry.path();
 
-        if !path.is_file() {
-            continue;
-        }
-
         if path.file_name().and_then(|n| n.to_str()) != Some("package.json") {
             continue;
         }
 
         // Read and check file content
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             // Check for the malicious preinstall pattern
             // Pattern: "preinstall": "node setup_bun.js" (with flexible whitespace)
             if content.contains(r#""preinstall""#) && content.contains("setup_bun.js") {
@@ -61,3 +51,24 @@ pub fn check_preinstall_bun_patterns(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_preinstall_bun_patterns()`
+pub struct PreinstallBunDetector;
+
+impl Detector for PreinstallBunDetector {
+    fn id(&self) -> &'static str {
+        "preinstall_bun_patterns"
+    }
+
+    fn description(&self) -> &'static str {
+        "Malicious preinstall scripts (fake Bun installation)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_preinstall_bun_patterns(ctx.index)
+    }
+}
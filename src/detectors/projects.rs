@@ -0,0 +1,296 @@
+// Project Resolution - Nearest lockfile / workspace root for every scanned file
+// Purpose: When the scan directory holds many repositories or a monorepo with nested
+//          packages, verify each package.json against the lockfile of its own project
+//          instead of the one (if any) at the scan root.
+//
+// A project root is the nearest ancestor directory holding a lockfile; without one,
+// the nearest workspace root (pnpm-workspace.yaml or package.json "workspaces").
+// Directories inside node_modules are never project roots - installed packages
+// belong to the project that installed them.
+
+use crate::detectors::lockfile_resolver::LockfileResolver;
+use crate::detectors::runtime_resolver::RuntimeResolver;
+use crate::file_index::FileIndex;
+use std::collections::{BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+
+/// Lockfile names in the order `LockfileResolver::load_from_dir` tries them
+pub const LOCKFILE_NAMES: [&str; 5] = [
+    "package-lock.json",
+    "pnpm-lock.yaml",
+    "yarn.lock",
+    "bun.lock",
+    "bun.lockb",
+];
+
+/// Project roots found below the scan directory
+#[derive(Debug, Clone, Default)]
+pub struct ProjectIndex {
+    lockfile_dirs: BTreeSet<PathBuf>,
+    workspace_roots: BTreeSet<PathBuf>,
+}
+
+impl ProjectIndex {
+    // Function: build
+    // Purpose: Collect lockfile directories and workspace roots from the file index
+    // Args: index - file index of the scan directory
+    // Returns: ProjectIndex (empty if the scan has no lockfile or workspace)
+    pub fn build(index: &FileIndex) -> Self {
+        let mut projects = Self::default();
+        for entry in index.files() {
+            if entry.archive().is_some() || in_node_modules(entry.path()) {
+                continue;
+            }
+            let Some(dir) = entry.path().parent() else {
+                continue;
+            };
+            let name = entry.file_name();
+            if LOCKFILE_NAMES.contains(&name) {
+                projects.lockfile_dirs.insert(dir.to_path_buf());
+            } else if name == "pnpm-workspace.yaml"
+                || (name == "package.json"
+                    && index
+                        .read_text(entry)
+                        .as_deref()
+                        .is_some_and(declares_workspaces))
+            {
+                projects.workspace_roots.insert(dir.to_path_buf());
+            }
+        }
+        projects
+    }
+
+    // Function: project_root
+    // Purpose: Find the project a file belongs to
+    // Args: path - any scanned file or directory
+    // Returns: Nearest ancestor with a lockfile, else nearest workspace root, else None
+    pub fn project_root(&self, path: &Path) -> Option<PathBuf> {
+        let candidates = || path.ancestors().filter(|dir| !in_node_modules(dir));
+        candidates()
+            .find(|dir| self.lockfile_dirs.contains(*dir))
+            .or_else(|| candidates().find(|dir| self.workspace_roots.contains(*dir)))
+            .map(Path::to_path_buf)
+    }
+
+    /// Every project root (lockfile directories and workspace roots), sorted
+    pub fn roots(&self) -> BTreeSet<&Path> {
+        self.lockfile_dirs
+            .iter()
+            .chain(&self.workspace_roots)
+            .map(PathBuf::as_path)
+            .collect()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lockfile_dirs.is_empty() && self.workspace_roots.is_empty()
+    }
+}
+
+/// Lockfile and runtime resolvers per project, loaded on first use (--verify)
+pub struct ProjectResolvers {
+    projects: ProjectIndex,
+    /// Used for files outside every project (previous single-resolver behavior)
+    scan_root: PathBuf,
+    lockfiles: HashMap<PathBuf, Option<LockfileResolver>>,
+    runtimes: HashMap<PathBuf, Option<RuntimeResolver>>,
+}
+
+impl ProjectResolvers {
+    pub fn new(projects: ProjectIndex, scan_root: &Path) -> Self {
+        Self {
+            projects,
+            scan_root: scan_root.to_path_buf(),
+            lockfiles: HashMap::new(),
+            runtimes: HashMap::new(),
+        }
+    }
+
+    /// Project root used to resolve a file (the scan root for files outside every project)
+    pub fn resolution_root(&self, path: &Path) -> PathBuf {
+        self.projects
+            .project_root(path)
+            .unwrap_or_else(|| self.scan_root.clone())
+    }
+
+    /// Lockfile resolver of the project containing `path`
+    pub fn lockfile_for(&mut self, path: &Path) -> Option<&LockfileResolver> {
+        let root = self.resolution_root(path);
+        self.lockfiles
+            .entry(root)
+            .or_insert_with_key(|root| load_lockfile(root))
+            .as_ref()
+    }
+
+    // Function: resolvers_for
+    // Purpose: Lockfile and runtime resolver of the project containing `path`
+    // Args: path - package.json (or any file) being verified
+    // Returns: (lockfile resolver, runtime resolver), each None if unavailable
+    pub fn resolvers_for(
+        &mut self,
+        path: &Path,
+    ) -> (Option<&LockfileResolver>, Option<&mut RuntimeResolver>) {
+        let root = self.resolution_root(path);
+        let lockfile = self
+            .lockfiles
+            .entry(root.clone())
+            .or_insert_with_key(|root| load_lockfile(root))
+            .as_ref();
+        let runtime = self
+            .runtimes
+            .entry(root)
+            .or_insert_with_key(|root| load_runtime(root))
+            .as_mut();
+        (lockfile, runtime)
+    }
+}
+
+// Helper: Load a project's lockfile resolver, reporting the result
+// NOTE: Status messages are Rust-only feature, tests ignore them via strip_verification_data
+fn load_lockfile(root: &Path) -> Option<LockfileResolver> {
+    match LockfileResolver::load_from_dir(root) {
+        Ok(resolver) if resolver.has_lockfile() => {
+            crate::colors::print_status(
+                crate::colors::Color::Green,
+                &format!(
+                    "✅ Lockfile loaded for {} ({:?} format, {} packages, {} installed copies)",
+                    crate::utils::normalize_path(root),
+                    resolver.lockfile_type.as_ref().unwrap(),
+                    resolver.packages.len(),
+                    resolver.packages.instance_count()
+                ),
+            );
+            Some(resolver)
+        }
+        _ => None,
+    }
+}
+
+// Helper: Query the package manager of one project for installed versions
+fn load_runtime(root: &Path) -> Option<RuntimeResolver> {
+    crate::colors::print_status(
+        crate::colors::Color::Blue,
+        &format!(
+            "🔍 Querying package manager for installed versions in {}...",
+            crate::utils::normalize_path(root)
+        ),
+    );
+    match RuntimeResolver::from_runtime(root) {
+        Ok(resolver) if resolver.has_packages() => {
+            crate::colors::print_status(
+                crate::colors::Color::Green,
+                &format!(
+                    "✅ Runtime resolver: {} packages found ({} installed copies)",
+                    resolver.packages.len(),
+                    resolver.packages.instance_count()
+                ),
+            );
+            Some(resolver)
+        }
+        Ok(_) => {
+            crate::colors::print_status(
+                crate::colors::Color::Yellow,
+                "⚠️  Runtime resolution failed - using lockfile only",
+            );
+            None
+        }
+        Err(e) => {
+            crate::colors::print_status(
+                crate::colors::Color::Yellow,
+                &format!("⚠️  Runtime resolution error: {e} - using lockfile only"),
+            );
+            None
+        }
+    }
+}
+
+// Helper: Path has a node_modules component
+pub(crate) fn in_node_modules(path: &Path) -> bool {
+    path.components().any(|c| c.as_os_str() == "node_modules")
+}
+
+// Helper: package.json declares workspaces (npm, yarn, bun)
+fn declares_workspaces(content: &str) -> bool {
+    serde_json::from_str::<serde_json::Value>(content)
+        .is_ok_and(|json| json.get("workspaces").is_some())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_nearest_lockfile_or_workspace_root() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for dir in [
+            "repo-a/node_modules/debug",
+            "repo-b/packages/app",
+            "mono/packages/web",
+            "loose",
+        ] {
+            fs::create_dir_all(root.join(dir)).unwrap();
+        }
+        fs::write(root.join("repo-a/package-lock.json"), "{}").unwrap();
+        // A lockfile shipped inside node_modules does not start a project
+        fs::write(root.join("repo-a/node_modules/debug/yarn.lock"), "").unwrap();
+        fs::write(root.join("repo-b/pnpm-lock.yaml"), "").unwrap();
+        fs::write(
+            root.join("mono/package.json"),
+            r#"{"workspaces": ["packages/*"]}"#,
+        )
+        .unwrap();
+        fs::write(root.join("loose/package.json"), "{}").unwrap();
+
+        let projects = ProjectIndex::build(&FileIndex::build(root, 1));
+        let project = |path: &str| projects.project_root(&root.join(path));
+
+        assert_eq!(
+            project("repo-a/node_modules/debug/package.json"),
+            Some(root.join("repo-a"))
+        );
+        assert_eq!(
+            project("repo-b/packages/app/package.json"),
+            Some(root.join("repo-b"))
+        );
+        assert_eq!(
+            project("mono/packages/web/package.json"),
+            Some(root.join("mono"))
+        );
+        assert_eq!(project("loose/package.json"), None);
+        assert_eq!(projects.roots().len(), 3);
+    }
+
+    #[test]
+    fn test_resolvers_cached_per_project() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        for (repo, version) in [("repo-a", "4.3.4"), ("repo-b", "2.6.9")] {
+            fs::create_dir_all(root.join(repo)).unwrap();
+            fs::write(
+                root.join(repo).join("package-lock.json"),
+                format!(
+                    r#"{{"lockfileVersion": 3, "packages": {{"node_modules/debug": {{"version": "{version}"}}}}}}"#
+                ),
+            )
+            .unwrap();
+        }
+
+        let projects = ProjectIndex::build(&FileIndex::build(root, 1));
+        let mut resolvers = ProjectResolvers::new(projects, root);
+        let version = |resolvers: &mut ProjectResolvers, repo: &str| {
+            resolvers
+                .lockfile_for(&root.join(repo).join("package.json"))
+                .and_then(|r| r.get_version("debug"))
+                .map(str::to_string)
+        };
+
+        assert_eq!(version(&mut resolvers, "repo-a").as_deref(), Some("4.3.4"));
+        assert_eq!(version(&mut resolvers, "repo-b").as_deref(), Some("2.6.9"));
+        assert_eq!(resolvers.lockfiles.len(), 2);
+        // Second lookup hits the cache
+        assert_eq!(version(&mut resolvers, "repo-a").as_deref(), Some("4.3.4"));
+        assert_eq!(resolvers.lockfiles.len(), 2);
+    }
+}
@@ -0,0 +1,219 @@
+// Detector Registry - Pluggable detector trait and ordered detector list
+// Purpose: Replace the hardcoded check_* call sequence in main with a registry
+//
+// Each detector module exposes a unit struct implementing `Detector`. The registry
+// keeps them in bash function execution order so output stays bash-compatible.
+
+use crate::data::{CompromisedPackage, IocData};
+use crate::detectors::projects::ProjectResolvers;
+use crate::detectors::{
+    bun_attack, caches, content, crypto, destructive_patterns, discussion_workflows, git,
+    github_runners, hashes, integrity, inventory, lockfile_sources, network, packages, postinstall,
+    preinstall_bun, repos, second_coming, sha1hulud_runner, trufflehog, typosquatting, workflow,
+    workflows_new, Finding, RiskLevel,
+};
+use crate::file_index::FileIndex;
+use crate::osv::Advisories;
+use anyhow::{bail, Result};
+use std::collections::HashSet;
+
+/// Shared state handed to every detector
+pub struct ScanContext<'a> {
+    pub index: &'a FileIndex,
+    pub compromised_packages: &'a HashSet<CompromisedPackage>,
+    pub malicious_hashes: &'a HashSet<String>,
+    /// OSV advisories (version ranges, advisory IDs for finding messages)
+    pub advisories: &'a Advisories,
+    /// Namespaces, Bun payload hashes and verified files (data/iocs.toml + --extra-iocs)
+    pub iocs: &'a IocData,
+    pub parallelism: usize,
+    pub check_semver_ranges: bool,
+    /// Report compromised versions installed in node_modules (--inventory)
+    pub inventory: bool,
+    /// Scan npm/pnpm/yarn caches of the current user (--scan-caches)
+    pub scan_caches: bool,
+    /// Per-project lockfile/runtime resolvers (only with --verify)
+    pub resolvers: Option<&'a mut ProjectResolvers>,
+}
+
+/// A single detection check
+///
+/// `id()` is the name used by `--only` / `--skip`. Findings are stored in the
+/// `ScanResults` bucket returned by `results_key()`; unknown keys end up in
+/// `ScanResults::additional_findings`, so new detectors need no changes elsewhere.
+pub trait Detector {
+    fn id(&self) -> &'static str;
+    fn description(&self) -> &'static str;
+    /// Level of the SARIF rule for findings without a built-in rule (see `default_severity()`)
+    fn default_severity(&self) -> RiskLevel;
+
+    /// Only run when --paranoid is set
+    fn paranoid_only(&self) -> bool {
+        false
+    }
+
+    /// `ScanResults` bucket for a finding produced by this detector
+    fn results_key(&self, _finding: &Finding) -> &'static str {
+        self.id()
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding>;
+}
+
+// Function: all_detectors
+// Purpose: List every registered detector in bash execution order
+// Args: None
+// Returns: Vec of boxed detectors
+pub fn all_detectors() -> Vec<Box<dyn Detector>> {
+    vec![
+        // Core Shai-Hulud detection checks (matching bash function execution order)
+        Box::new(workflow::WorkflowFilesDetector),
+        Box::new(hashes::FileHashesDetector),
+        Box::new(packages::PackagesDetector),
+        Box::new(packages::SemverRangesDetector),
+        Box::new(inventory::InstalledPackagesDetector),
+        Box::new(caches::PackageCachesDetector),
+        Box::new(postinstall::PostinstallHooksDetector),
+        Box::new(content::ContentDetector),
+        Box::new(crypto::CryptoPatternsDetector),
+        Box::new(trufflehog::TrufflehogDetector),
+        Box::new(git::GitBranchesDetector),
+        Box::new(repos::ShaiHuludReposDetector),
+        Box::new(integrity::PackageIntegrityDetector),
+        Box::new(lockfile_sources::LockfileSourcesDetector),
+        // November 2025 "Shai-Hulud: The Second Coming" Attack detectors
+        Box::new(bun_attack::BunAttackDetector),
+        Box::new(workflows_new::NewWorkflowPatternsDetector),
+        Box::new(discussion_workflows::DiscussionWorkflowsDetector),
+        Box::new(github_runners::GithubRunnersDetector),
+        Box::new(destructive_patterns::DestructivePatternsDetector),
+        Box::new(preinstall_bun::PreinstallBunDetector),
+        Box::new(sha1hulud_runner::Sha1huludRunnerDetector),
+        Box::new(second_coming::SecondComingReposDetector),
+        // Additional security checks (paranoid mode only)
+        Box::new(typosquatting::TyposquattingDetector),
+        Box::new(network::NetworkExfiltrationDetector),
+    ]
+}
+
+// Function: select_detectors
+// Purpose: Apply --only / --skip filters to the registry
+// Args: only - detector ids to keep (empty = all), skip - detector ids to drop
+// Returns: Filtered detectors in registry order, or error for unknown ids
+pub fn select_detectors(only: &[String], skip: &[String]) -> Result<Vec<Box<dyn Detector>>> {
+    let detectors = all_detectors();
+    let known: Vec<&str> = detectors.iter().map(|d| d.id()).collect();
+
+    for id in only.iter().chain(skip) {
+        if !known.contains(&id.as_str()) {
+            bail!(
+                "Error: Unknown detector '{id}'. Available detectors: {}",
+                known.join(", ")
+            );
+        }
+    }
+
+    Ok(detectors
+        .into_iter()
+        .filter(|d| only.is_empty() || only.iter().any(|id| id == d.id()))
+        .filter(|d| !skip.iter().any(|id| id == d.id()))
+        .collect())
+}
+
+/// Results key a detector files some findings under besides its own id
+struct ExtraResultsKey {
+    key: &'static str,
+    /// Id of the detector producing the findings
+    owner: &'static str,
+    description: &'static str,
+    severity: RiskLevel,
+}
+
+const EXTRA_RESULTS_KEYS: [ExtraResultsKey; 2] = [
+    ExtraResultsKey {
+        key: "dependency_sources",
+        owner: "packages",
+        description: "package.json dependencies from git, tarball URLs or local paths",
+        severity: RiskLevel::Low,
+    },
+    ExtraResultsKey {
+        key: "unverified_lockfiles",
+        owner: "integrity_issues",
+        description:
+            "Binary bun.lockb lockfiles not checked (decoding them needs --decode-bun-lockb)",
+        severity: RiskLevel::Low,
+    },
+];
+
+// Helper: Entry for a results key filed besides its owner's id
+fn extra_results_key(key: &str) -> Option<&'static ExtraResultsKey> {
+    EXTRA_RESULTS_KEYS.iter().find(|extra| extra.key == key)
+}
+
+// Helper: Detector filing findings under a detector id / results key
+fn owner(key: &str) -> Option<Box<dyn Detector>> {
+    let id = extra_results_key(key).map_or(key, |extra| extra.owner);
+    all_detectors().into_iter().find(|d| d.id() == id)
+}
+
+// Function: describe
+// Purpose: Look up the human-readable description for a detector id / results key
+// Args: id - detector id
+// Returns: Description if the id is registered
+pub fn describe(id: &str) -> Option<&'static str> {
+    extra_results_key(id)
+        .map(|extra| extra.description)
+        .or_else(|| owner(id).map(|d| d.description()))
+}
+
+// Function: default_severity
+// Purpose: Look up the default severity of a results key (its own for extra keys,
+//          otherwise the owning detector's)
+// Args: key - detector id / results key
+// Returns: Severity if a registered detector owns the key
+pub fn default_severity(key: &str) -> Option<RiskLevel> {
+    match extra_results_key(key) {
+        Some(extra) => Some(extra.severity.clone()),
+        None => owner(key).map(|d| d.default_severity()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detector_ids_unique() {
+        let detectors = all_detectors();
+        let ids: HashSet<&str> = detectors.iter().map(|d| d.id()).collect();
+        assert_eq!(ids.len(), detectors.len());
+    }
+
+    #[test]
+    fn test_select_only_and_skip() {
+        let only = vec!["workflow_files".to_string(), "malicious_hashes".to_string()];
+        let selected = select_detectors(&only, &[]).unwrap();
+        assert_eq!(selected.len(), 2);
+
+        let skip = vec!["workflow_files".to_string()];
+        let selected = select_detectors(&[], &skip).unwrap();
+        assert_eq!(selected.len(), all_detectors().len() - 1);
+        assert!(selected.iter().all(|d| d.id() != "workflow_files"));
+    }
+
+    #[test]
+    fn test_describe_results_keys() {
+        assert_eq!(default_severity("malicious_hashes"), Some(RiskLevel::High));
+        assert_eq!(default_severity("dependency_sources"), Some(RiskLevel::Low));
+        assert!(describe("dependency_sources")
+            .unwrap()
+            .starts_with("package.json dependencies"));
+        assert_eq!(default_severity("future_detector"), None);
+    }
+
+    #[test]
+    fn test_select_unknown_detector() {
+        let only = vec!["does_not_exist".to_string()];
+        assert!(select_detectors(&only, &[]).is_err());
+    }
+}
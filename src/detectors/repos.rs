@@ -1,17 +1,16 @@
 // Shai-Hulud Repositories Detector
 // Rust port of: check_shai_hulud_repos()
 
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: check_shai_hulud_repos
 // Purpose: Detect Shai-Hulud worm repositories and malicious migration patterns
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: SHAI_HULUD_REPOS (global array)
 // Returns: Populates array with repository patterns and migration indicators
-pub fn check_shai_hulud_repos<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_shai_hulud_repos(index: &FileIndex) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for Shai-Hulud repositories and migration patterns...",
@@ -19,11 +18,7 @@ pub fn check_shai_hulud_repos<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     let mut findings = Vec::new();
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_dir() && e.file_name() == ".git")
-    {
+    for entry in index.dirs_named(".git") {
         let repo_dir = entry.path().parent().unwrap_or(entry.path());
         let repo_name = repo_dir.file_name().and_then(|n| n.to_str()).unwrap_or("");
 
@@ -77,3 +72,24 @@ pub fn check_shai_hulud_repos<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_shai_hulud_repos()`
+pub struct ShaiHuludReposDetector;
+
+impl Detector for ShaiHuludReposDetector {
+    fn id(&self) -> &'static str {
+        "shai_hulud_repos"
+    }
+
+    fn description(&self) -> &'static str {
+        "Shai-Hulud repositories and migration patterns"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_shai_hulud_repos(ctx.index)
+    }
+}
@@ -4,6 +4,9 @@
 
 #![allow(dead_code)]
 
+use crate::detectors::lockfile_resolver::{
+    chain_path, install_chain, PackageInstance, PackageInstances,
+};
 use anyhow::{Context, Result};
 use serde::Deserialize;
 use std::collections::HashMap;
@@ -47,8 +50,8 @@ struct NpmPackage {
 
 /// Runtime resolver - queries package manager for actual installed versions
 pub struct RuntimeResolver {
-    pub packages: HashMap<String, String>, // package_name -> version
-    base_dir: std::path::PathBuf,          // Store base directory for fallback queries
+    pub packages: PackageInstances, // every installed copy of every package
+    base_dir: std::path::PathBuf,   // Store base directory for fallback queries
 }
 
 impl RuntimeResolver {
@@ -70,12 +73,12 @@ impl RuntimeResolver {
 
         // No runtime resolution available
         Ok(Self {
-            packages: HashMap::new(),
+            packages: PackageInstances::default(),
             base_dir,
         })
     }
     /// Query pnpm for installed packages
-    fn query_pnpm<P: AsRef<Path>>(dir: P) -> Result<HashMap<String, String>> {
+    fn query_pnpm<P: AsRef<Path>>(dir: P) -> Result<PackageInstances> {
         let output = Command::new("pnpm")
             .arg("list")
             .arg("--json")
@@ -94,11 +97,11 @@ impl RuntimeResolver {
         let results: Vec<PnpmListOutput> =
             serde_json::from_str(&stdout).context("Failed to parse pnpm list output")?;
 
-        let mut all_packages = HashMap::new();
+        let mut all_packages = PackageInstances::default();
 
         for result in results {
-            Self::flatten_pnpm_deps(&result.dependencies, &mut all_packages);
-            Self::flatten_pnpm_deps(&result.dev_dependencies, &mut all_packages);
+            Self::flatten_pnpm_deps(&result.dependencies, &[], &mut all_packages);
+            Self::flatten_pnpm_deps(&result.dev_dependencies, &[], &mut all_packages);
         }
 
         // If no packages found, consider it a failure
@@ -116,7 +119,7 @@ impl RuntimeResolver {
     }
 
     /// Query npm for installed packages
-    fn query_npm<P: AsRef<Path>>(dir: P) -> Result<HashMap<String, String>> {
+    fn query_npm<P: AsRef<Path>>(dir: P) -> Result<PackageInstances> {
         let output = Command::new("npm")
             .arg("list")
             .arg("--json")
@@ -134,8 +137,8 @@ impl RuntimeResolver {
         let result: NpmListOutput =
             serde_json::from_str(&stdout).context("Failed to parse npm list output")?;
 
-        let mut all_packages = HashMap::new();
-        Self::flatten_npm_deps(&result.dependencies, &mut all_packages);
+        let mut all_packages = PackageInstances::default();
+        Self::flatten_npm_deps(&result.dependencies, &[], &mut all_packages);
 
         // If no packages found, consider it a failure
         if all_packages.is_empty() {
@@ -145,28 +148,44 @@ impl RuntimeResolver {
         Ok(all_packages)
     }
 
-    /// Recursively flatten pnpm dependencies
+    /// Recursively flatten pnpm dependencies, keeping every copy with its dependency chain
     fn flatten_pnpm_deps(
         deps: &HashMap<String, PnpmPackageInfo>,
-        output: &mut HashMap<String, String>,
+        parent_chain: &[String],
+        output: &mut PackageInstances,
     ) {
         for (name, pkg) in deps {
-            // Store first version found (most likely to be used)
-            output.entry(name.clone()).or_insert(pkg.version.clone());
+            let chain = child_chain(parent_chain, name);
+            output.add(PackageInstance::new(
+                name,
+                &pkg.version,
+                &chain_path(&chain),
+                chain.clone(),
+            ));
 
             // Recurse into nested dependencies AND devDependencies
-            Self::flatten_pnpm_deps(&pkg.dependencies, output);
-            Self::flatten_pnpm_deps(&pkg.dev_dependencies, output);
+            Self::flatten_pnpm_deps(&pkg.dependencies, &chain, output);
+            Self::flatten_pnpm_deps(&pkg.dev_dependencies, &chain, output);
         }
     }
 
-    /// Recursively flatten npm dependencies
-    fn flatten_npm_deps(deps: &HashMap<String, NpmPackage>, output: &mut HashMap<String, String>) {
+    /// Recursively flatten npm dependencies, keeping every copy with its dependency chain
+    fn flatten_npm_deps(
+        deps: &HashMap<String, NpmPackage>,
+        parent_chain: &[String],
+        output: &mut PackageInstances,
+    ) {
         for (name, pkg) in deps {
-            output.entry(name.clone()).or_insert(pkg.version.clone());
+            let chain = child_chain(parent_chain, name);
+            output.add(PackageInstance::new(
+                name,
+                &pkg.version,
+                &chain_path(&chain),
+                chain.clone(),
+            ));
             // Recurse into nested dependencies AND devDependencies
-            Self::flatten_npm_deps(&pkg.dependencies, output);
-            Self::flatten_npm_deps(&pkg.dev_dependencies, output);
+            Self::flatten_npm_deps(&pkg.dependencies, &chain, output);
+            Self::flatten_npm_deps(&pkg.dev_dependencies, &chain, output);
         }
     }
 
@@ -175,7 +194,7 @@ impl RuntimeResolver {
     #[allow(clippy::unnecessary_wraps)]
     fn scan_node_modules_fallback<P: AsRef<Path>>(
         dir: P,
-        output: &mut HashMap<String, String>,
+        output: &mut PackageInstances,
     ) -> Result<()> {
         use std::fs;
         use walkdir::WalkDir;
@@ -198,9 +217,18 @@ impl RuntimeResolver {
                         json.get("name").and_then(|n| n.as_str()),
                         json.get("version").and_then(|v| v.as_str()),
                     ) {
-                        output
-                            .entry(name.to_string())
-                            .or_insert(version.to_string());
+                        // Install path relative to the project, e.g. node_modules/a/node_modules/b
+                        let path = entry
+                            .path()
+                            .parent()
+                            .and_then(|p| p.strip_prefix(dir.as_ref()).ok())
+                            .map(|p| p.to_string_lossy().replace('\\', "/"))
+                            .unwrap_or_default();
+                        let mut chain: Vec<String> = install_chain(&path);
+                        if chain.is_empty() {
+                            chain.push(name.to_string());
+                        }
+                        output.add(PackageInstance::new(name, version, &path, chain));
                     }
                 }
             }
@@ -209,22 +237,35 @@ impl RuntimeResolver {
         Ok(())
     }
 
-    /// Get version for a package, with fallback to specific package query
+    /// Get version for a package (hoisted copy), with fallback to specific package query
     pub fn get_version(&mut self, package_name: &str) -> Option<String> {
+        self.instances(package_name)
+            .first()
+            .map(|instance| instance.version.clone())
+    }
+
+    /// Every installed copy of a package, with fallback to specific package query
+    pub fn instances(&mut self, package_name: &str) -> Vec<PackageInstance> {
         // Try cache first
-        if let Some(version) = self.packages.get(package_name) {
-            return Some(version.clone());
+        let cached = self.packages.get(package_name);
+        if !cached.is_empty() {
+            return cached.to_vec();
         }
 
         // Fallback: Query specific package
         if let Ok(version) = Self::query_specific_package(package_name, &self.base_dir) {
             // Cache it for future lookups
-            self.packages
-                .insert(package_name.to_string(), version.clone());
-            return Some(version);
+            let chain = vec![package_name.to_string()];
+            self.packages.add(PackageInstance::new(
+                package_name,
+                &version,
+                &chain_path(&chain),
+                chain,
+            ));
+            return self.packages.get(package_name).to_vec();
         }
 
-        None
+        Vec::new()
     }
 
     /// Query a specific package with pnpm list <package>
@@ -380,6 +421,13 @@ impl RuntimeResolver {
     }
 }
 
+// Helper: Dependency chain of a child package
+fn child_chain(parent_chain: &[String], name: &str) -> Vec<String> {
+    let mut chain = parent_chain.to_vec();
+    chain.push(name.to_string());
+    chain
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -407,10 +455,13 @@ mod tests {
             },
         );
 
-        let mut output = HashMap::new();
-        RuntimeResolver::flatten_pnpm_deps(&deps, &mut output);
+        let mut output = PackageInstances::default();
+        RuntimeResolver::flatten_pnpm_deps(&deps, &[], &mut output);
 
-        assert_eq!(output.get("debug"), Some(&"4.3.4".to_string()));
-        assert_eq!(output.get("ms"), Some(&"2.1.3".to_string()));
+        assert_eq!(output.get("debug")[0].version, "4.3.4");
+        let ms = &output.get("ms")[0];
+        assert_eq!(ms.version, "2.1.3");
+        assert_eq!(ms.chain_display(), "debug > ms");
+        assert_eq!(ms.path, "node_modules/debug/node_modules/ms");
     }
 }
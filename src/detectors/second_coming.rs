@@ -4,17 +4,17 @@
 // Corresponds to bash function:
 // - check_second_coming_repos() - Lines 581-609 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
+use crate::file_index::FileIndex;
 use std::path::Path;
 use std::process::{Command, Stdio};
-use walkdir::WalkDir;
 
 // Function: check_second_coming_repos
 // Purpose: Detect repository descriptions with "Sha1-Hulud: The Second Coming" pattern
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with git repositories matching the description pattern
-pub fn check_second_coming_repos(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_second_coming_repos(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for 'Second Coming' repository descriptions...",
@@ -23,17 +23,9 @@ pub fn check_second_coming_repos(scan_dir: &Path) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     // Look for git repositories
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.dirs() {
         let path = entry.path();
 
-        if !path.is_dir() {
-            continue;
-        }
-
         // Check if this is a .git directory
         if path.file_name().and_then(|n| n.to_str()) != Some(".git") {
             continue;
@@ -61,6 +53,27 @@ pub fn check_second_coming_repos(scan_dir: &Path) -> Vec<Finding> {
     findings
 }
 
+/// Registry entry for `check_second_coming_repos()`
+pub struct SecondComingReposDetector;
+
+impl Detector for SecondComingReposDetector {
+    fn id(&self) -> &'static str {
+        "second_coming_repos"
+    }
+
+    fn description(&self) -> &'static str {
+        "Repositories with 'Second Coming' marker"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_second_coming_repos(ctx.index)
+    }
+}
+
 // Helper function to get git repository description with timeout
 // Implements 5-second timeout to prevent hanging
 fn get_git_description(repo_dir: &Path) -> Option<String> {
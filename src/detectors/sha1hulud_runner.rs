@@ -4,17 +4,15 @@
 // Corresponds to bash function:
 // - check_github_actions_runner() - Lines 565-579 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::file_index::FileIndex;
 
 // Function: check_github_actions_runner
 // Purpose: Detect SHA1HULUD GitHub Actions runners in workflow files
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with workflow files containing SHA1HULUD runner references
-pub fn check_github_actions_runner(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_github_actions_runner(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for SHA1HULUD GitHub Actions runners...",
@@ -23,17 +21,9 @@ pub fn check_github_actions_runner(scan_dir: &Path) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     // Look for workflow files containing SHA1HULUD runner names
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.files() {
         let path = entry.path();
 
-        if !path.is_file() {
-            continue;
-        }
-
         // Check for YAML workflow files
         let filename = path.file_name().and_then(|n| n.to_str());
         if !filename.is_some_and(|f| {
@@ -45,7 +35,7 @@ pub fn check_github_actions_runner(scan_dir: &Path) -> Vec<Finding> {
         }
 
         // Read file content and check for SHA1HULUD runner references
-        if let Ok(content) = fs::read_to_string(path) {
+        if let Some(content) = index.read_text(entry).as_deref() {
             if content.to_lowercase().contains("sha1hulud") {
                 findings.push(Finding::new(
                     path.to_path_buf(),
@@ -59,3 +49,24 @@ pub fn check_github_actions_runner(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_github_actions_runner()`
+pub struct Sha1huludRunnerDetector;
+
+impl Detector for Sha1huludRunnerDetector {
+    fn id(&self) -> &'static str {
+        "github_sha1hulud_runners"
+    }
+
+    fn description(&self) -> &'static str {
+        "SHA1HULUD malicious runner references"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_github_actions_runner(ctx.index)
+    }
+}
@@ -3,13 +3,11 @@
 //
 // IMPORTANT: Bash uses "skip if already flagged" logic - only ONE finding per file!
 
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use regex::Regex;
 use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
 use std::sync::LazyLock;
-use walkdir::WalkDir;
 
 // HIGH PRIORITY: Dynamic TruffleHog download patterns
 static DOWNLOAD_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
@@ -44,7 +42,7 @@ static ENV_SUSPICIOUS: LazyLock<Regex> = LazyLock::new(|| {
 /// - Only ONE finding per file (skip if already flagged)
 /// - Same pattern order and risk levels as bash
 #[allow(clippy::too_many_lines)]
-pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_trufflehog_activity(index: &FileIndex) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for Trufflehog activity and secret scanning...",
@@ -55,26 +53,12 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     // Collect code files (matching bash: script_files + code_files)
     let extensions = &["js", "py", "sh", "json", "ts"];
-    let code_files: Vec<_> = WalkDir::new(&scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-        .collect();
+    let code_files: Vec<_> = index.files_with_extensions(extensions).collect();
 
     // 1. Look for trufflehog files by name (always HIGH RISK)
     // BASH: grep "trufflehog" all_files_raw.txt - matches ANY file with trufflehog in path
-    for entry in WalkDir::new(&scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-    {
-        let filename = entry.file_name().to_string_lossy().to_lowercase();
+    for entry in index.files() {
+        let filename = entry.file_name().to_lowercase();
         if filename.contains("trufflehog") {
             let path_key = entry.path().to_string_lossy().to_lowercase();
             findings.push(Finding::new(
@@ -90,8 +74,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
     // 2. HIGH PRIORITY: Dynamic TruffleHog download patterns (November 2025 attack)
     // BASH: NO deduplication for HIGH priority checks!
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if DOWNLOAD_PATTERN.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if DOWNLOAD_PATTERN.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "November 2025 pattern - Dynamic TruffleHog download via curl/wget/Bun"
@@ -109,8 +93,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
     // 3. HIGH PRIORITY: TruffleHog credential harvesting patterns
     // BASH: NO deduplication for HIGH priority checks!
     for entry in &code_files {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if CREDENTIAL_SCAN_PATTERN.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if CREDENTIAL_SCAN_PATTERN.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "TruffleHog credential scanning pattern detected".to_string(),
@@ -134,8 +118,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
         {
             continue;
         }
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if EXFIL_PATTERN.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if EXFIL_PATTERN.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Credential patterns with potential exfiltration".to_string(),
@@ -164,8 +148,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
         {
             continue;
         }
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if TRUFFLEHOG_REF.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if TRUFFLEHOG_REF.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Contains trufflehog references in source code".to_string(),
@@ -192,8 +176,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
         {
             continue;
         }
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if CREDENTIAL_PATTERN.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if CREDENTIAL_PATTERN.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Contains credential scanning patterns".to_string(),
@@ -220,8 +204,8 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
         {
             continue;
         }
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if ENV_SUSPICIOUS.is_match(&content) {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if ENV_SUSPICIOUS.is_match(content) {
                 findings.push(Finding::new(
                     entry.path().to_path_buf(),
                     "Potentially suspicious environment variable access".to_string(),
@@ -235,3 +219,24 @@ pub fn check_trufflehog_activity<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_trufflehog_activity()`
+pub struct TrufflehogDetector;
+
+impl Detector for TrufflehogDetector {
+    fn id(&self) -> &'static str {
+        "trufflehog_activity"
+    }
+
+    fn description(&self) -> &'static str {
+        "Trufflehog activity and secret scanning"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_trufflehog_activity(ctx.index)
+    }
+}
@@ -1,12 +1,10 @@
 // Typosquatting Detector
 // Rust port of: check_typosquatting()
 
-use crate::detectors::{Finding, RiskLevel};
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 use serde_json::Value;
 use std::collections::HashSet;
-use std::fs;
-use std::path::Path;
-use walkdir::WalkDir;
 
 // Popular packages commonly targeted for typosquatting
 const POPULAR_PACKAGES: &[&str] = &[
@@ -40,23 +38,19 @@ const POPULAR_PACKAGES: &[&str] = &[
 
 // Function: check_typosquatting
 // Purpose: Detect typosquatting and homoglyph attacks in package dependencies
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: TYPOSQUATTING_WARNINGS (global array)
 // Returns: Populates TYPOSQUATTING_WARNINGS with Unicode chars, confusables, and similar names
 #[allow(clippy::too_many_lines)]
-pub fn check_typosquatting<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_typosquatting(index: &FileIndex) -> Vec<Finding> {
     let mut findings = Vec::new();
     // BASH EXACT: Deduplicate by package_name only (not file:package)
     // Bash's warned_packages array effectively deduplicates by package name across all files
     let mut warned_packages: HashSet<String> = HashSet::new();
 
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file() && e.file_name() == "package.json")
-    {
-        if let Ok(content) = fs::read_to_string(entry.path()) {
-            if let Ok(json) = serde_json::from_str::<Value>(&content) {
+    for entry in index.files_named("package.json") {
+        if let Some(content) = index.read_text(entry).as_deref() {
+            if let Ok(json) = serde_json::from_str::<Value>(content) {
                 // Extract package names from dependencies sections
                 for section in &[
                     "dependencies",
@@ -273,3 +267,32 @@ pub fn check_typosquatting<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_typosquatting()` (paranoid mode only)
+pub struct TyposquattingDetector;
+
+impl Detector for TyposquattingDetector {
+    fn id(&self) -> &'static str {
+        "typosquatting_warnings"
+    }
+
+    fn description(&self) -> &'static str {
+        "Typosquatting and homoglyph attacks (paranoid)"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::Medium
+    }
+
+    fn paranoid_only(&self) -> bool {
+        true
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            "Checking for typosquatting and homoglyph attacks...",
+        );
+        check_typosquatting(ctx.index)
+    }
+}
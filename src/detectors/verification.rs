@@ -3,8 +3,8 @@
 
 #![allow(dead_code)]
 
-use crate::data::{CompromisedPackage, VERIFIED_FILES};
-use crate::detectors::lockfile_resolver::LockfileResolver;
+use crate::data::{CompromisedPackage, VerifiedFile};
+use crate::detectors::lockfile_resolver::{LockfileResolver, PackageInstance};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::collections::HashSet;
@@ -45,64 +45,114 @@ pub enum VerificationMethod {
 }
 
 /// Verify if a package is safe via lockfile or runtime resolution
+///
+/// Every installed copy is evaluated - a compromised nested copy (e.g.
+/// `node_modules/express/node_modules/debug`) makes the package compromised even
+/// when the hoisted copy is safe.
 pub fn verify_via_lockfile(
     package_name: &str,
     lockfile_resolver: Option<&LockfileResolver>,
     runtime_resolver: Option<&mut crate::detectors::runtime_resolver::RuntimeResolver>,
     compromised_packages: &HashSet<CompromisedPackage>,
 ) -> VerificationStatus {
-    // Try runtime resolver first (most accurate - actual installed version)
+    // Try runtime resolver first (most accurate - actual installed versions)
     if let Some(runtime) = runtime_resolver {
-        if let Some(installed_version) = runtime.get_version(package_name) {
-            let is_compromised = compromised_packages
-                .iter()
-                .any(|cp| cp.name == package_name && cp.version == installed_version);
-
-            if is_compromised {
-                return VerificationStatus::Compromised {
-                    reason: format!("Installed version {installed_version} is COMPROMISED"),
-                };
-            }
-            return VerificationStatus::Verified {
-                reason: format!("Installed version {installed_version} is safe"),
-                confidence: Confidence::High,
-                method: VerificationMethod::LockfileMatch,
-            };
+        let installed = runtime.instances(package_name);
+        if !installed.is_empty() {
+            return verify_instances(&installed, compromised_packages, |version, compromised| {
+                if compromised {
+                    format!("Installed version {version} is COMPROMISED")
+                } else {
+                    format!("Installed version {version} is safe")
+                }
+            });
         }
     }
 
     // Fallback to lockfile
     if let Some(lockfile) = lockfile_resolver {
-        if let Some(locked_version) = lockfile.get_version(package_name) {
-            let is_compromised = compromised_packages
-                .iter()
-                .any(|cp| cp.name == package_name && cp.version == locked_version);
-
-            if is_compromised {
-                return VerificationStatus::Compromised {
-                    reason: format!("Lockfile pins to COMPROMISED version {locked_version}"),
-                };
-            }
-            return VerificationStatus::Verified {
-                reason: format!("Lockfile pins to safe version {locked_version}"),
-                confidence: Confidence::High,
-                method: VerificationMethod::LockfileMatch,
-            };
+        let locked = lockfile.instances(package_name);
+        if !locked.is_empty() {
+            return verify_instances(locked, compromised_packages, |version, compromised| {
+                if compromised {
+                    format!("Lockfile pins to COMPROMISED version {version}")
+                } else {
+                    format!("Lockfile pins to safe version {version}")
+                }
+            });
         }
     }
 
     VerificationStatus::Unknown
 }
 
-/// Verify file by SHA-256 hash against AI-reviewed whitelist
-pub fn verify_file_by_hash(file_path: &Path) -> VerificationStatus {
+// Function: verify_instances
+// Purpose: Evaluate every installed/locked copy of a package against the compromised set
+// Args: instances - copies of one package, compromised_packages - known bad versions,
+//       describe - message for (version, is_compromised)
+// Returns: Compromised if any copy is compromised (naming its path and chain), else Verified
+fn verify_instances(
+    instances: &[PackageInstance],
+    compromised_packages: &HashSet<CompromisedPackage>,
+    describe: impl Fn(&str, bool) -> String,
+) -> VerificationStatus {
+    let is_compromised = |instance: &PackageInstance| {
+        compromised_packages.contains(&CompromisedPackage::new(
+            instance.name.clone(),
+            instance.version.clone(),
+        ))
+    };
+    // Nested copies are named by location so they can be found and removed
+    let locate = |instance: &PackageInstance| {
+        if instance.chain.len() > 1 {
+            format!(" ({} via {})", instance.path, instance.chain_display())
+        } else {
+            String::new()
+        }
+    };
+
+    let compromised: Vec<String> = instances
+        .iter()
+        .filter(|instance| is_compromised(instance))
+        .map(|instance| format!("{}{}", describe(&instance.version, true), locate(instance)))
+        .collect();
+    if !compromised.is_empty() {
+        return VerificationStatus::Compromised {
+            reason: compromised.join("; "),
+        };
+    }
+
+    let mut versions: Vec<&str> = instances.iter().map(|i| i.version.as_str()).collect();
+    versions.dedup();
+    let reason = if instances.len() == 1 {
+        describe(&instances[0].version, false)
+    } else {
+        format!(
+            "{} (all {} copies: {})",
+            describe(&instances[0].version, false),
+            instances.len(),
+            versions.join(", ")
+        )
+    };
+    VerificationStatus::Verified {
+        reason,
+        confidence: Confidence::High,
+        method: VerificationMethod::LockfileMatch,
+    }
+}
+
+/// Verify file by SHA-256 hash against AI-reviewed whitelist (`verified_files` in data/iocs.toml)
+pub fn verify_file_by_hash(
+    file_path: &Path,
+    verified_files: &[VerifiedFile],
+) -> VerificationStatus {
     // Calculate SHA-256 hash of file
     let Ok(hash) = calculate_file_hash(file_path) else {
         return VerificationStatus::Unknown;
     };
 
     // Check against verified files list
-    for verified in VERIFIED_FILES {
+    for verified in verified_files {
         if verified.hash == hash {
             return VerificationStatus::Verified {
                 reason: format!(
@@ -129,17 +179,24 @@ fn calculate_file_hash(file_path: &Path) -> Result<String, std::io::Error> {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use std::collections::HashMap;
+    use crate::detectors::lockfile_resolver::PackageInstances;
 
-    #[test]
-    fn test_verify_safe_via_lockfile() {
-        let mut packages = HashMap::new();
-        packages.insert("ansi-regex".to_string(), "6.1.0".to_string());
-
-        let resolver = LockfileResolver {
+    fn resolver(instances: &[(&str, &str, &str)]) -> LockfileResolver {
+        let mut packages = PackageInstances::default();
+        for (name, version, path) in instances {
+            let chain = crate::detectors::lockfile_resolver::install_chain(path);
+            packages.add(PackageInstance::new(name, version, path, chain));
+        }
+        LockfileResolver {
             packages,
             lockfile_type: Some(crate::detectors::lockfile_resolver::LockfileType::Pnpm),
-        };
+            importers: std::collections::BTreeMap::new(),
+        }
+    }
+
+    #[test]
+    fn test_verify_safe_via_lockfile() {
+        let resolver = resolver(&[("ansi-regex", "6.1.0", "node_modules/ansi-regex")]);
 
         let mut compromised = HashSet::new();
         compromised.insert(CompromisedPackage {
@@ -165,13 +222,7 @@ mod tests {
 
     #[test]
     fn test_verify_compromised_via_lockfile() {
-        let mut packages = HashMap::new();
-        packages.insert("ansi-regex".to_string(), "6.2.1".to_string());
-
-        let resolver = LockfileResolver {
-            packages,
-            lockfile_type: Some(crate::detectors::lockfile_resolver::LockfileType::Pnpm),
-        };
+        let resolver = resolver(&[("ansi-regex", "6.2.1", "node_modules/ansi-regex")]);
 
         let mut compromised = HashSet::new();
         compromised.insert(CompromisedPackage {
@@ -193,8 +244,9 @@ mod tests {
     #[test]
     fn test_verify_unknown_no_lockfile() {
         let resolver = LockfileResolver {
-            packages: HashMap::new(),
+            packages: PackageInstances::default(),
             lockfile_type: None,
+            importers: std::collections::BTreeMap::new(),
         };
 
         let compromised = HashSet::new();
@@ -202,4 +254,25 @@ mod tests {
 
         assert!(matches!(result, VerificationStatus::Unknown));
     }
+
+    #[test]
+    fn test_verify_finds_compromised_nested_copy() {
+        let resolver = resolver(&[
+            ("debug", "4.3.4", "node_modules/debug"),
+            ("debug", "4.4.2", "node_modules/express/node_modules/debug"),
+        ]);
+        let compromised: HashSet<_> = [CompromisedPackage::from_line("debug:4.4.2").unwrap()]
+            .into_iter()
+            .collect();
+
+        let result = verify_via_lockfile("debug", Some(&resolver), None, &compromised);
+
+        match result {
+            VerificationStatus::Compromised { reason } => assert_eq!(
+                reason,
+                "Lockfile pins to COMPROMISED version 4.4.2 (node_modules/express/node_modules/debug via express > debug)"
+            ),
+            _ => panic!("Expected Compromised status"),
+        }
+    }
 }
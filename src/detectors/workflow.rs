@@ -1,16 +1,15 @@
 // Workflow Files Detector
 // Rust port of: check_workflow_files()
 
-use crate::detectors::{Finding, RiskLevel};
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::detectors::{Detector, Finding, RiskLevel, ScanContext};
+use crate::file_index::FileIndex;
 
 // Function: check_workflow_files
 // Purpose: Detect malicious shai-hulud-workflow.yml files in project directories
-// Args: $1 = scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Modifies: WORKFLOW_FILES (global array)
 // Returns: Populates WORKFLOW_FILES array with paths to suspicious workflow files
-pub fn check_workflow_files<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
+pub fn check_workflow_files(index: &FileIndex) -> Vec<Finding> {
     crate::colors::print_status(
         crate::colors::Color::Blue,
         "Checking for malicious workflow files...",
@@ -19,24 +18,39 @@ pub fn check_workflow_files<P: AsRef<Path>>(scan_dir: P) -> Vec<Finding> {
     let mut findings = Vec::new();
 
     // Look specifically for shai-hulud-workflow.yml files
-    for entry in WalkDir::new(scan_dir)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-    {
-        if entry.file_name() == "shai-hulud-workflow.yml" {
-            findings.push(Finding::new(
-                entry.path().to_path_buf(),
-                "Known malicious workflow filename".to_string(),
-                RiskLevel::High,
-                "workflow",
-            ));
-        }
+    for entry in index.files_named("shai-hulud-workflow.yml") {
+        findings.push(Finding::new(
+            entry.path().to_path_buf(),
+            "Known malicious workflow filename".to_string(),
+            RiskLevel::High,
+            "workflow",
+        ));
     }
 
     findings
 }
 
+/// Registry entry for `check_workflow_files()`
+pub struct WorkflowFilesDetector;
+
+impl Detector for WorkflowFilesDetector {
+    fn id(&self) -> &'static str {
+        "workflow_files"
+    }
+
+    fn description(&self) -> &'static str {
+        "Known malicious shai-hulud-workflow.yml files"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_workflow_files(ctx.index)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -52,7 +66,7 @@ mod tests {
         let malicious_file = workflows_dir.join("shai-hulud-workflow.yml");
         fs::write(&malicious_file, "malicious content").unwrap();
 
-        let findings = check_workflow_files(temp.path());
+        let findings = check_workflow_files(&FileIndex::build(temp.path(), 1));
 
         assert_eq!(findings.len(), 1);
         assert_eq!(findings[0].risk_level, RiskLevel::High);
@@ -67,7 +81,7 @@ mod tests {
         let clean_file = workflows_dir.join("ci.yml");
         fs::write(&clean_file, "clean content").unwrap();
 
-        let findings = check_workflow_files(temp.path());
+        let findings = check_workflow_files(&FileIndex::build(temp.path(), 1));
 
         assert_eq!(findings.len(), 0);
     }
@@ -4,16 +4,15 @@
 // Corresponds to bash function:
 // - check_new_workflow_patterns() - Lines 348-371 in shai-hulud-detector.sh
 
-use super::{Finding, RiskLevel};
+use super::{Detector, Finding, RiskLevel, ScanContext};
 use crate::colors;
-use std::path::Path;
-use walkdir::WalkDir;
+use crate::file_index::FileIndex;
 
 // Function: check_new_workflow_patterns
 // Purpose: Detect November 2025 new workflow file patterns and actionsSecrets.json
-// Args: scan_dir (directory to scan)
+// Args: index - file index of scan_dir (directory to scan)
 // Returns: Vec<Finding> with paths to new attack pattern files
-pub fn check_new_workflow_patterns(scan_dir: &Path) -> Vec<Finding> {
+pub fn check_new_workflow_patterns(index: &FileIndex) -> Vec<Finding> {
     colors::print_status(
         colors::Color::Blue,
         "🔍 Checking for new workflow patterns...",
@@ -21,17 +20,9 @@ pub fn check_new_workflow_patterns(scan_dir: &Path) -> Vec<Finding> {
 
     let mut findings = Vec::new();
 
-    for entry in WalkDir::new(scan_dir)
-        .follow_links(false)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-    {
+    for entry in index.files() {
         let path = entry.path();
 
-        if !path.is_file() {
-            continue;
-        }
-
         // Look for formatter_123456789.yml workflow files in .github/workflows/
         if let Some(filename) = path.file_name().and_then(|n| n.to_str()) {
             if filename.starts_with("formatter_")
@@ -66,3 +57,32 @@ pub fn check_new_workflow_patterns(scan_dir: &Path) -> Vec<Finding> {
 
     findings
 }
+
+/// Registry entry for `check_new_workflow_patterns()`
+pub struct NewWorkflowPatternsDetector;
+
+impl Detector for NewWorkflowPatternsDetector {
+    fn id(&self) -> &'static str {
+        "new_workflow_patterns"
+    }
+
+    fn description(&self) -> &'static str {
+        "Malicious formatter workflows and actionsSecrets.json files"
+    }
+
+    fn default_severity(&self) -> RiskLevel {
+        RiskLevel::High
+    }
+
+    fn results_key(&self, finding: &Finding) -> &'static str {
+        if finding.category == "actions_secrets_files" {
+            "actions_secrets_files"
+        } else {
+            "new_workflow_files"
+        }
+    }
+
+    fn run(&self, ctx: &mut ScanContext) -> Vec<Finding> {
+        check_new_workflow_patterns(ctx.index)
+    }
+}
@@ -0,0 +1,314 @@
+// Yarn Lockfile Parser - classic v1 yarn.lock and Yarn 2+ (YAML with __metadata)
+// Purpose: Resolve exact versions behind npm:, alias and patch: descriptors
+//
+// Classic v1 entry format:
+//   "@babel/core@^7.0.0", "@babel/core@^7.1.0":
+//     version "7.1.2"
+//
+// Berry entry format:
+//   "debug@npm:^4.1.0, debug@npm:^4.3.4":
+//     version: 4.3.4
+//     resolution: "debug@npm:4.3.4"
+//
+//   "strip@npm:strip-ansi@^6.0.0":                      (alias: real name in resolution)
+//     resolution: "strip-ansi@npm:6.0.1"
+//
+//   "resolve@patch:resolve@npm%3A^1.20.0#~builtin<compat/resolve>":
+//     resolution: "resolve@patch:resolve@npm%3A1.22.1#~builtin<compat/resolve>::version=1.22.1&hash=07638b"
+
+use crate::detectors::pnpm_lockfile::scalar_string;
+use anyhow::{Context, Result};
+use serde_yaml::Value;
+
+/// Protocols of packages that live inside the project (never from a registry)
+const LOCAL_PROTOCOLS: [&str; 4] = ["workspace", "link", "portal", "file"];
+
+/// One resolved package of a classic v1 lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YarnClassicPackage {
+    /// Real package name ("npm:" aliases resolve to the target)
+    pub name: String,
+    pub version: String,
+    /// Descriptors of the entry, e.g. ["debug@^4.1.0", "debug@^4.3.4"]
+    pub descriptors: Vec<String>,
+    /// Tarball or git URL the package was fetched from
+    pub resolved: Option<String>,
+    /// Subresource integrity, e.g. "sha512-..."
+    pub integrity: Option<String>,
+}
+
+/// One resolved package of a Berry lockfile
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct YarnBerryPackage {
+    /// Real package name (from the resolution, so aliases resolve to the target)
+    pub name: String,
+    pub version: String,
+    /// Resolution protocol: "npm", "patch", "workspace", "git", "https", ...
+    pub protocol: String,
+    /// Full resolution, e.g. "debug@npm:4.3.4"
+    pub resolution: String,
+    /// Descriptors from package.json ranges that resolve to this package
+    pub descriptors: Vec<String>,
+}
+
+impl YarnBerryPackage {
+    /// True for workspace/link/portal/file packages (project code, not installed copies)
+    pub fn is_local(&self) -> bool {
+        LOCAL_PROTOCOLS.contains(&self.protocol.as_str())
+    }
+}
+
+// Function: is_berry_lockfile
+// Purpose: Distinguish Yarn 2+ lockfiles from classic v1 yarn.lock
+// Args: content - yarn.lock content
+// Returns: true if the lockfile has a __metadata block
+pub fn is_berry_lockfile(content: &str) -> bool {
+    content.lines().any(|line| line.starts_with("__metadata:"))
+}
+
+// Function: parse_berry_lockfile
+// Purpose: Parse a Yarn 2+ yarn.lock
+// Args: content - yarn.lock content
+// Returns: Resolved packages in lockfile order
+pub fn parse_berry_lockfile(content: &str) -> Result<Vec<YarnBerryPackage>> {
+    let root: Value = serde_yaml::from_str(content).context("Failed to parse yarn.lock")?;
+    let root = root
+        .as_mapping()
+        .context("yarn.lock is not a YAML mapping")?;
+
+    let mut packages = Vec::new();
+    for (key, entry) in root {
+        let Some(key) = scalar_string(key) else {
+            continue;
+        };
+        if key == "__metadata" {
+            continue;
+        }
+        let Some(entry) = entry.as_mapping() else {
+            continue;
+        };
+        let (Some(resolution), Some(version)) = (
+            entry.get("resolution").and_then(scalar_string),
+            entry.get("version").and_then(scalar_string),
+        ) else {
+            continue;
+        };
+        let Some((name, protocol)) = parse_resolution(&resolution) else {
+            continue;
+        };
+        packages.push(YarnBerryPackage {
+            name,
+            version,
+            protocol,
+            resolution,
+            descriptors: key.split(", ").map(str::to_string).collect(),
+        });
+    }
+    Ok(packages)
+}
+
+// Function: parse_classic_lockfile
+// Purpose: Parse a classic v1 yarn.lock (also what `bun bun.lockb` prints)
+// Args: content - yarn.lock content
+// Returns: Resolved packages in lockfile order
+pub fn parse_classic_lockfile(content: &str) -> Vec<YarnClassicPackage> {
+    let mut packages = Vec::new();
+    let mut current: Option<YarnClassicPackage> = None;
+
+    for line in content.lines() {
+        // Entry header: unindented, ends with ':'
+        if !line.starts_with([' ', '#']) && line.ends_with(':') {
+            packages.extend(current.take().filter(|p| !p.version.is_empty()));
+            let descriptors: Vec<String> = line
+                .trim_end_matches(':')
+                .split(", ")
+                .map(|d| d.trim_matches('"').to_string())
+                .collect();
+            current = descriptors
+                .first()
+                .and_then(|d| descriptor_package_name(d))
+                .map(|name| YarnClassicPackage {
+                    name,
+                    version: String::new(),
+                    descriptors,
+                    resolved: None,
+                    integrity: None,
+                });
+            continue;
+        }
+
+        // Entry fields are indented by exactly two spaces (dependencies by four)
+        let (Some(package), Some(field)) = (current.as_mut(), line.strip_prefix("  ")) else {
+            continue;
+        };
+        if field.starts_with(' ') {
+            continue;
+        }
+        let Some((key, value)) = field.split_once(' ') else {
+            continue;
+        };
+        let value = value.trim().trim_matches('"').to_string();
+        match key {
+            "version" => package.version = value,
+            "resolved" => package.resolved = Some(value),
+            "integrity" => package.integrity = Some(value),
+            _ => {}
+        }
+    }
+    packages.extend(current.filter(|p| !p.version.is_empty()));
+    packages
+}
+
+// Helper: Package installed for a descriptor
+// ("debug@^4.3.4" -> "debug", "strip@npm:strip-ansi@^6.0.0" -> "strip-ansi")
+fn descriptor_package_name(descriptor: &str) -> Option<String> {
+    let at = descriptor.get(1..)?.find('@')? + 1;
+    if let Some(target) = descriptor[at + 1..].strip_prefix("npm:") {
+        if let Some(target_at) = target.get(1..).and_then(|t| t.find('@')) {
+            return Some(target[..=target_at].to_string());
+        }
+    }
+    Some(descriptor[..at].to_string())
+}
+
+// Function: parse_resolution
+// Purpose: Split "name@protocol:reference" into package name and protocol
+// Args: resolution - e.g. "@scope/pkg@npm:1.0.0", "pkg@patch:pkg@npm%3A1.0.0#..."
+// Returns: (name, protocol), None if the resolution has no protocol
+pub fn parse_resolution(resolution: &str) -> Option<(String, String)> {
+    let at = resolution.get(1..)?.find('@')? + 1;
+    let name = &resolution[..at];
+    let (protocol, _) = resolution[at + 1..].split_once(':')?;
+    if name.is_empty() || protocol.is_empty() {
+        return None;
+    }
+    Some((name.to_string(), protocol.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_berry_lockfile() {
+        let content = r#"# This file is generated by running "yarn install" inside your project.
+# Manual changes might be lost - proceed with caution!
+
+__metadata:
+  version: 8
+  cacheKey: 10c0
+
+"@ctrl/tinycolor@npm:^4.1.0":
+  version: 4.1.1
+  resolution: "@ctrl/tinycolor@npm:4.1.1"
+  checksum: 10c0/aaa
+  languageName: node
+  linkType: hard
+
+"debug@npm:^4.1.0, debug@npm:^4.3.4":
+  version: 4.3.4
+  resolution: "debug@npm:4.3.4"
+  languageName: node
+  linkType: hard
+
+"my-app@workspace:.":
+  version: 0.0.0-use.local
+  resolution: "my-app@workspace:."
+  languageName: unknown
+  linkType: soft
+
+"resolve@patch:resolve@npm%3A^1.20.0#optional!builtin<compat/resolve>":
+  version: 1.22.8
+  resolution: "resolve@patch:resolve@npm%3A1.22.8#optional!builtin<compat/resolve>::version=1.22.8&hash=c3c19d"
+  languageName: node
+  linkType: hard
+
+"strip@npm:strip-ansi@^6.0.0":
+  version: 6.0.1
+  resolution: "strip-ansi@npm:6.0.1"
+  languageName: node
+  linkType: hard
+"#;
+        assert!(is_berry_lockfile(content));
+        let packages = parse_berry_lockfile(content).unwrap();
+        let summary: Vec<_> = packages
+            .iter()
+            .map(|p| format!("{}@{} ({})", p.name, p.version, p.protocol))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "@ctrl/tinycolor@4.1.1 (npm)",
+                "debug@4.3.4 (npm)",
+                "my-app@0.0.0-use.local (workspace)",
+                "resolve@1.22.8 (patch)",
+                "strip-ansi@6.0.1 (npm)",
+            ]
+        );
+        assert!(packages[2].is_local());
+        assert_eq!(
+            packages[1].descriptors,
+            vec!["debug@npm:^4.1.0", "debug@npm:^4.3.4"]
+        );
+        assert_eq!(packages[4].descriptors, vec!["strip@npm:strip-ansi@^6.0.0"]);
+    }
+
+    #[test]
+    fn test_parse_classic_lockfile() {
+        let content = r#"# THIS IS AN AUTOGENERATED FILE. DO NOT EDIT THIS FILE DIRECTLY.
+# yarn lockfile v1
+
+
+"@babel/core@^7.0.0", "@babel/core@^7.1.0":
+  version "7.1.2"
+  resolved "https://registry.yarnpkg.com/@babel/core/-/core-7.1.2.tgz"
+  dependencies:
+    "@babel/code-frame" "^7.0.0"
+
+debug@^4.3.4:
+  version "4.3.4"
+  resolved "https://registry.yarnpkg.com/debug/-/debug-4.3.4.tgz#1319f6579357f2338d3337d2cdd4914bb5dcc865"
+  integrity sha512-PRWFHuSU3eDtQJPvnNY7Jcket1j0t5OuOsFzPPzsekD52Zl8qUfFIPEiswXqIvHWGVHOgX+7G/vCNNhehwxfkQ==
+  dependencies:
+    version "1.0.0"
+
+"strip@npm:strip-ansi@^6.0.0":
+  version "6.0.1"
+"#;
+        let packages = parse_classic_lockfile(content);
+        let summary: Vec<_> = packages
+            .iter()
+            .map(|p| format!("{}@{} <- {}", p.name, p.version, p.descriptors.join(", ")))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "@babel/core@7.1.2 <- @babel/core@^7.0.0, @babel/core@^7.1.0",
+                "debug@4.3.4 <- debug@^4.3.4",
+                "strip-ansi@6.0.1 <- strip@npm:strip-ansi@^6.0.0",
+            ]
+        );
+        assert!(packages[1]
+            .resolved
+            .as_deref()
+            .unwrap()
+            .ends_with(".tgz#1319f6579357f2338d3337d2cdd4914bb5dcc865"));
+        assert!(packages[1]
+            .integrity
+            .as_deref()
+            .unwrap()
+            .starts_with("sha512-PRWF"));
+        assert_eq!(packages[2].integrity, None);
+    }
+
+    #[test]
+    fn test_classic_lockfile_is_not_berry() {
+        let classic = "# yarn lockfile v1\n\ndebug@^4.3.4:\n  version \"4.3.4\"\n";
+        assert!(!is_berry_lockfile(classic));
+        assert_eq!(
+            parse_resolution("@scope/pkg@npm:1.0.0"),
+            Some(("@scope/pkg".to_string(), "npm".to_string()))
+        );
+        assert_eq!(parse_resolution("debug"), None);
+    }
+}
@@ -0,0 +1,985 @@
+// IOC Feeds - Where compromised package data comes from
+// Purpose: Select the feed source (GitHub, custom URL, local file), honour --offline,
+//          cache downloads with metadata (TTL, ETag/Last-Modified, SHA-256) and
+//          refresh the cache explicitly (update-feeds)
+//
+// Feeds: compromised-packages.txt (package:version list, --feed) and iocs.toml
+// (hashes, namespaces, verified files - see data/iocs.toml, --ioc-feed).
+//
+// Cache layout (default: $XDG_CACHE_HOME/shai-hulud-detector):
+//   <feed>            feed content
+//   <feed>.meta.json  FeedMetadata (source, fetch time, validators, hash)
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::Duration;
+
+/// Upstream compromised-packages.txt maintained by the Bash scanner project
+pub const DEFAULT_FEED_URL: &str =
+    "https://raw.githubusercontent.com/Cobenian/shai-hulud-detect/main/compromised-packages.txt";
+
+/// Legacy local feed file (next to the binary or in the current directory)
+pub const LOCAL_CACHE_FILE: &str = "compromised-packages.txt";
+
+/// Cache name of the compromised package feed
+pub const COMPROMISED_PACKAGES_FEED: &str = "compromised-packages.txt";
+
+/// IOC data file (hashes, namespaces, verified files) published with this scanner
+pub const DEFAULT_IOC_URL: &str =
+    "https://raw.githubusercontent.com/gstrainovic/shai-hulud-detect-rust/main/data/iocs.toml";
+
+/// Cache name of the IOC data feed
+pub const IOC_FEED: &str = "iocs.toml";
+
+/// Environment variable overriding the cache directory
+pub const CACHE_DIR_ENV: &str = "SHAI_HULUD_CACHE_DIR";
+
+/// Explicitly selected feed source (--feed)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FeedSource {
+    Url(String),
+    File(PathBuf),
+}
+
+impl FromStr for FeedSource {
+    type Err = std::convert::Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.starts_with("https://") || s.starts_with("http://") {
+            Ok(Self::Url(s.to_string()))
+        } else {
+            Ok(Self::File(PathBuf::from(s)))
+        }
+    }
+}
+
+impl std::fmt::Display for FeedSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Url(url) => write!(f, "{url}"),
+            Self::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// How IOC feeds are loaded (mirrors --offline / --feed / --ioc-feed / --extra-iocs /
+/// --cache-dir / --feed-ttl)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FeedOptions {
+    /// Never access the network - use the feed file, local cache or embedded list
+    pub offline: bool,
+    /// Explicit feed source instead of the upstream GitHub feed
+    pub source: Option<FeedSource>,
+    /// Explicit IOC data source instead of the published data/iocs.toml
+    pub ioc_source: Option<FeedSource>,
+    /// User-supplied IOC files merged into the loaded IOC data
+    pub extra_iocs: Vec<PathBuf>,
+    /// Cache directory (None = $SHAI_HULUD_CACHE_DIR or the XDG cache directory)
+    pub cache_dir: Option<PathBuf>,
+    /// Use a cached download without re-fetching while it is younger than this
+    /// (zero = always revalidate with a conditional request)
+    pub ttl: Duration,
+    /// Fail instead of falling back to a stale cache, local copy or the embedded snapshot
+    pub require_fresh: bool,
+}
+
+impl FeedOptions {
+    // Function: validate
+    // Purpose: Reject contradicting feed options
+    // Args: self
+    // Returns: Error if a URL feed is combined with --offline
+    pub fn validate(&self) -> Result<()> {
+        if !self.offline {
+            return Ok(());
+        }
+        for (flag, source) in [("--feed", &self.source), ("--ioc-feed", &self.ioc_source)] {
+            if let Some(FeedSource::Url(url)) = source {
+                bail!(
+                    "Error: {flag} {url} requires network access and cannot be used with --offline"
+                );
+            }
+        }
+        Ok(())
+    }
+
+    // Function: check_fresh
+    // Purpose: Enforce --require-fresh-feed for a loaded feed
+    // Args: metadata - metadata of the loaded feed
+    // Returns: Error if fresh feeds are required and this one was a degraded fallback
+    pub fn check_fresh(&self, metadata: &FeedMetadata) -> Result<()> {
+        if !self.require_fresh || metadata.origin.is_some_and(FeedOrigin::is_fresh) {
+            return Ok(());
+        }
+        let origin = metadata
+            .origin
+            .map_or("unknown source", FeedOrigin::describe);
+        bail!(
+            "Error: --require-fresh-feed: {} is only available from {origin} ({}, generation {}) - refusing to scan with degraded data",
+            metadata.name,
+            metadata.source,
+            metadata.fetched_at
+        );
+    }
+
+    /// URL to download a feed from, or None when offline / reading a local feed file
+    pub fn download_url<'a>(&self, spec: &FeedSpec<'a>) -> Option<&'a str> {
+        match (spec.source, self.offline) {
+            (_, true) | (Some(FeedSource::File(_)), _) => None,
+            (Some(FeedSource::Url(url)), false) => Some(url),
+            (None, false) => Some(spec.default_url),
+        }
+    }
+
+    /// Feed cache in the configured (or default) cache directory
+    pub fn cache(&self) -> Option<FeedCache> {
+        self.cache_dir
+            .clone()
+            .or_else(default_cache_dir)
+            .map(FeedCache::new)
+    }
+}
+
+// Function: default_cache_dir
+// Purpose: Resolve the default feed cache directory
+// Args: None
+// Returns: $SHAI_HULUD_CACHE_DIR, else <XDG cache dir>/shai-hulud-detector
+pub fn default_cache_dir() -> Option<PathBuf> {
+    std::env::var_os(CACHE_DIR_ENV)
+        .filter(|dir| !dir.is_empty())
+        .map(PathBuf::from)
+        .or_else(|| dirs::cache_dir().map(|dir| dir.join("shai-hulud-detector")))
+}
+
+// Function: parse_ttl
+// Purpose: Parse a --feed-ttl value (plain seconds or with s/m/h/d suffix)
+// Args: value - e.g. "0", "900", "30m", "6h", "1d"
+// Returns: Duration or error message for clap
+pub fn parse_ttl(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let (number, unit) = match value.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&value[..i], c),
+        _ => (value, 's'),
+    };
+    let amount: u64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{value}' (use e.g. 0, 900, 30m, 6h, 1d)"))?;
+    let multiplier: u64 = match unit {
+        's' => 1,
+        'm' => 60,
+        'h' => 3600,
+        'd' => 86400,
+        _ => return Err(format!("invalid duration unit '{unit}' (use s, m, h or d)")),
+    };
+    let seconds = amount
+        .checked_mul(multiplier)
+        .ok_or_else(|| format!("duration '{value}' is too large"))?;
+    Ok(Duration::from_secs(seconds))
+}
+
+/// How a loaded feed was obtained
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FeedOrigin {
+    /// Downloaded during this run
+    Downloaded,
+    /// Cached copy confirmed current by the server (304 Not Modified)
+    Revalidated,
+    /// Cached download younger than --feed-ttl
+    FreshCache,
+    /// Cached download used because the feed could not be fetched
+    StaleCache,
+    /// Legacy local compromised-packages.txt next to the binary / in the cwd
+    LocalFile,
+    /// Explicit --feed / --ioc-feed / --extra-iocs file chosen by the user
+    FeedFile,
+    /// Data compiled into the binary
+    Embedded,
+}
+
+impl FeedOrigin {
+    /// Current data (not a fallback) - accepted by --require-fresh-feed
+    pub fn is_fresh(self) -> bool {
+        matches!(
+            self,
+            Self::Downloaded | Self::Revalidated | Self::FreshCache | Self::FeedFile
+        )
+    }
+
+    pub fn describe(self) -> &'static str {
+        match self {
+            Self::Downloaded => "live download",
+            Self::Revalidated => "cache revalidated with the server",
+            Self::FreshCache => "cache within --feed-ttl",
+            Self::StaleCache => "stale cache",
+            Self::LocalFile => "local file",
+            Self::FeedFile => "feed file",
+            Self::Embedded => "embedded snapshot",
+        }
+    }
+}
+
+/// Where a loaded feed came from - persisted next to cached feeds and reported in scan output
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct FeedMetadata {
+    pub name: String,
+    pub source: String,
+    pub fetched_at: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub etag: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_modified: Option<String>,
+    pub sha256: String,
+    pub entries: usize,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_file: Option<String>,
+    /// How this run obtained the feed (not stored in the cache metadata)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub origin: Option<FeedOrigin>,
+}
+
+impl FeedMetadata {
+    // Function: new
+    // Purpose: Describe feed content loaded from a source
+    // Args: name - feed name, source - URL or path, content - feed content,
+    //       entries - number of parsed entries
+    // Returns: FeedMetadata fetched now, without HTTP validators
+    pub fn new(name: &str, source: &str, content: &str, entries: usize) -> Self {
+        Self {
+            name: name.to_string(),
+            source: source.to_string(),
+            fetched_at: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+            etag: None,
+            last_modified: None,
+            sha256: sha256_hex(content),
+            entries,
+            cache_file: None,
+            origin: None,
+        }
+    }
+
+    /// Same metadata, marked with how this run obtained the feed
+    #[must_use]
+    pub fn with_origin(mut self, origin: FeedOrigin) -> Self {
+        self.origin = Some(origin);
+        self
+    }
+
+    /// Feed generation line for the report: origin, entries, timestamp and hash
+    pub fn generation(&self) -> String {
+        format!(
+            "{}: {} - {} entries from {}, generation {}, sha256 {}",
+            self.name,
+            self.origin.map_or("unknown source", FeedOrigin::describe),
+            self.entries,
+            self.source,
+            self.fetched_at,
+            &self.sha256[..self.sha256.len().min(12)]
+        )
+    }
+
+    /// Time the feed was fetched, if the stored timestamp is valid
+    pub fn fetched_time(&self) -> Option<DateTime<Utc>> {
+        DateTime::parse_from_rfc3339(&self.fetched_at)
+            .ok()
+            .map(|t| t.with_timezone(&Utc))
+    }
+
+    /// Cached copy is young enough to be used without re-fetching
+    pub fn is_fresh(&self, ttl: Duration) -> bool {
+        let Some(fetched) = self.fetched_time() else {
+            return false;
+        };
+        let age = Utc::now().signed_duration_since(fetched);
+        age >= chrono::Duration::zero() && age.to_std().is_ok_and(|age| age < ttl)
+    }
+
+    /// One-line description for the scan output
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{}: {} entries from {} (fetched {}, sha256 {}",
+            self.name,
+            self.entries,
+            self.source,
+            self.fetched_at,
+            &self.sha256[..self.sha256.len().min(12)]
+        );
+        if let Some(ref etag) = self.etag {
+            summary.push_str(&format!(", ETag {etag}"));
+        }
+        if let Some(ref last_modified) = self.last_modified {
+            summary.push_str(&format!(", Last-Modified {last_modified}"));
+        }
+        summary.push(')');
+        summary
+    }
+}
+
+/// SHA-256 of feed content as lowercase hex
+pub fn sha256_hex(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Directory holding downloaded feeds and their metadata
+#[derive(Debug, Clone)]
+pub struct FeedCache {
+    dir: PathBuf,
+}
+
+impl FeedCache {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn data_path(&self, name: &str) -> PathBuf {
+        self.dir.join(name)
+    }
+
+    pub fn meta_path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{name}.meta.json"))
+    }
+
+    // Function: load
+    // Purpose: Read a cached feed and its metadata
+    // Args: name - feed name
+    // Returns: (content, metadata), None if missing, unreadable or the hash does not match
+    pub fn load(&self, name: &str) -> Option<(String, FeedMetadata)> {
+        let content = fs::read_to_string(self.data_path(name)).ok()?;
+        let meta: FeedMetadata =
+            serde_json::from_str(&fs::read_to_string(self.meta_path(name)).ok()?).ok()?;
+        // Ignore a cache that was modified or truncated after download
+        (meta.sha256 == sha256_hex(&content)).then_some((content, meta))
+    }
+
+    // Function: store
+    // Purpose: Write feed content and metadata to the cache
+    // Args: name - feed name, content - feed content, metadata - metadata to persist
+    // Returns: Metadata including the cache file path
+    pub fn store(&self, name: &str, content: &str, metadata: FeedMetadata) -> Result<FeedMetadata> {
+        fs::create_dir_all(&self.dir)
+            .with_context(|| format!("Failed to create cache dir {}", self.dir.display()))?;
+
+        let data_path = self.data_path(name);
+        let metadata = FeedMetadata {
+            cache_file: Some(crate::utils::normalize_path(&data_path)),
+            ..metadata
+        };
+        fs::write(&data_path, content)
+            .with_context(|| format!("Failed to write {}", data_path.display()))?;
+        self.write_meta(name, &metadata)?;
+        Ok(metadata)
+    }
+
+    fn write_meta(&self, name: &str, metadata: &FeedMetadata) -> Result<()> {
+        let meta_path = self.meta_path(name);
+        // The origin describes one run, not the cached file
+        let stored = FeedMetadata {
+            origin: None,
+            ..metadata.clone()
+        };
+        fs::write(&meta_path, serde_json::to_string_pretty(&stored)?)
+            .with_context(|| format!("Failed to write {}", meta_path.display()))
+    }
+}
+
+/// Result of a conditional feed request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FetchStatus {
+    /// Server answered 304 - the cached copy is current
+    NotModified,
+    /// New content was downloaded (and cached when possible)
+    Downloaded,
+}
+
+#[derive(Debug, Clone)]
+pub struct FetchedFeed {
+    pub content: String,
+    pub metadata: FeedMetadata,
+    pub status: FetchStatus,
+}
+
+// Function: fetch
+// Purpose: Download a feed, revalidating a cached copy with If-None-Match/If-Modified-Since
+// Args: url - feed URL, name - feed name, cache - feed cache (if available),
+//       cached - cached (content, metadata) from the same URL, count - entry counter
+// Returns: FetchedFeed or error if the request failed or the download has no entries
+pub fn fetch(
+    url: &str,
+    name: &str,
+    cache: Option<&FeedCache>,
+    cached: Option<(String, FeedMetadata)>,
+    count: impl Fn(&str) -> usize,
+) -> Result<FetchedFeed> {
+    let mut request = ureq::get(url)
+        .config()
+        .timeout_global(Some(Duration::from_secs(10)))
+        .build();
+    if let Some((_, ref meta)) = cached {
+        if let Some(ref etag) = meta.etag {
+            request = request.header("If-None-Match", etag);
+        }
+        if let Some(ref last_modified) = meta.last_modified {
+            request = request.header("If-Modified-Since", last_modified);
+        }
+    }
+
+    let response = request.call().context("HTTP request failed")?;
+
+    if response.status() == 304 {
+        if let Some((content, meta)) = cached {
+            let metadata = FeedMetadata {
+                fetched_at: Utc::now().to_rfc3339_opts(chrono::SecondsFormat::Secs, true),
+                ..meta
+            };
+            if let Some(cache) = cache {
+                // Best effort: a stale timestamp only costs another conditional request
+                let _ = cache.write_meta(name, &metadata);
+            }
+            return Ok(FetchedFeed {
+                content,
+                metadata,
+                status: FetchStatus::NotModified,
+            });
+        }
+        bail!("Server answered 304 Not Modified without a cached copy");
+    }
+
+    let header = |key: &str| {
+        response
+            .headers()
+            .get(key)
+            .and_then(|v| v.to_str().ok())
+            .map(ToString::to_string)
+    };
+    let etag = header("etag");
+    let last_modified = header("last-modified");
+
+    let content = response
+        .into_body()
+        .read_to_string()
+        .context("Failed to read response")?;
+
+    // Never replace a good cache with a broken download (captive portal page, empty body)
+    let entries = count(&content);
+    if entries == 0 {
+        bail!("Downloaded feed contains no entries");
+    }
+
+    let metadata = FeedMetadata {
+        etag,
+        last_modified,
+        ..FeedMetadata::new(name, url, &content, entries)
+    };
+    let metadata = match cache {
+        Some(cache) => match cache.store(name, &content, metadata.clone()) {
+            Ok(stored) => stored,
+            Err(e) => {
+                crate::colors::print_status(
+                    crate::colors::Color::Yellow,
+                    &format!("⚠️  Could not cache file: {e}"),
+                );
+                metadata
+            }
+        },
+        None => metadata,
+    };
+
+    Ok(FetchedFeed {
+        content,
+        metadata,
+        status: FetchStatus::Downloaded,
+    })
+}
+
+/// A feed the scanner loads (cache name, status wording, upstream URL, explicit source)
+#[derive(Debug, Clone, Copy)]
+pub struct FeedSpec<'a> {
+    /// Cache file name
+    pub name: &'a str,
+    /// What the feed contains, for status messages ("compromised packages")
+    pub label: &'a str,
+    /// Unit for entry counts ("packages")
+    pub unit: &'a str,
+    /// Upstream URL used when no explicit source is configured
+    pub default_url: &'a str,
+    /// Explicit source (--feed / --ioc-feed)
+    pub source: Option<&'a FeedSource>,
+}
+
+impl FeedSpec<'_> {
+    /// Human-readable name of the feed source
+    pub fn source_label(&self) -> String {
+        self.source
+            .map_or_else(|| self.default_url.to_string(), ToString::to_string)
+    }
+}
+
+// Function: load
+// Purpose: Load a feed honouring --feed/--ioc-feed, --offline, the cache TTL and
+//          conditional re-fetches, falling back to the cached or a local copy
+// Args: spec - feed to load, options - feed options, local_file - legacy local copy,
+//       count - entry counter for status messages and metadata
+// Returns: (content, metadata), None if no source was available (caller falls back
+//          to its compiled-in data), error if an explicit feed file is unreadable
+pub fn load(
+    spec: &FeedSpec,
+    options: &FeedOptions,
+    local_file: Option<&Path>,
+    count: impl Fn(&str) -> usize,
+) -> Result<Option<(String, FeedMetadata)>> {
+    options.validate()?;
+    let unit = spec.unit;
+
+    // Explicit feed file: no fallback, the user chose the source
+    if let Some(FeedSource::File(feed_file)) = spec.source {
+        let content = fs::read_to_string(feed_file)
+            .with_context(|| format!("Failed to read feed file {}", feed_file.display()))?;
+        let entries = count(&content);
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!(
+                "📦 Using feed file {} ({entries} {unit})",
+                feed_file.display()
+            ),
+        );
+        let source = crate::utils::normalize_path(feed_file);
+        let metadata = FeedMetadata::new(spec.name, &source, &content, entries)
+            .with_origin(FeedOrigin::FeedFile);
+        return Ok(Some((content, metadata)));
+    }
+
+    let cache = options.cache();
+
+    // STRATEGY: GitHub-First (always fresh data!)
+    // 1. Try GitHub download (best - always up to date) - skipped with --offline
+    //    or while the cached download is younger than --feed-ttl
+    // 2. Fall back to cached/local files (offline mode)
+    // 3. Caller falls back to its embedded data
+    if let Some(url) = options.download_url(spec) {
+        let source = if url == spec.default_url {
+            "GitHub"
+        } else {
+            url
+        };
+        let cached = cache
+            .as_ref()
+            .and_then(|c| c.load(spec.name))
+            .filter(|(_, meta)| meta.source == url);
+
+        if let Some((content, metadata)) = cached
+            .as_ref()
+            .filter(|(_, meta)| meta.is_fresh(options.ttl))
+        {
+            crate::colors::print_status(
+                crate::colors::Color::Blue,
+                &format!(
+                    "📦 Using cached feed from {source} ({} {unit}, within --feed-ttl)",
+                    count(content)
+                ),
+            );
+            let metadata = metadata.clone().with_origin(FeedOrigin::FreshCache);
+            return Ok(Some((content.clone(), metadata)));
+        }
+
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!("📡 Fetching latest {} from {source}...", spec.label),
+        );
+
+        match fetch(url, spec.name, cache.as_ref(), cached, &count) {
+            Ok(fetched) => {
+                let entries = count(&fetched.content);
+                let (message, origin) = match fetched.status {
+                    FetchStatus::NotModified => (
+                        format!("✅ {source} feed unchanged - using cache ({entries} {unit})"),
+                        FeedOrigin::Revalidated,
+                    ),
+                    FetchStatus::Downloaded => (
+                        format!("✅ Downloaded {entries} {} from {source}", spec.label),
+                        FeedOrigin::Downloaded,
+                    ),
+                };
+                crate::colors::print_status(crate::colors::Color::Green, &message);
+                let metadata = fetched.metadata.with_origin(origin);
+                return Ok(Some((fetched.content, metadata)));
+            }
+            Err(e) => {
+                crate::colors::print_status(
+                    crate::colors::Color::Yellow,
+                    &format!("⚠️  {source} download failed: {e} - using cached files..."),
+                );
+            }
+        }
+    } else {
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!("📴 Offline mode: skipping {} download", spec.label),
+        );
+    }
+
+    // Fallback: Try cached download (any source), then the local file. An explicit
+    // --feed URL only falls back to a cached copy of that same URL.
+    let explicit_url = match spec.source {
+        Some(FeedSource::Url(url)) => Some(url),
+        _ => None,
+    };
+    let cached = cache
+        .as_ref()
+        .and_then(|c| c.load(spec.name))
+        .filter(|(_, meta)| explicit_url.is_none_or(|url| meta.source == *url))
+        .map(|(content, meta)| {
+            let origin = if meta.is_fresh(options.ttl) {
+                FeedOrigin::FreshCache
+            } else {
+                FeedOrigin::StaleCache
+            };
+            (content, meta.with_origin(origin))
+        });
+    if let (Some(url), None) = (explicit_url, &cached) {
+        bail!("Error: --feed {url} could not be downloaded and no cached copy of it is available");
+    }
+    let local = || {
+        let local_file = local_file?;
+        let content = fs::read_to_string(local_file).ok()?;
+        let source = crate::utils::normalize_path(local_file);
+        let metadata = FeedMetadata::new(spec.name, &source, &content, count(&content))
+            .with_origin(FeedOrigin::LocalFile);
+        Some((content, metadata))
+    };
+    let found = cached.or_else(local);
+    if let Some((ref content, _)) = found {
+        crate::colors::print_status(
+            crate::colors::Color::Blue,
+            &format!("📦 Using cached file ({} {unit})", count(content)),
+        );
+    }
+    Ok(found)
+}
+
+// Function: update
+// Purpose: Refresh one feed in the cache without scanning (update-feeds subcommand)
+// Args: spec - feed to refresh, options - feed options (--cache-dir),
+//       validate - parses the content and returns its entry count
+// Returns: Metadata of the cached feed and whether it changed
+pub fn update(
+    spec: &FeedSpec,
+    options: &FeedOptions,
+    validate: impl Fn(&str) -> Result<usize>,
+) -> Result<(FeedMetadata, FetchStatus)> {
+    options.validate()?;
+    if options.offline {
+        bail!("Error: update-feeds needs network access and cannot be used with --offline");
+    }
+    let cache = options
+        .cache()
+        .context("No cache directory available - use --cache-dir or set SHAI_HULUD_CACHE_DIR")?;
+    let count = |content: &str| validate(content).unwrap_or(0);
+
+    let (content, metadata, status) = match spec.source {
+        Some(FeedSource::File(path)) => {
+            let content = fs::read_to_string(path)
+                .with_context(|| format!("Failed to read feed file {}", path.display()))?;
+            let source = crate::utils::normalize_path(path);
+            let metadata = FeedMetadata::new(spec.name, &source, &content, count(&content));
+            (content, metadata, FetchStatus::Downloaded)
+        }
+        _ => {
+            let url = options.download_url(spec).unwrap_or(spec.default_url);
+            // Ignore the TTL, but still revalidate instead of re-downloading
+            let cached = cache.load(spec.name).filter(|(_, meta)| meta.source == url);
+            let fetched = fetch(url, spec.name, None, cached, count)
+                .with_context(|| format!("Failed to download {url}"))?;
+            (fetched.content, fetched.metadata, fetched.status)
+        }
+    };
+
+    // Never replace a good cache with a broken download
+    let entries = validate(&content)
+        .with_context(|| format!("Invalid {} feed {}", spec.label, spec.source_label()))?;
+    if entries == 0 {
+        bail!("Feed {} contains no {}", spec.source_label(), spec.label);
+    }
+
+    let metadata = match status {
+        FetchStatus::NotModified => {
+            cache.write_meta(spec.name, &metadata)?;
+            metadata
+        }
+        FetchStatus::Downloaded => cache.store(spec.name, &content, metadata)?,
+    };
+    Ok((metadata, status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn packages_spec(source: Option<&FeedSource>) -> FeedSpec<'_> {
+        FeedSpec {
+            name: COMPROMISED_PACKAGES_FEED,
+            label: "compromised packages",
+            unit: "packages",
+            default_url: DEFAULT_FEED_URL,
+            source,
+        }
+    }
+
+    fn count_lines(content: &str) -> Result<usize> {
+        Ok(content
+            .lines()
+            .filter(|l| !l.trim().is_empty() && !l.starts_with('#'))
+            .count())
+    }
+
+    #[test]
+    fn test_feed_source_and_download_url() {
+        assert_eq!(
+            "https://example.com/feed.txt"
+                .parse::<FeedSource>()
+                .unwrap(),
+            FeedSource::Url("https://example.com/feed.txt".to_string())
+        );
+        assert_eq!(
+            "feeds/packages.txt".parse::<FeedSource>().unwrap(),
+            FeedSource::File(PathBuf::from("feeds/packages.txt"))
+        );
+
+        assert_eq!(
+            FeedOptions::default().download_url(&packages_spec(None)),
+            Some(DEFAULT_FEED_URL)
+        );
+        let offline = FeedOptions {
+            offline: true,
+            ..FeedOptions::default()
+        };
+        assert_eq!(offline.download_url(&packages_spec(None)), None);
+
+        let offline_url = FeedOptions {
+            offline: true,
+            ioc_source: Some(FeedSource::Url("https://example.com/f".to_string())),
+            ..FeedOptions::default()
+        };
+        assert!(offline_url.validate().is_err());
+    }
+
+    #[test]
+    fn test_update_feeds_from_file() {
+        let temp = TempDir::new().unwrap();
+        let feed = temp.path().join("feed.txt");
+        fs::write(
+            &feed,
+            "# comment\n@ctrl/tinycolor:4.1.1\nkoa2-swagger-ui:5.11.1\n",
+        )
+        .unwrap();
+
+        let source = FeedSource::File(feed);
+        let spec = packages_spec(Some(&source));
+        let options = FeedOptions {
+            cache_dir: Some(temp.path().join("cache")),
+            ..FeedOptions::default()
+        };
+        let (metadata, _) = update(&spec, &options, count_lines).unwrap();
+        assert_eq!(metadata.entries, 2);
+
+        let cache = options.cache().unwrap();
+        let (_, cached) = cache.load(COMPROMISED_PACKAGES_FEED).unwrap();
+        assert_eq!(cached, metadata);
+
+        let offline = FeedOptions {
+            offline: true,
+            ..options.clone()
+        };
+        assert!(update(&spec, &offline, count_lines).is_err());
+
+        // A feed without entries never replaces the cache
+        let empty = temp.path().join("empty.txt");
+        fs::write(&empty, "# nothing\n").unwrap();
+        let empty_source = FeedSource::File(empty);
+        assert!(update(&packages_spec(Some(&empty_source)), &options, count_lines).is_err());
+        assert_eq!(cache.load(COMPROMISED_PACKAGES_FEED).unwrap().1, metadata);
+    }
+
+    #[test]
+    fn test_load_offline_uses_cache_then_local_file() {
+        let temp = TempDir::new().unwrap();
+        let options = FeedOptions {
+            offline: true,
+            cache_dir: Some(temp.path().join("cache")),
+            ..FeedOptions::default()
+        };
+        let count = |c: &str| count_lines(c).unwrap();
+
+        let local = temp.path().join("compromised-packages.txt");
+        fs::write(&local, "a:1.0.0\n").unwrap();
+        let (content, metadata) = load(&packages_spec(None), &options, Some(&local), count)
+            .unwrap()
+            .unwrap();
+        assert_eq!(content, "a:1.0.0\n");
+        assert_eq!(metadata.origin, Some(FeedOrigin::LocalFile));
+
+        let cache = options.cache().unwrap();
+        let metadata =
+            FeedMetadata::new(COMPROMISED_PACKAGES_FEED, DEFAULT_FEED_URL, "b:2.0.0\n", 1);
+        cache
+            .store(COMPROMISED_PACKAGES_FEED, "b:2.0.0\n", metadata)
+            .unwrap();
+        let (content, metadata) = load(&packages_spec(None), &options, Some(&local), count)
+            .unwrap()
+            .unwrap();
+        assert_eq!(content, "b:2.0.0\n");
+        assert_eq!(metadata.source, DEFAULT_FEED_URL);
+        assert_eq!(metadata.origin, Some(FeedOrigin::StaleCache));
+
+        // --require-fresh-feed rejects the stale cache, but accepts it within --feed-ttl
+        let strict = FeedOptions {
+            require_fresh: true,
+            ..options.clone()
+        };
+        assert!(strict.check_fresh(&metadata).is_err());
+        let strict_ttl = FeedOptions {
+            ttl: Duration::from_secs(3600),
+            ..strict
+        };
+        let (_, metadata) = load(&packages_spec(None), &strict_ttl, Some(&local), count)
+            .unwrap()
+            .unwrap();
+        assert_eq!(metadata.origin, Some(FeedOrigin::FreshCache));
+        assert!(strict_ttl.check_fresh(&metadata).is_ok());
+
+        assert!(load(
+            &packages_spec(None),
+            &FeedOptions {
+                cache_dir: Some(temp.path().join("none")),
+                ..options
+            },
+            None,
+            count
+        )
+        .unwrap()
+        .is_none());
+    }
+
+    #[test]
+    fn test_load_explicit_url_falls_back_to_same_source_only() {
+        let temp = TempDir::new().unwrap();
+        let options = FeedOptions {
+            cache_dir: Some(temp.path().join("cache")),
+            ..FeedOptions::default()
+        };
+        let count = |c: &str| count_lines(c).unwrap();
+        // Nothing listens on the discard port, so the download fails
+        let url = "http://127.0.0.1:9/compromised-packages.txt";
+        let source = FeedSource::Url(url.to_string());
+        let spec = packages_spec(Some(&source));
+        let local = temp.path().join("compromised-packages.txt");
+        fs::write(&local, "a:1.0.0\n").unwrap();
+
+        // A cached copy of the default feed is not what --feed asked for
+        let cache = options.cache().unwrap();
+        let metadata =
+            FeedMetadata::new(COMPROMISED_PACKAGES_FEED, DEFAULT_FEED_URL, "b:2.0.0\n", 1);
+        cache
+            .store(COMPROMISED_PACKAGES_FEED, "b:2.0.0\n", metadata)
+            .unwrap();
+        assert!(load(&spec, &options, Some(&local), count).is_err());
+
+        let metadata = FeedMetadata::new(COMPROMISED_PACKAGES_FEED, url, "c:3.0.0\n", 1);
+        cache
+            .store(COMPROMISED_PACKAGES_FEED, "c:3.0.0\n", metadata)
+            .unwrap();
+        let (content, metadata) = load(&spec, &options, Some(&local), count).unwrap().unwrap();
+        assert_eq!(content, "c:3.0.0\n");
+        assert_eq!(metadata.origin, Some(FeedOrigin::StaleCache));
+    }
+
+    #[test]
+    fn test_download_without_entries_keeps_cache() {
+        use std::io::{Read, Write};
+        use std::net::TcpListener;
+
+        // Captive portal answering 200 with an HTML page
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!(
+            "http://{}/compromised-packages.txt",
+            listener.local_addr().unwrap()
+        );
+        let server = std::thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = [0u8; 4096];
+            let _ = stream.read(&mut request).unwrap();
+            let body = "<html><body>Please log in</body></html>";
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            )
+            .unwrap();
+        });
+
+        let temp = TempDir::new().unwrap();
+        let options = FeedOptions {
+            cache_dir: Some(temp.path().join("cache")),
+            ..FeedOptions::default()
+        };
+        let cache = options.cache().unwrap();
+        let metadata = FeedMetadata::new(COMPROMISED_PACKAGES_FEED, &url, "a:1.0.0\n", 1);
+        cache
+            .store(COMPROMISED_PACKAGES_FEED, "a:1.0.0\n", metadata)
+            .unwrap();
+
+        let source = FeedSource::Url(url);
+        let count = |c: &str| c.lines().filter(|l| l.contains(':')).count();
+        let (content, metadata) = load(&packages_spec(Some(&source)), &options, None, count)
+            .unwrap()
+            .unwrap();
+        server.join().unwrap();
+
+        assert_eq!(content, "a:1.0.0\n");
+        assert_eq!(metadata.origin, Some(FeedOrigin::StaleCache));
+        assert_eq!(
+            cache.load(COMPROMISED_PACKAGES_FEED).unwrap().0,
+            "a:1.0.0\n"
+        );
+    }
+
+    #[test]
+    fn test_cache_rejects_modified_content() {
+        let temp = TempDir::new().unwrap();
+        let cache = FeedCache::new(temp.path().to_path_buf());
+        let content = "@ctrl/tinycolor:4.1.1\n";
+        let metadata = FeedMetadata::new("feed.txt", DEFAULT_FEED_URL, content, 1);
+        cache.store("feed.txt", content, metadata).unwrap();
+        assert!(cache.load("feed.txt").is_some());
+
+        fs::write(cache.data_path("feed.txt"), "tampered:1.0.0\n").unwrap();
+        assert!(cache.load("feed.txt").is_none());
+    }
+
+    #[test]
+    fn test_ttl_and_freshness() {
+        assert_eq!(parse_ttl("0").unwrap(), Duration::ZERO);
+        assert_eq!(parse_ttl("900").unwrap(), Duration::from_secs(900));
+        assert_eq!(parse_ttl("30m").unwrap(), Duration::from_secs(1800));
+        assert_eq!(parse_ttl("6h").unwrap(), Duration::from_secs(21600));
+        assert_eq!(parse_ttl("1d").unwrap(), Duration::from_secs(86400));
+        assert!(parse_ttl("soon").is_err());
+        assert!(parse_ttl("5w").is_err());
+        assert!(parse_ttl("18446744073709551615d").is_err());
+
+        let mut metadata = FeedMetadata::new("feed.txt", DEFAULT_FEED_URL, "", 0);
+        assert!(!metadata.is_fresh(Duration::ZERO));
+        assert!(metadata.is_fresh(Duration::from_secs(3600)));
+
+        metadata.fetched_at = "2020-01-01T00:00:00Z".to_string();
+        assert!(!metadata.is_fresh(Duration::from_secs(3600)));
+    }
+}
@@ -0,0 +1,515 @@
+// File Index - Single-pass shared file index for all detectors
+// Purpose: Walk the scan directory once (in parallel) and let every detector query
+//          the resulting index instead of starting its own WalkDir traversal.
+//
+// Entries keep the exact order a sequential WalkDir would produce, so detectors
+// report findings in the same order as before. File contents are read lazily and
+// cached, so each file is read from disk at most once per scan - up to a per-file and
+// total size budget. Files above it are read again by every detector that asks, so
+// huge node_modules trees don't stay resident for the whole scan.
+//
+// Yarn Berry keeps installed packages as zips in .yarn/cache (Plug'n'Play, no
+// node_modules). Their members are indexed right after the archive as virtual files
+// ("<archive>.zip/node_modules/pkg/index.js") so content detectors see inside them.
+
+use rayon::prelude::*;
+use std::fs;
+use std::io::Read;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, OnceLock};
+use walkdir::{DirEntry, WalkDir};
+
+/// Type of an indexed entry (symlinks are not followed, matching bash `find -type f/d`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryKind {
+    File,
+    Dir,
+    Symlink,
+}
+
+/// A single file system entry discovered during the walk
+#[derive(Debug, Clone)]
+pub struct IndexEntry {
+    id: usize,
+    path: PathBuf,
+    extension: Option<String>,
+    kind: EntryKind,
+    /// (archive, member name) for files inside a .yarn/cache zip
+    archive_member: Option<(PathBuf, String)>,
+}
+
+impl IndexEntry {
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn is_file(&self) -> bool {
+        self.kind == EntryKind::File
+    }
+
+    pub fn is_dir(&self) -> bool {
+        self.kind == EntryKind::Dir
+    }
+
+    /// Zip archive containing this file (None for files on disk)
+    pub fn archive(&self) -> Option<&Path> {
+        self.archive_member
+            .as_ref()
+            .map(|(archive, _)| archive.as_path())
+    }
+
+    /// File name component (empty for the scan root on some platforms)
+    pub fn file_name(&self) -> &str {
+        self.path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .unwrap_or_default()
+    }
+
+    /// Check extension against a list (case-sensitive, like the original WalkDir filters)
+    pub fn has_extension(&self, extensions: &[&str]) -> bool {
+        self.extension
+            .as_deref()
+            .is_some_and(|ext| extensions.contains(&ext))
+    }
+}
+
+/// Cached file content - text when valid UTF-8, raw bytes otherwise
+#[derive(Debug)]
+pub enum FileContent {
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+impl FileContent {
+    pub fn bytes(&self) -> &[u8] {
+        match self {
+            FileContent::Text(text) => text.as_bytes(),
+            FileContent::Binary(bytes) => bytes,
+        }
+    }
+
+    /// UTF-8 content, or None for binary files (same as `fs::read_to_string` failing)
+    pub fn text(&self) -> Option<&str> {
+        match self {
+            FileContent::Text(text) => Some(text),
+            FileContent::Binary(_) => None,
+        }
+    }
+}
+
+/// UTF-8 content of a file, shared with the cache when it was cached
+#[derive(Debug, Clone)]
+pub struct TextContent(Arc<FileContent>);
+
+impl Deref for TextContent {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        self.0.text().unwrap_or_default()
+    }
+}
+
+/// Files larger than this are never cached
+pub const MAX_CACHED_FILE_SIZE: u64 = 16 * 1024 * 1024;
+
+/// Default total size of cached file contents
+pub const DEFAULT_CACHE_LIMIT: u64 = 512 * 1024 * 1024;
+
+/// Index of every entry below the scan directory
+pub struct FileIndex {
+    root: PathBuf,
+    entries: Vec<IndexEntry>,
+    contents: Vec<OnceLock<Option<Arc<FileContent>>>>,
+    /// Bytes currently held in `contents`
+    cached_bytes: AtomicU64,
+    cache_limit: u64,
+}
+
+impl FileIndex {
+    // Function: build
+    // Purpose: Walk the scan directory once and record path, extension and type
+    // Args: root - directory to scan, parallelism - number of walker threads
+    // Returns: FileIndex with entries in sequential WalkDir order
+    pub fn build<P: AsRef<Path>>(root: P, parallelism: usize) -> Self {
+        let root = root.as_ref().to_path_buf();
+
+        // Top level is listed sequentially; each top-level directory is walked in parallel.
+        // Concatenating the per-directory results in listing order reproduces the
+        // depth-first order of a single sequential WalkDir.
+        let top_level: Vec<DirEntry> = WalkDir::new(&root)
+            .max_depth(1)
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .collect();
+
+        let walk = |entry: &DirEntry| -> Vec<DirEntry> {
+            let mut subtree = vec![entry.clone()];
+            if entry.depth() == 1 && entry.file_type().is_dir() {
+                subtree.extend(
+                    WalkDir::new(entry.path())
+                        .min_depth(1)
+                        .into_iter()
+                        .filter_map(std::result::Result::ok),
+                );
+            }
+            subtree
+        };
+
+        let walked: Vec<Vec<DirEntry>> = match rayon::ThreadPoolBuilder::new()
+            .num_threads(parallelism.max(1))
+            .build()
+        {
+            Ok(pool) => pool.install(|| top_level.par_iter().map(walk).collect()),
+            Err(_) => top_level.iter().map(walk).collect(),
+        };
+
+        let mut entries: Vec<IndexEntry> = Vec::new();
+        for entry in walked.into_iter().flatten() {
+            let file_type = entry.file_type();
+            let kind = if file_type.is_dir() {
+                EntryKind::Dir
+            } else if file_type.is_file() {
+                EntryKind::File
+            } else {
+                EntryKind::Symlink
+            };
+            let path = entry.into_path();
+            let is_yarn_cache_archive = kind == EntryKind::File && is_yarn_cache_archive(&path);
+
+            entries.push(IndexEntry {
+                id: entries.len(),
+                extension: extension_of(&path),
+                path: path.clone(),
+                kind,
+                archive_member: None,
+            });
+            if is_yarn_cache_archive {
+                for mut member in archive_members(&path) {
+                    member.id = entries.len();
+                    entries.push(member);
+                }
+            }
+        }
+
+        let contents = entries.iter().map(|_| OnceLock::new()).collect();
+
+        Self {
+            root,
+            entries,
+            contents,
+            cached_bytes: AtomicU64::new(0),
+            cache_limit: DEFAULT_CACHE_LIMIT,
+        }
+    }
+
+    /// Cap the total size of cached file contents (files beyond it are read uncached)
+    #[cfg(test)]
+    #[must_use]
+    pub fn with_cache_limit(mut self, bytes: u64) -> Self {
+        self.cache_limit = bytes;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// All entries in traversal order
+    #[cfg(test)]
+    pub fn entries(&self) -> &[IndexEntry] {
+        &self.entries
+    }
+
+    /// Regular files in traversal order
+    pub fn files(&self) -> impl Iterator<Item = &IndexEntry> {
+        self.entries.iter().filter(|e| e.is_file())
+    }
+
+    /// Directories in traversal order
+    pub fn dirs(&self) -> impl Iterator<Item = &IndexEntry> {
+        self.entries.iter().filter(|e| e.is_dir())
+    }
+
+    /// Regular files whose extension is in `extensions`
+    pub fn files_with_extensions<'a>(
+        &'a self,
+        extensions: &'a [&'a str],
+    ) -> impl Iterator<Item = &'a IndexEntry> {
+        self.files().filter(move |e| e.has_extension(extensions))
+    }
+
+    /// Regular files with an exact file name
+    pub fn files_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a IndexEntry> {
+        self.files().filter(move |e| e.file_name() == name)
+    }
+
+    /// Directories with an exact name
+    pub fn dirs_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a IndexEntry> {
+        self.dirs().filter(move |e| e.file_name() == name)
+    }
+
+    // Function: read
+    // Purpose: Read file content and share it between all detectors while it fits the
+    //          cache budget (MAX_CACHED_FILE_SIZE per file, cache limit in total)
+    // Args: entry - indexed file
+    // Returns: Content, or None if the file could not be read
+    pub fn read(&self, entry: &IndexEntry) -> Option<Arc<FileContent>> {
+        let slot = &self.contents[entry.id];
+        if let Some(cached) = slot.get() {
+            return cached.clone();
+        }
+
+        let bytes = match &entry.archive_member {
+            Some((archive, name)) => read_archive_member(archive, name),
+            None => fs::read(&entry.path).ok(),
+        };
+        let content = bytes.map(|bytes| {
+            Arc::new(match String::from_utf8(bytes) {
+                Ok(text) => FileContent::Text(text),
+                Err(e) => FileContent::Binary(e.into_bytes()),
+            })
+        });
+
+        // Another thread may have cached the file meanwhile - give the reservation back
+        let size = content.as_ref().map_or(0, |c| c.bytes().len() as u64);
+        if size <= MAX_CACHED_FILE_SIZE && self.reserve(size) && slot.set(content.clone()).is_err()
+        {
+            self.cached_bytes.fetch_sub(size, Ordering::Relaxed);
+        }
+        content
+    }
+
+    /// UTF-8 content (None for unreadable or binary files, like `fs::read_to_string`)
+    pub fn read_text(&self, entry: &IndexEntry) -> Option<TextContent> {
+        self.read(entry)
+            .filter(|content| content.text().is_some())
+            .map(TextContent)
+    }
+
+    // Helper: Claim `size` bytes of the cache budget
+    fn reserve(&self, size: u64) -> bool {
+        self.cached_bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |cached| {
+                Some(cached + size).filter(|total| *total <= self.cache_limit)
+            })
+            .is_ok()
+    }
+}
+
+// Helper: File extension as owned string
+fn extension_of(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .map(std::string::ToString::to_string)
+}
+
+// Helper: Zip archive directly inside a .yarn/cache directory
+fn is_yarn_cache_archive(path: &Path) -> bool {
+    let in_yarn_cache = path.parent().is_some_and(|cache| {
+        cache.file_name().is_some_and(|name| name == "cache")
+            && cache
+                .parent()
+                .and_then(Path::file_name)
+                .is_some_and(|name| name == ".yarn")
+    });
+    in_yarn_cache && path.extension().is_some_and(|ext| ext == "zip")
+}
+
+// Function: archive_members
+// Purpose: List the files inside a zip archive as virtual index entries
+// Args: archive - path of the zip on disk
+// Returns: One entry per file member (unreadable archives yield none)
+fn archive_members(archive: &Path) -> Vec<IndexEntry> {
+    let Some(mut zip) = fs::File::open(archive)
+        .ok()
+        .and_then(|file| zip::ZipArchive::new(file).ok())
+    else {
+        return Vec::new();
+    };
+
+    let mut members = Vec::new();
+    for i in 0..zip.len() {
+        let Ok(file) = zip.by_index_raw(i) else {
+            continue;
+        };
+        // Skip directories and names escaping the archive ("../x")
+        if file.is_dir() || file.enclosed_name().is_none() {
+            continue;
+        }
+        let name = file.name().to_string();
+        let path = archive.join(&name);
+        members.push(IndexEntry {
+            id: 0,
+            extension: extension_of(&path),
+            path,
+            kind: EntryKind::File,
+            archive_member: Some((archive.to_path_buf(), name)),
+        });
+    }
+    members
+}
+
+// Function: read_archive_member
+// Purpose: Decompress one zip member, at most MAX_CACHED_FILE_SIZE bytes
+// Args: archive - zip file, name - member name
+// Returns: Member content, None if unreadable or larger than the limit
+// NOTE: The size in the zip header is not trusted - a crafted archive (zip bomb)
+//       would otherwise be expanded into memory
+pub fn read_archive_member(archive: &Path, name: &str) -> Option<Vec<u8>> {
+    let file = fs::File::open(archive).ok()?;
+    let mut zip = zip::ZipArchive::new(file).ok()?;
+    let member = zip.by_name(name).ok()?;
+    let mut bytes = Vec::new();
+    member
+        .take(MAX_CACHED_FILE_SIZE + 1)
+        .read_to_end(&mut bytes)
+        .ok()?;
+    (bytes.len() as u64 <= MAX_CACHED_FILE_SIZE).then_some(bytes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_index_matches_sequential_walk() {
+        let temp = TempDir::new().unwrap();
+        fs::create_dir_all(temp.path().join("a/b")).unwrap();
+        fs::create_dir_all(temp.path().join("c")).unwrap();
+        fs::write(temp.path().join("a/b/one.js"), "one").unwrap();
+        fs::write(temp.path().join("c/two.json"), "{}").unwrap();
+        fs::write(temp.path().join("three.ts"), "three").unwrap();
+
+        let index = FileIndex::build(temp.path(), 4);
+        let sequential: Vec<PathBuf> = WalkDir::new(temp.path())
+            .into_iter()
+            .filter_map(std::result::Result::ok)
+            .map(walkdir::DirEntry::into_path)
+            .collect();
+        let indexed: Vec<PathBuf> = index.entries().iter().map(|e| e.path.clone()).collect();
+
+        assert_eq!(indexed, sequential);
+        assert_eq!(index.files_with_extensions(&["js", "ts"]).count(), 2);
+        assert_eq!(index.files_named("two.json").count(), 1);
+        assert_eq!(index.dirs_named("b").count(), 1);
+    }
+
+    #[test]
+    fn test_content_read_once_and_shared() {
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("file.js");
+        fs::write(&path, "original").unwrap();
+
+        let index = FileIndex::build(temp.path(), 1);
+        let entry = index.files_named("file.js").next().unwrap();
+        let first = index.read(entry).unwrap();
+
+        // Changing the file on disk does not change the cached content
+        fs::write(&path, "modified").unwrap();
+        let second = index.read(entry).unwrap();
+
+        assert!(Arc::ptr_eq(&first, &second));
+        assert_eq!(second.text(), Some("original"));
+    }
+
+    #[test]
+    fn test_content_beyond_cache_limit_is_reread() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("a.js"), "aaaaaa").unwrap();
+        fs::write(temp.path().join("b.js"), "bbbbbb").unwrap();
+
+        let index = FileIndex::build(temp.path(), 1).with_cache_limit(10);
+        let a = index.files_named("a.js").next().unwrap();
+        let b = index.files_named("b.js").next().unwrap();
+        assert_eq!(index.read_text(a).as_deref(), Some("aaaaaa"));
+        assert_eq!(index.read_text(b).as_deref(), Some("bbbbbb"));
+
+        // a.js filled the budget, so b.js is read from disk again
+        fs::write(temp.path().join("a.js"), "AAAAAA").unwrap();
+        fs::write(temp.path().join("b.js"), "BBBBBB").unwrap();
+        assert_eq!(index.read_text(a).as_deref(), Some("aaaaaa"));
+        assert_eq!(index.read_text(b).as_deref(), Some("BBBBBB"));
+    }
+
+    #[test]
+    fn test_binary_content_has_no_text() {
+        let temp = TempDir::new().unwrap();
+        fs::write(temp.path().join("blob.js"), [0xff, 0xfe, 0x00]).unwrap();
+
+        let index = FileIndex::build(temp.path(), 1);
+        let entry = index.files().next().unwrap();
+        let content = index.read(entry).unwrap();
+
+        assert!(content.text().is_none());
+        assert_eq!(content.bytes(), &[0xff, 0xfe, 0x00]);
+    }
+
+    #[test]
+    fn test_yarn_cache_archive_members_are_indexed() {
+        use std::io::Write;
+
+        let temp = TempDir::new().unwrap();
+        let cache = temp.path().join(".yarn/cache");
+        fs::create_dir_all(&cache).unwrap();
+        let archive = cache.join("evil-npm-1.0.0-abc123.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        zip.add_directory("node_modules/evil/", options).unwrap();
+        zip.start_file("node_modules/evil/index.js", options)
+            .unwrap();
+        zip.write_all(b"fetch('https://webhook.site/x')").unwrap();
+        zip.finish().unwrap();
+        // Zips elsewhere are left alone
+        fs::copy(&archive, temp.path().join("other.zip")).unwrap();
+
+        let index = FileIndex::build(temp.path(), 1);
+        let members: Vec<_> = index.files().filter(|e| e.archive().is_some()).collect();
+        assert_eq!(members.len(), 1);
+        assert_eq!(
+            members[0].path(),
+            archive.join("node_modules/evil/index.js")
+        );
+        assert!(members[0].has_extension(&["js"]));
+        assert_eq!(
+            index.read_text(members[0]).as_deref(),
+            Some("fetch('https://webhook.site/x')")
+        );
+        // Member directly follows its archive
+        let position = |path: &Path| index.entries().iter().position(|e| e.path() == path);
+        assert_eq!(
+            position(members[0].path()),
+            position(&archive).map(|p| p + 1)
+        );
+    }
+
+    #[test]
+    fn test_oversized_archive_member_is_not_read() {
+        use std::io::Write;
+
+        let temp = TempDir::new().unwrap();
+        let archive = temp.path().join("bomb.zip");
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        let options = zip::write::SimpleFileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated);
+        zip.start_file("small.js", options).unwrap();
+        zip.write_all(b"ok").unwrap();
+        // Compresses to a few KB, expands beyond the limit
+        zip.start_file("huge.js", options).unwrap();
+        let zeros = vec![0u8; 1024 * 1024];
+        for _ in 0..=MAX_CACHED_FILE_SIZE / zeros.len() as u64 {
+            zip.write_all(&zeros).unwrap();
+        }
+        zip.finish().unwrap();
+
+        assert_eq!(
+            read_archive_member(&archive, "small.js"),
+            Some(b"ok".to_vec())
+        );
+        assert_eq!(read_archive_member(&archive, "huge.js"), None);
+    }
+}
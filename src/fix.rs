@@ -0,0 +1,534 @@
+// Remediation - override blocks that keep compromised versions out of every install
+// Purpose: `fix --dry-run` / `fix --apply`: for each project with a compromised package
+//          (declared in package.json or locked anywhere in its lockfile) write the
+//          package manager's override block into the project's root package.json,
+//          pinned to the nearest safe version (Rust-only, no Bash equivalent)
+//
+//   npm / bun:  "overrides":   { "@ctrl/tinycolor": "4.0.0" }
+//   yarn:       "resolutions": { "@ctrl/tinycolor": "4.0.0" }
+//   pnpm:       "pnpm": { "overrides": { "@ctrl/tinycolor": "4.0.0" } }
+//
+// The nearest safe version is a version of the package that the lockfile already contains
+// (so it is known to be published) and that neither the compromised list nor an OSV
+// advisory covers, closest above the compromised one. If only older safe versions are
+// locked the pin is a downgrade and reported as such; without any, the package is skipped
+// with a warning instead of guessing a version. npm rejects an override that differs from a direct dependency's specifier, so for
+// npm/bun the direct dependency is rewritten to the same version.
+
+use crate::data::CompromisedPackage;
+use crate::detectors::exposure::OverrideField;
+use crate::detectors::projects::{ProjectIndex, ProjectResolvers};
+use crate::detectors::ScanResults;
+use crate::file_index::FileIndex;
+use crate::osv::Advisories;
+use crate::semver::Version;
+use anyhow::{Context, Result};
+use serde_json::{Map, Value};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Dependency sections whose specifiers npm checks against overrides
+const DEPENDENCY_SECTIONS: [&str; 4] = [
+    "dependencies",
+    "devDependencies",
+    "peerDependencies",
+    "optionalDependencies",
+];
+
+/// Lines of unchanged context around each change in a diff
+const DIFF_CONTEXT: usize = 3;
+
+/// Planned change of one package.json
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FixPlan {
+    pub package_json: PathBuf,
+    pub field: OverrideField,
+    /// Package name -> pinned safe version
+    pub overrides: BTreeMap<String, String>,
+    pub original: String,
+    pub updated: String,
+}
+
+impl FixPlan {
+    // Function: diff
+    // Purpose: Unified diff of the planned change
+    // Args: self
+    // Returns: "--- a\n+++ b\n@@ ... @@" hunks with DIFF_CONTEXT lines of context
+    pub fn diff(&self) -> String {
+        let path = crate::utils::normalize_path(&self.package_json);
+        let mut diff = format!("--- {path}\n+++ {path}\n");
+        diff.push_str(&unified_diff(&self.original, &self.updated));
+        diff
+    }
+
+    // Function: apply
+    // Purpose: Write the updated package.json
+    // Args: self
+    // Returns: Ok once written
+    pub fn apply(&self) -> Result<()> {
+        fs::write(&self.package_json, &self.updated)
+            .with_context(|| format!("Failed to write {}", self.package_json.display()))
+    }
+}
+
+/// Changes planned by `plan_fixes()`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FixPlans {
+    /// One plan per root package.json that changes, sorted by path
+    pub plans: Vec<FixPlan>,
+    /// Packages left unpinned or pinned to an older version, for manual review
+    pub warnings: Vec<String>,
+}
+
+/// Version a compromised package is pinned to
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SafeVersion {
+    /// Closest safe version above every compromised one
+    Upgrade(String),
+    /// Newest safe version, but older than the compromised ones
+    Downgrade(String),
+}
+
+// Function: plan_fixes
+// Purpose: Work out the override block every affected project needs
+// Args: scan_dir - project directory, results - scan results (compromised_found),
+//       compromised_packages - known bad versions, advisories - OSV version ranges,
+//       parallelism - file index threads
+// Returns: FixPlans with the package.json changes and warnings for skipped packages
+pub fn plan_fixes(
+    scan_dir: &Path,
+    results: &ScanResults,
+    compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
+    parallelism: usize,
+) -> Result<FixPlans> {
+    let index = FileIndex::build(scan_dir, parallelism);
+    let projects = ProjectIndex::build(&index);
+    let roots: Vec<PathBuf> = projects
+        .roots()
+        .into_iter()
+        .map(Path::to_path_buf)
+        .collect();
+    let mut resolvers = ProjectResolvers::new(projects, scan_dir);
+
+    // Compromised versions per project root: declared ones (compromised_found) ...
+    let mut affected: BTreeMap<PathBuf, BTreeMap<String, BTreeSet<String>>> = BTreeMap::new();
+    for finding in &results.compromised_found {
+        let Some((name, version)) = finding_package(&finding.message) else {
+            continue;
+        };
+        let root = resolvers.resolution_root(&finding.file_path);
+        affected
+            .entry(root)
+            .or_default()
+            .entry(name)
+            .or_default()
+            .insert(version);
+    }
+    // ... and every locked copy, nested ones included
+    for root in &roots {
+        let Some(lockfile) = resolvers.lockfile_for(&root.join("package.json")) else {
+            continue;
+        };
+        for instance in lockfile.packages.iter() {
+            let key = CompromisedPackage::new(instance.name.clone(), instance.version.clone());
+            if compromised_packages.contains(&key) {
+                affected
+                    .entry(root.clone())
+                    .or_default()
+                    .entry(key.name)
+                    .or_default()
+                    .insert(key.version);
+            }
+        }
+    }
+
+    // Versions flagged by OSV advisories only count as compromised too
+    let mut known_bad = compromised_packages.clone();
+    for (name, version) in affected
+        .values()
+        .flatten()
+        .flat_map(|(name, versions)| versions.iter().map(move |v| (name, v)))
+    {
+        known_bad.insert(CompromisedPackage::new(name.clone(), version.clone()));
+    }
+
+    let mut fixes = FixPlans::default();
+    for (root, packages) in affected {
+        let package_json = root.join("package.json");
+        let Ok(original) = fs::read_to_string(&package_json) else {
+            continue;
+        };
+        let lockfile = resolvers.lockfile_for(&package_json);
+        let field = OverrideField::for_lockfile(lockfile.and_then(|lr| lr.lockfile_type));
+
+        let mut overrides = BTreeMap::new();
+        for (name, bad_versions) in &packages {
+            let locked: Vec<String> = lockfile
+                .map(|lr| {
+                    lr.instances(name)
+                        .iter()
+                        .map(|i| i.version.clone())
+                        .collect()
+                })
+                .unwrap_or_default();
+            let location = crate::utils::normalize_path(&package_json);
+            match nearest_safe_version(name, bad_versions, &locked, &known_bad, advisories) {
+                Some(SafeVersion::Upgrade(version)) => {
+                    overrides.insert(name.clone(), version);
+                }
+                Some(SafeVersion::Downgrade(version)) => {
+                    fixes.warnings.push(format!(
+                        "{name} in {location}: no safe version above the compromised one is locked - pinned to older {version}"
+                    ));
+                    overrides.insert(name.clone(), version);
+                }
+                None => fixes.warnings.push(format!(
+                    "{name} in {location}: no safe version in the lockfile - skipped, pin it manually"
+                )),
+            }
+        }
+
+        let updated = add_overrides(&original, field, &overrides)
+            .with_context(|| format!("Failed to update {}", package_json.display()))?;
+        if updated != original {
+            fixes.plans.push(FixPlan {
+                package_json,
+                field,
+                overrides,
+                original,
+                updated,
+            });
+        }
+    }
+    Ok(fixes)
+}
+
+// Helper: "name@version" from a compromised_found message ("@scope/pkg@1.0.0 [GHSA-...]")
+fn finding_package(message: &str) -> Option<(String, String)> {
+    let spec = message.split_whitespace().next()?;
+    let at = spec.get(1..)?.rfind('@')? + 1;
+    Some((spec[..at].to_string(), spec[at + 1..].to_string()))
+}
+
+// Function: nearest_safe_version
+// Purpose: Exact version to pin a compromised package to
+// Args: name - package, bad_versions - compromised versions found, locked - versions in
+//       the project lockfile, compromised_packages - every known bad version,
+//       advisories - OSV ranges (versions inside them are not safe either)
+// Returns: Closest locked safe version above the highest bad one, otherwise the newest
+//          older one as a downgrade; None if no safe version is locked
+pub fn nearest_safe_version(
+    name: &str,
+    bad_versions: &BTreeSet<String>,
+    locked: &[String],
+    compromised_packages: &HashSet<CompromisedPackage>,
+    advisories: &Advisories,
+) -> Option<SafeVersion> {
+    let highest_bad = bad_versions
+        .iter()
+        .filter_map(|v| Version::parse(v))
+        .max()?;
+    let mut safe: Vec<Version> = locked
+        .iter()
+        .filter(|v| {
+            !compromised_packages.contains(&CompromisedPackage::new(name.to_string(), (*v).clone()))
+                && advisories.references(name, v).is_none()
+        })
+        .filter_map(|v| Version::parse(v))
+        .filter(|v| !v.is_prerelease())
+        .collect();
+    safe.sort();
+    match safe.iter().find(|v| **v > highest_bad) {
+        Some(version) => Some(SafeVersion::Upgrade(version.to_string())),
+        None => safe
+            .last()
+            .map(|version| SafeVersion::Downgrade(version.to_string())),
+    }
+}
+
+// Function: add_overrides
+// Purpose: Insert override entries into package.json text, keeping key order and indentation
+// Args: content - package.json text, field - overrides field, overrides - name -> version
+// Returns: Updated package.json text (unchanged if every entry is already present)
+pub fn add_overrides(
+    content: &str,
+    field: OverrideField,
+    overrides: &BTreeMap<String, String>,
+) -> Result<String> {
+    let mut json: Value = serde_json::from_str(content)?;
+    let root = json
+        .as_object_mut()
+        .context("package.json is not a JSON object")?;
+
+    let mut changed = false;
+    let mut block = root;
+    for key in field.path() {
+        let entry = block
+            .entry(*key)
+            .or_insert_with(|| Value::Object(Map::new()));
+        if !entry.is_object() {
+            anyhow::bail!("\"{key}\" is not an object");
+        }
+        block = entry.as_object_mut().expect("checked above");
+    }
+    for (name, version) in overrides {
+        if block.get(name).and_then(Value::as_str) != Some(version) {
+            block.insert(name.clone(), Value::String(version.clone()));
+            changed = true;
+        }
+    }
+
+    if field == OverrideField::Overrides {
+        for section in DEPENDENCY_SECTIONS {
+            let Some(deps) = json.get_mut(section).and_then(Value::as_object_mut) else {
+                continue;
+            };
+            for (name, version) in overrides {
+                if let Some(spec) = deps
+                    .get_mut(name)
+                    .filter(|spec| spec.as_str() != Some(version))
+                {
+                    *spec = Value::String(version.clone());
+                    changed = true;
+                }
+            }
+        }
+    }
+
+    if !changed {
+        return Ok(content.to_string());
+    }
+    let mut updated = to_json_with_indent(&json, &detect_indent(content))?;
+    if content.ends_with('\n') {
+        updated.push('\n');
+    }
+    Ok(updated)
+}
+
+// Helper: Indentation of the first indented line (two spaces if none)
+fn detect_indent(content: &str) -> String {
+    content
+        .lines()
+        .map(|line| &line[..line.len() - line.trim_start().len()])
+        .find(|indent| !indent.is_empty())
+        .unwrap_or("  ")
+        .to_string()
+}
+
+// Helper: Pretty JSON with a custom indent
+fn to_json_with_indent(json: &Value, indent: &str) -> Result<String> {
+    let mut output = Vec::new();
+    let formatter = serde_json::ser::PrettyFormatter::with_indent(indent.as_bytes());
+    let mut serializer = serde_json::Serializer::with_formatter(&mut output, formatter);
+    serde::Serialize::serialize(json, &mut serializer)?;
+    Ok(String::from_utf8(output)?)
+}
+
+// Helper: Line diff (longest common subsequence) as unified diff hunks
+fn unified_diff(old: &str, new: &str) -> String {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = common lines of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // (tag, line, old line number, new line number)
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((' ', old[i], i, j));
+            i += 1;
+            j += 1;
+        } else if i < old.len() && (j == new.len() || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push(('-', old[i], i, j));
+            i += 1;
+        } else {
+            ops.push(('+', new[j], i, j));
+            j += 1;
+        }
+    }
+
+    let mut diff = String::new();
+    let changes: Vec<usize> = (0..ops.len()).filter(|&k| ops[k].0 != ' ').collect();
+    let mut k = 0;
+    while k < changes.len() {
+        // Extend the hunk while the next change is within the shared context
+        let start = changes[k].saturating_sub(DIFF_CONTEXT);
+        let mut end = changes[k];
+        while k + 1 < changes.len() && changes[k + 1] <= end + 2 * DIFF_CONTEXT + 1 {
+            k += 1;
+            end = changes[k];
+        }
+        let end = (end + DIFF_CONTEXT + 1).min(ops.len());
+        let hunk = &ops[start..end];
+        let old_count = hunk.iter().filter(|op| op.0 != '+').count();
+        let new_count = hunk.iter().filter(|op| op.0 != '-').count();
+        diff.push_str(&format!(
+            "@@ -{},{old_count} +{},{new_count} @@\n",
+            hunk[0].2 + 1,
+            hunk[0].3 + 1
+        ));
+        for (tag, line, _, _) in hunk {
+            diff.push_str(&format!("{tag}{line}\n"));
+        }
+        k += 1;
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::{Finding, RiskLevel};
+    use tempfile::TempDir;
+
+    fn none() -> Advisories {
+        Advisories::default()
+    }
+
+    fn compromised() -> HashSet<CompromisedPackage> {
+        ["@ctrl/tinycolor:4.1.1", "@ctrl/tinycolor:4.1.2"]
+            .iter()
+            .filter_map(|line| CompromisedPackage::from_line(line))
+            .collect()
+    }
+
+    #[test]
+    fn test_nearest_safe_version() {
+        let bad: BTreeSet<String> = ["4.1.1".to_string()].into_iter().collect();
+        let locked = |versions: &[&str]| versions.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+        let nearest = |locked: &[String], advisories: &Advisories| {
+            nearest_safe_version("@ctrl/tinycolor", &bad, locked, &compromised(), advisories)
+        };
+        let none = Advisories::default();
+        assert_eq!(
+            nearest(&locked(&["4.1.1", "3.6.0", "4.2.0"]), &none),
+            Some(SafeVersion::Upgrade("4.2.0".to_string()))
+        );
+        assert_eq!(
+            nearest(&locked(&["4.1.1", "3.6.0"]), &none),
+            Some(SafeVersion::Downgrade("3.6.0".to_string()))
+        );
+        // Nothing safe locked: no guessed version
+        assert_eq!(nearest(&locked(&["4.1.1"]), &none), None);
+
+        // Versions inside an advisory range are not safe either
+        let mut advisories = Advisories::default();
+        advisories.extend(
+            Advisories::parse(
+                r#"{"id": "MAL-2025-0001", "affected": [{"package": {"ecosystem": "npm", "name": "@ctrl/tinycolor"},
+                    "ranges": [{"type": "SEMVER", "events": [{"introduced": "4.1.0"}, {"fixed": "4.2.0"}]}]}]}"#,
+            )
+            .unwrap(),
+        );
+        assert_eq!(
+            nearest(&locked(&["4.1.1", "4.1.5", "4.2.0"]), &advisories),
+            Some(SafeVersion::Upgrade("4.2.0".to_string()))
+        );
+    }
+
+    #[test]
+    fn test_add_overrides_keeps_layout() {
+        let content = "{\n    \"name\": \"app\",\n    \"dependencies\": {\n        \"@ctrl/tinycolor\": \"4.1.1\"\n    }\n}\n";
+        let overrides: BTreeMap<_, _> = [("@ctrl/tinycolor".to_string(), "4.1.0".to_string())]
+            .into_iter()
+            .collect();
+
+        let npm = add_overrides(content, OverrideField::Overrides, &overrides).unwrap();
+        assert_eq!(
+            npm,
+            "{\n    \"name\": \"app\",\n    \"dependencies\": {\n        \"@ctrl/tinycolor\": \"4.1.0\"\n    },\n    \"overrides\": {\n        \"@ctrl/tinycolor\": \"4.1.0\"\n    }\n}\n"
+        );
+        assert_eq!(
+            add_overrides(&npm, OverrideField::Overrides, &overrides).unwrap(),
+            npm
+        );
+
+        let pnpm = add_overrides(content, OverrideField::PnpmOverrides, &overrides).unwrap();
+        let json: Value = serde_json::from_str(&pnpm).unwrap();
+        assert_eq!(json["pnpm"]["overrides"]["@ctrl/tinycolor"], "4.1.0");
+        assert_eq!(json["dependencies"]["@ctrl/tinycolor"], "4.1.1");
+    }
+
+    #[test]
+    fn test_plan_fixes_for_yarn_project() {
+        let temp = TempDir::new().unwrap();
+        let root = temp.path();
+        let package_json = root.join("package.json");
+        fs::write(
+            &package_json,
+            "{\n  \"name\": \"app\",\n  \"dependencies\": {\n    \"lib\": \"^1.0.0\"\n  }\n}\n",
+        )
+        .unwrap();
+        // Compromised copy pulled in transitively, a safe copy elsewhere in the tree
+        fs::write(
+            root.join("yarn.lock"),
+            "\"@ctrl/tinycolor@^4.0.0\":\n  version \"4.1.1\"\n\n\"@ctrl/tinycolor@4.0.0\":\n  version \"4.0.0\"\n\nlib@^1.0.0:\n  version \"1.0.0\"\n",
+        )
+        .unwrap();
+
+        let fixes = plan_fixes(root, &ScanResults::new(), &compromised(), &none(), 1).unwrap();
+        // 4.0.0 is older than the compromised copy - pinned, but reported
+        assert_eq!(fixes.warnings.len(), 1);
+        assert!(fixes.warnings[0].ends_with("pinned to older 4.0.0"));
+        let plans = fixes.plans;
+        assert_eq!(plans.len(), 1);
+        let plan = &plans[0];
+        assert_eq!(plan.field, OverrideField::Resolutions);
+        assert_eq!(plan.overrides["@ctrl/tinycolor"], "4.0.0");
+        assert!(plan.diff().ends_with(
+            "@@ -2,5 +2,8 @@\n   \"name\": \"app\",\n   \"dependencies\": {\n     \"lib\": \"^1.0.0\"\n+  },\n+  \"resolutions\": {\n+    \"@ctrl/tinycolor\": \"4.0.0\"\n   }\n }\n"
+        ));
+
+        // Nothing is written until apply()
+        assert!(!fs::read_to_string(&package_json)
+            .unwrap()
+            .contains("resolutions"));
+        plan.apply().unwrap();
+        assert!(fs::read_to_string(&package_json)
+            .unwrap()
+            .contains("resolutions"));
+        assert!(
+            plan_fixes(root, &ScanResults::new(), &compromised(), &none(), 1)
+                .unwrap()
+                .plans
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_plan_fixes_from_compromised_found() {
+        let temp = TempDir::new().unwrap();
+        let package_json = temp.path().join("package.json");
+        fs::write(
+            &package_json,
+            r#"{"dependencies": {"@ctrl/tinycolor": "4.1.2"}}"#,
+        )
+        .unwrap();
+        let mut results = ScanResults::new();
+        results.compromised_found.push(Finding::new(
+            package_json,
+            "@ctrl/tinycolor@4.1.2".to_string(),
+            RiskLevel::High,
+            "compromised_package",
+        ));
+
+        // No lockfile, so no version known to be published and safe: warn, write nothing
+        let fixes = plan_fixes(temp.path(), &results, &compromised(), &none(), 1).unwrap();
+        assert!(fixes.plans.is_empty());
+        assert_eq!(fixes.warnings.len(), 1);
+        assert!(fixes.warnings[0].starts_with("@ctrl/tinycolor in "));
+    }
+}
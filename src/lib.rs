@@ -0,0 +1,30 @@
+// Shai-Hulud NPM Supply Chain Attack Detection Library
+// Rust port of shai-hulud-detector.sh
+//
+// The shai-hulud-detector binary is a thin wrapper around this crate. Embedding
+// applications can run the same scan in-process:
+//
+//     let options = shai_hulud_detector::ScanOptions::default();
+//     let results = shai_hulud_detector::scan("path/to/project", &options)?;
+//     println!("{} high risk findings", results.high_risk_count());
+
+pub mod baseline;
+#[macro_use]
+pub mod colors;
+pub mod data;
+pub mod detectors;
+pub mod feeds;
+pub mod file_index;
+pub mod fix;
+pub mod osv;
+pub mod report;
+pub mod sarif;
+pub mod scanner;
+pub mod semver;
+pub mod suppressions;
+pub mod utils;
+
+pub use data::{load_detection_data, DetectionData, IocData};
+pub use detectors::{Finding, RiskLevel, ScanResults};
+pub use feeds::{FeedOptions, FeedSource};
+pub use scanner::{scan, scan_with_data, ScanOptions};
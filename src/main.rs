@@ -5,16 +5,16 @@
 // Each function corresponds to a bash function from the original script
 
 mod cli;
-mod colors;
-mod data;
-mod detectors;
-mod report;
-mod semver;
-mod utils;
 
 use anyhow::Result;
 use clap::Parser;
 use cli::Cli;
+use shai_hulud_detector::{
+    baseline, colors, data, detectors, feeds, fix, human_println, report, sarif, scanner,
+    suppressions,
+};
+use std::io::Write;
+use std::path::{Path, PathBuf};
 use std::time::Instant;
 
 // Function: main
@@ -28,223 +28,104 @@ fn main() -> Result<()> {
     let start_timestamp = chrono::Local::now();
 
     let mut args = Cli::parse();
-    args.validate()?;
-
-    // Load compromised packages from external file
-    // Try multiple locations: same dir as exe, parent dir, or fallback
-    let script_dir = std::env::current_exe()?.parent().unwrap().to_path_buf();
 
-    // First try: exe_dir/../../../shai-hulud-detect/compromised-packages.txt (for dev)
-    // Second try: exe_dir/../compromised-packages.txt (for release)
-    // Third try: ./compromised-packages.txt (fallback)
-    let packages_file = script_dir
-        .parent()
-        .and_then(|target| target.parent()) // dev-rust-scanner-1
-        .and_then(|project| project.parent()) // rust-scanner
-        .map(|root| root.join("shai-hulud-detect/compromised-packages.txt"))
-        .filter(|p| p.exists())
-        .or_else(|| {
-            script_dir
-                .parent()
-                .map(|p| p.join("compromised-packages.txt"))
-                .filter(|p| p.exists())
-        })
-        .unwrap_or_else(|| std::path::PathBuf::from("compromised-packages.txt"));
-
-    let (compromised_packages, malicious_hashes) = data::load_detection_data(&packages_file)?;
-
-    colors::print_status(
-        colors::Color::Green,
-        "Starting Shai-Hulud detection scan...",
+    // Keep stdout clean for JSON: all human-readable output goes to stderr
+    colors::set_human_output_to_stderr(
+        args.format == cli::OutputFormat::Json || args.json_to_stdout(),
     );
 
-    let paranoid_msg = if args.paranoid {
-        format!(
-            "Scanning directory: {} (with paranoid mode enabled)",
-            utils::normalize_path(&args.scan_dir)
-        )
-    } else {
-        format!(
-            "Scanning directory: {}",
-            utils::normalize_path(&args.scan_dir)
-        )
-    };
-    colors::print_status(colors::Color::Blue, &paranoid_msg);
-    println!();
-
-    // Load verification resolvers (if --verify flag is set)
-    // NOTE: Status messages are Rust-only feature, tests ignore them via strip_verification_data
-    let (lockfile_resolver, mut runtime_resolver) = if args.verify {
-        // Try lockfile first (static analysis)
-        let lockfile =
-            match detectors::lockfile_resolver::LockfileResolver::load_from_dir(&args.scan_dir) {
-                Ok(resolver) if resolver.has_lockfile() => {
-                    colors::print_status(
-                        colors::Color::Green,
-                        &format!(
-                            "✅ Lockfile loaded ({:?} format, {} packages)",
-                            resolver.lockfile_type.as_ref().unwrap(),
-                            resolver.packages.len()
-                        ),
-                    );
-                    Some(resolver)
-                }
-                _ => None,
-            };
-
-        // Try runtime resolution (actual installed packages)
-        colors::print_status(
-            colors::Color::Blue,
-            "🔍 Querying package manager for installed versions...",
-        );
-        let runtime =
-            match detectors::runtime_resolver::RuntimeResolver::from_runtime(&args.scan_dir) {
-                Ok(resolver) if resolver.has_packages() => {
-                    colors::print_status(
-                        colors::Color::Green,
-                        &format!(
-                            "✅ Runtime resolver: {} packages found",
-                            resolver.packages.len()
-                        ),
-                    );
-                    Some(resolver)
-                }
-                Ok(_) => {
-                    colors::print_status(
-                        colors::Color::Yellow,
-                        "⚠️  Runtime resolution failed - using lockfile only",
-                    );
-                    None
-                }
-                Err(e) => {
-                    colors::print_status(
-                        colors::Color::Yellow,
-                        &format!("⚠️  Runtime resolution error: {e} - using lockfile only"),
-                    );
-                    None
-                }
-            };
-
-        (lockfile, runtime)
-    } else {
-        (None, None)
-    };
-
-    // Create results container
-    let mut results = detectors::ScanResults::new();
-
-    // Run core Shai-Hulud detection checks (matching bash function execution order)
-    // 1. check_workflow_files
-    results.workflow_files = detectors::workflow::check_workflow_files(&args.scan_dir);
-
-    // 2. check_file_hashes
-    results.malicious_hashes =
-        detectors::hashes::check_file_hashes(&args.scan_dir, &malicious_hashes, args.parallelism);
+    args.validate()?;
+    detectors::bun_lockfile::set_decode_with_bun(args.decode_bun_lockb);
 
-    // 3. check_packages
-    let (comp, susp, lockfile_safe, ns) = detectors::packages::check_packages(
-        &args.scan_dir,
-        &compromised_packages,
-        lockfile_resolver.as_ref(),
-        runtime_resolver.as_mut(),
-    );
-    results.compromised_found = comp;
-    results.suspicious_found = susp;
-    results.lockfile_safe_versions = lockfile_safe;
-    results.namespace_warnings = ns;
-
-    // 3.5. check_semver_ranges (if enabled)
-    if args.check_semver_ranges {
-        let semver_findings = detectors::packages::check_semver_ranges(
-            &args.scan_dir,
-            &compromised_packages,
-            lockfile_resolver.as_ref(),
-        );
-        results.lockfile_safe_versions.extend(semver_findings);
+    // Subcommands that don't scan
+    if let Some(cli::Command::UpdateFeeds) = args.command {
+        return update_feeds(&args);
     }
-
-    // 4. check_postinstall_hooks
-    results.postinstall_hooks = detectors::postinstall::check_postinstall_hooks(&args.scan_dir);
-
-    // 5. check_content
-    results.suspicious_content = detectors::content::check_content(&args.scan_dir);
-
-    // 6. check_crypto_theft_patterns
-    results.crypto_patterns = detectors::crypto::check_crypto_theft_patterns(&args.scan_dir);
-
-    // 7. check_trufflehog_activity
-    results.trufflehog_activity = detectors::trufflehog::check_trufflehog_activity(&args.scan_dir);
-
-    // 8. check_git_branches
-    results.git_branches = detectors::git::check_git_branches(&args.scan_dir);
-
-    // 9. check_shai_hulud_repos
-    results.shai_hulud_repos = detectors::repos::check_shai_hulud_repos(&args.scan_dir);
-
-    // 10. check_package_integrity
-    results.integrity_issues =
-        detectors::integrity::check_package_integrity(&args.scan_dir, &compromised_packages);
-
-    // 11-20. November 2025 "Shai-Hulud: The Second Coming" Attack detectors
-    // check_bun_attack_files (combines setup_bun.js and bun_environment.js)
-    let bun_findings = detectors::bun_attack::check_bun_attack_files(&args.scan_dir);
-    for finding in bun_findings {
-        if finding.category == "bun_setup_files" {
-            results.bun_setup_files.push(finding);
-        } else if finding.category == "bun_environment_files" {
-            results.bun_environment_files.push(finding);
-        }
+    if let Some(cli::Command::Fix { ref dir, apply, .. }) = args.command {
+        return fix(&args, dir, apply);
     }
+    let scan_dir = args
+        .scan_dir
+        .clone()
+        .expect("scan_dir is required without a subcommand");
+
+    let packages_file = packages_file()?;
+
+    let options = scanner::ScanOptions {
+        paranoid: args.paranoid,
+        verify: args.verify,
+        parallelism: args.parallelism,
+        check_semver_ranges: args.check_semver_ranges,
+        inventory: args.inventory,
+        scan_caches: args.scan_caches,
+        only: args.only.clone(),
+        skip: args.skip.clone(),
+        packages_file,
+        feeds: args.feed_options(),
+        quiet: false,
+    };
 
-    // check_new_workflow_patterns
-    let new_workflow_findings =
-        detectors::workflows_new::check_new_workflow_patterns(&args.scan_dir);
-    for finding in new_workflow_findings {
-        if finding.category == "new_workflow_files" {
-            results.new_workflow_files.push(finding);
-        } else if finding.category == "actions_secrets_files" {
-            results.actions_secrets_files.push(finding);
+    // Run all registered detectors (see scanner::scan)
+    let mut results = scanner::scan(&scan_dir, &options)?;
+
+    // Move reviewed exceptions (.shai-hulud-ignore.toml) out of the risk counts
+    let ignore_file = args
+        .ignore_file
+        .clone()
+        .or_else(|| Some(scan_dir.join(suppressions::DEFAULT_IGNORE_FILE)).filter(|p| p.exists()));
+    if let Some(ref ignore_path) = ignore_file {
+        let suppressions = suppressions::Suppressions::load(ignore_path)?;
+        let today = chrono::Local::now().date_naive();
+        for expired in suppressions.apply(&mut results, &scan_dir, today) {
+            let entry = &expired.suppression;
+            colors::print_status(
+                colors::Color::Yellow,
+                &format!(
+                    "⚠️  Suppression expired on {} (owner: {}, reason: {}) - {} finding(s) reported again",
+                    entry.expires.map(|d| d.to_string()).unwrap_or_default(),
+                    entry.owner,
+                    entry.reason,
+                    expired.reactivated
+                ),
+            );
         }
+        colors::print_status(
+            colors::Color::Blue,
+            &format!(
+                "🔕 Suppressions: {} findings suppressed by {}",
+                results.suppressed.len(),
+                ignore_path.display()
+            ),
+        );
     }
 
-    // check_discussion_workflows
-    results.discussion_workflows =
-        detectors::discussion_workflows::check_discussion_workflows(&args.scan_dir);
-
-    // check_github_runners
-    results.github_runners = detectors::github_runners::check_github_runners(&args.scan_dir);
+    // Load the baseline before --write-baseline may overwrite the same file
+    let baseline = args
+        .baseline
+        .as_deref()
+        .map(baseline::Baseline::load)
+        .transpose()?;
 
-    // check_destructive_patterns
-    results.destructive_patterns =
-        detectors::destructive_patterns::check_destructive_patterns(&args.scan_dir);
-
-    // check_preinstall_bun_patterns
-    results.preinstall_bun_patterns =
-        detectors::preinstall_bun::check_preinstall_bun_patterns(&args.scan_dir);
-
-    // check_github_actions_runner (SHA1HULUD)
-    results.github_sha1hulud_runners =
-        detectors::sha1hulud_runner::check_github_actions_runner(&args.scan_dir);
-
-    // check_second_coming_repos
-    results.second_coming_repos =
-        detectors::second_coming::check_second_coming_repos(&args.scan_dir);
-
-    // Run additional security checks only in paranoid mode
-    if args.paranoid {
+    // Write/refresh the baseline with every current finding
+    if let Some(ref baseline_path) = args.write_baseline {
+        baseline::Baseline::write(baseline_path, &results, &scan_dir)?;
         colors::print_status(
-            colors::Color::Blue,
-            "Checking for typosquatting and homoglyph attacks...",
+            colors::Color::Green,
+            &format!(
+                "📋 Baseline written: {} ({} findings)",
+                baseline_path.display(),
+                results.all_findings().count()
+            ),
         );
-        results.typosquatting_warnings =
-            detectors::typosquatting::check_typosquatting(&args.scan_dir);
+    }
 
+    // Report and exit only on findings that are not in the baseline
+    if let Some(baseline) = baseline {
+        let known = baseline.apply(&mut results, &scan_dir);
         colors::print_status(
             colors::Color::Blue,
-            "Checking for network exfiltration patterns...",
+            &format!("📋 Baseline: {known} known findings hidden, reporting new findings only"),
         );
-        results.network_exfiltration_warnings =
-            detectors::network::check_network_exfiltration(&args.scan_dir);
     }
 
     // Calculate total_issues using ScanResults methods which include all detectors
@@ -252,16 +133,6 @@ fn main() -> Result<()> {
     let medium_risk = results.medium_risk_count(args.paranoid);
     let total_issues = high_risk + medium_risk;
 
-    // BASH EXACT: Apply namespace warning logic - only include in results if they would be shown
-    // Bash shows namespace warnings in detail only when total_issues == 0 OR total_issues < 5
-    if total_issues >= 5 {
-        // Store count for bash compatibility before filtering
-        results.suppressed_namespace_count = results.namespace_warnings.len();
-        // Too many critical issues - don't include namespace warnings in detailed output/JSON
-        results.namespace_warnings = Vec::new();
-    }
-    // If total_issues == 0 or < 5, keep namespace warnings as-is
-
     // Generate report
     report::generate_report(&results, args.paranoid);
 
@@ -291,20 +162,35 @@ fn main() -> Result<()> {
     }
 
     // Save JSON output for pattern-level verification
-    // Save in current directory by default (can be redirected in scripts)
-    let json_output_path = "scan_results.json";
+    // Save in current directory by default (--json-out PATH / --json-out - to redirect)
     let json_output = serde_json::to_string_pretty(&results_for_json)?;
-    std::fs::write(json_output_path, json_output)?;
-    colors::print_status(
-        colors::Color::Green,
-        &format!("💾 JSON results saved: {json_output_path}"),
-    );
+    if args.json_to_stdout() {
+        let mut stdout = std::io::stdout().lock();
+        writeln!(stdout, "{json_output}")?;
+        stdout.flush()?;
+    } else {
+        let json_output_path = args.json_output_path();
+        std::fs::write(json_output_path, json_output)?;
+        colors::print_status(
+            colors::Color::Green,
+            &format!("💾 JSON results saved: {}", json_output_path.display()),
+        );
+    }
+
+    // Write SARIF 2.1.0 output if --sarif-out was specified
+    if let Some(ref sarif_path) = args.sarif_out {
+        sarif::write_sarif(sarif_path, &results, &scan_dir)?;
+        colors::print_status(
+            colors::Color::Green,
+            &format!("💾 SARIF results saved: {}", sarif_path.display()),
+        );
+    }
 
     // Print timing information
     let end_timestamp = chrono::Local::now();
     let duration = start_time.elapsed();
 
-    println!();
+    human_println!();
     colors::print_status(
         colors::Color::Blue,
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
@@ -314,17 +200,17 @@ fn main() -> Result<()> {
         colors::Color::Blue,
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
     );
-    println!(
+    human_println!(
         "   Started:  {}",
         start_timestamp.format("%Y-%m-%d %H:%M:%S")
     );
-    println!("   Finished: {}", end_timestamp.format("%Y-%m-%d %H:%M:%S"));
-    println!("   Duration: {:.2}s", duration.as_secs_f64());
+    human_println!("   Finished: {}", end_timestamp.format("%Y-%m-%d %H:%M:%S"));
+    human_println!("   Duration: {:.2}s", duration.as_secs_f64());
     colors::print_status(
         colors::Color::Blue,
         "━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━━",
     );
-    println!();
+    human_println!();
 
     // Return appropriate exit code based on findings (matching bash script)
     if high_risk > 0 {
@@ -335,3 +221,133 @@ fn main() -> Result<()> {
         Ok(()) // Clean - no significant findings (exit code 0)
     }
 }
+
+// Function: packages_file
+// Purpose: Locate compromised-packages.txt next to the binary
+// Args: None
+// Returns: Path of the compromised package list (./compromised-packages.txt as fallback)
+fn packages_file() -> Result<PathBuf> {
+    // Load compromised packages from external file
+    // Try multiple locations: same dir as exe, parent dir, or fallback
+    let script_dir = std::env::current_exe()?.parent().unwrap().to_path_buf();
+
+    // First try: exe_dir/../../../shai-hulud-detect/compromised-packages.txt (for dev)
+    // Second try: exe_dir/../compromised-packages.txt (for release)
+    // Third try: ./compromised-packages.txt (fallback)
+    Ok(script_dir
+        .parent()
+        .and_then(|target| target.parent()) // dev-rust-scanner-1
+        .and_then(|project| project.parent()) // rust-scanner
+        .map(|root| root.join("shai-hulud-detect/compromised-packages.txt"))
+        .filter(|p| p.exists())
+        .or_else(|| {
+            script_dir
+                .parent()
+                .map(|p| p.join("compromised-packages.txt"))
+                .filter(|p| p.exists())
+        })
+        .unwrap_or_else(|| PathBuf::from("compromised-packages.txt")))
+}
+
+// Function: fix
+// Purpose: Show (and with --apply write) the override blocks that pin compromised
+//          packages to the nearest safe version (fix subcommand)
+// Args: args - parsed command line (feed options), dir - project directory,
+//       apply - write the changes instead of only showing the diff
+// Returns: Ok once the diff is shown / the files are written
+fn fix(args: &Cli, dir: &Path, apply: bool) -> Result<()> {
+    let data = data::load_detection_data(&packages_file()?, &args.feed_options())?;
+    let options = scanner::ScanOptions {
+        parallelism: args.parallelism,
+        only: vec!["packages".to_string()],
+        feeds: args.feed_options(),
+        ..scanner::ScanOptions::default()
+    };
+    let results = scanner::scan_with_data(dir, &options, &data)?;
+    let fixes = fix::plan_fixes(
+        dir,
+        &results,
+        &data.compromised_packages,
+        &data.advisories,
+        args.parallelism,
+    )?;
+    human_println!();
+
+    for warning in &fixes.warnings {
+        colors::print_status(colors::Color::Yellow, &format!("⚠️  {warning}"));
+    }
+    let plans = fixes.plans;
+    if plans.is_empty() {
+        if fixes.warnings.is_empty() {
+            colors::print_status(
+                colors::Color::Green,
+                "✅ No overrides needed - no compromised packages declared or locked",
+            );
+        }
+        return Ok(());
+    }
+
+    for plan in &plans {
+        for line in plan.diff().lines() {
+            match line.chars().next() {
+                Some('+') if !line.starts_with("+++") => {
+                    colors::print_status(colors::Color::Green, line);
+                }
+                Some('-') if !line.starts_with("---") => {
+                    colors::print_status(colors::Color::Red, line);
+                }
+                Some('@') => colors::print_status(colors::Color::Blue, line),
+                _ => human_println!("{line}"),
+            }
+        }
+        human_println!();
+    }
+
+    if !apply {
+        colors::print_status(
+            colors::Color::Yellow,
+            &format!(
+                "🔧 Dry run: {} package.json file(s) would be updated - re-run with `fix --apply` to write them",
+                plans.len()
+            ),
+        );
+        return Ok(());
+    }
+    for plan in &plans {
+        plan.apply()?;
+        colors::print_status(
+            colors::Color::Green,
+            &format!("✅ Updated {}", plan.package_json.display()),
+        );
+    }
+    colors::print_status(
+        colors::Color::Blue,
+        "Run your package manager's install to update the lockfiles.",
+    );
+    Ok(())
+}
+
+// Function: update_feeds
+// Purpose: Refresh the IOC feed cache without scanning (update-feeds subcommand)
+// Args: args - parsed command line (--feed, --ioc-feed, --cache-dir)
+// Returns: Ok if the cache was refreshed, error otherwise
+fn update_feeds(args: &Cli) -> Result<()> {
+    let feed_options = args.feed_options();
+    colors::print_status(colors::Color::Blue, "📡 Updating IOC feeds...");
+
+    for (metadata, status) in data::update_feeds(&feed_options)? {
+        let message = match status {
+            feeds::FetchStatus::NotModified => "✅ Feed unchanged since last update",
+            feeds::FetchStatus::Downloaded => "✅ Feed cache updated",
+        };
+        colors::print_status(colors::Color::Green, message);
+        colors::print_status(
+            colors::Color::Blue,
+            &format!("🗂️  Feed {}", metadata.summary()),
+        );
+        if let Some(ref cache_file) = metadata.cache_file {
+            colors::print_status(colors::Color::Blue, &format!("💾 Cached to {cache_file}"));
+        }
+    }
+    Ok(())
+}
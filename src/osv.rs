@@ -0,0 +1,382 @@
+// OSV Advisories - Malicious-package advisories (OSV / GHSA JSON) as package feed input
+// Purpose: Match npm packages against advisory version ranges instead of flat name:version lines
+//
+// Accepts one OSV JSON file (a single record or an array of records) or a directory
+// of them, e.g. the MAL- entries from https://github.com/ossf/malicious-packages or
+// GHSA malware advisories exported in OSV format:
+//
+//   shai-hulud-detector --feed ./osv/malicious/npm /path/to/scan
+//
+// Only `affected` entries with ecosystem "npm" are used. Explicit `versions` are also
+// added to the compromised package set so every exact-match check picks them up;
+// SEMVER / ECOSYSTEM `ranges` are evaluated when a package version is checked.
+
+use crate::data::CompromisedPackage;
+use crate::semver::compare_versions;
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+use walkdir::WalkDir;
+
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum OsvFile {
+    Many(Vec<OsvRecord>),
+    One(Box<OsvRecord>),
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRecord {
+    id: String,
+    #[serde(default)]
+    aliases: Vec<String>,
+    #[serde(default)]
+    withdrawn: Option<String>,
+    #[serde(default)]
+    affected: Vec<OsvAffected>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvAffected {
+    package: Option<OsvPackage>,
+    #[serde(default)]
+    ranges: Vec<OsvRange>,
+    #[serde(default)]
+    versions: Vec<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvPackage {
+    ecosystem: String,
+    name: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct OsvRange {
+    #[serde(rename = "type")]
+    kind: String,
+    #[serde(default)]
+    events: Vec<OsvEvent>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OsvEvent {
+    introduced: Option<String>,
+    fixed: Option<String>,
+    last_affected: Option<String>,
+}
+
+/// One affected version interval: introduced <= v < fixed (or <= last_affected)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AffectedRange {
+    /// None = every version up to the end of the range ("introduced": "0")
+    pub introduced: Option<String>,
+    pub fixed: Option<String>,
+    pub last_affected: Option<String>,
+}
+
+impl AffectedRange {
+    // Function: contains
+    // Purpose: Check if an exact version falls inside this interval
+    // Args: version - exact version (ranges like "^1.0.0" never match)
+    // Returns: true if introduced <= version and version is below fixed / at most last_affected
+    pub fn contains(&self, version: &str) -> bool {
+        // Unparsable versions (ranges, tags) never fall inside an open-ended range
+        if compare_versions(version, "0.0.0").is_none() {
+            return false;
+        }
+        let at_or_above = |bound: &String| compare_versions(version, bound) != Some(Ordering::Less);
+        let below = |bound: &String| compare_versions(version, bound) == Some(Ordering::Less);
+        let at_or_below =
+            |bound: &String| compare_versions(version, bound) != Some(Ordering::Greater);
+
+        self.introduced.as_ref().is_none_or(at_or_above)
+            && self.fixed.as_ref().is_none_or(below)
+            && self.last_affected.as_ref().is_none_or(at_or_below)
+    }
+}
+
+/// An advisory's affected entry for one npm package
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Advisory {
+    pub id: String,
+    pub aliases: Vec<String>,
+    pub package: String,
+    pub versions: Vec<String>,
+    pub ranges: Vec<AffectedRange>,
+}
+
+impl Advisory {
+    /// true if the advisory lists the version or one of its ranges contains it
+    pub fn affects(&self, version: &str) -> bool {
+        self.versions.iter().any(|v| v == version)
+            || self.ranges.iter().any(|range| range.contains(version))
+    }
+
+    /// Advisory ID with aliases for finding messages, e.g. "MAL-2025-1234 (GHSA-xxxx-xxxx-xxxx)"
+    pub fn reference(&self) -> String {
+        if self.aliases.is_empty() {
+            self.id.clone()
+        } else {
+            format!("{} ({})", self.id, self.aliases.join(", "))
+        }
+    }
+}
+
+/// Advisories indexed by npm package name
+#[derive(Debug, Clone, Default)]
+pub struct Advisories {
+    by_package: HashMap<String, Vec<Advisory>>,
+    len: usize,
+}
+
+impl Advisories {
+    // Function: parse
+    // Purpose: Parse one OSV JSON document (single record or array of records)
+    // Args: content - OSV JSON
+    // Returns: npm advisories (withdrawn records and other ecosystems are skipped)
+    pub fn parse(content: &str) -> Result<Vec<Advisory>> {
+        let records = match serde_json::from_str::<OsvFile>(content)? {
+            OsvFile::Many(records) => records,
+            OsvFile::One(record) => vec![*record],
+        };
+
+        let mut advisories = Vec::new();
+        for record in records.into_iter().filter(|r| r.withdrawn.is_none()) {
+            for affected in record.affected {
+                let Some(package) = affected.package else {
+                    continue;
+                };
+                if !package.ecosystem.eq_ignore_ascii_case("npm") {
+                    continue;
+                }
+                advisories.push(Advisory {
+                    id: record.id.clone(),
+                    aliases: record.aliases.clone(),
+                    package: package.name,
+                    versions: affected.versions,
+                    ranges: affected
+                        .ranges
+                        .iter()
+                        .filter(|r| r.kind == "SEMVER" || r.kind == "ECOSYSTEM")
+                        .flat_map(|r| intervals(&r.events))
+                        .collect(),
+                });
+            }
+        }
+        Ok(advisories)
+    }
+
+    // Function: load
+    // Purpose: Load OSV advisories from a JSON file or a directory of JSON files
+    // Args: path - OSV file or directory (searched recursively)
+    // Returns: (advisories, concatenated file contents for the feed hash)
+    pub fn load(path: &Path) -> Result<(Self, String)> {
+        let mut files: Vec<_> = if path.is_dir() {
+            WalkDir::new(path)
+                .into_iter()
+                .filter_map(std::result::Result::ok)
+                .filter(|e| e.file_type().is_file())
+                .map(walkdir::DirEntry::into_path)
+                .filter(|p| p.extension().is_some_and(|ext| ext == "json"))
+                .collect()
+        } else {
+            vec![path.to_path_buf()]
+        };
+        files.sort();
+
+        let mut advisories = Self::default();
+        let mut combined = String::new();
+        for file in files {
+            let content = fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read OSV file {}", file.display()))?;
+            let parsed = Self::parse(&content)
+                .with_context(|| format!("Invalid OSV file {}", file.display()))?;
+            advisories.extend(parsed);
+            combined.push_str(&content);
+        }
+        Ok((advisories, combined))
+    }
+
+    pub fn extend(&mut self, advisories: impl IntoIterator<Item = Advisory>) {
+        for advisory in advisories {
+            self.len += 1;
+            self.by_package
+                .entry(advisory.package.clone())
+                .or_default()
+                .push(advisory);
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Number of distinct packages with at least one advisory
+    pub fn package_count(&self) -> usize {
+        self.by_package.len()
+    }
+
+    /// Explicitly listed versions as name:version entries for exact-match checks
+    pub fn exact_versions(&self) -> impl Iterator<Item = CompromisedPackage> + '_ {
+        self.by_package.values().flatten().flat_map(|advisory| {
+            advisory
+                .versions
+                .iter()
+                .map(|version| CompromisedPackage::new(advisory.package.clone(), version.clone()))
+        })
+    }
+
+    /// Advisories affecting this package version
+    pub fn matching<'a>(
+        &'a self,
+        package: &str,
+        version: &'a str,
+    ) -> impl Iterator<Item = &'a Advisory> + 'a {
+        self.by_package
+            .get(package)
+            .into_iter()
+            .flatten()
+            .filter(move |advisory| advisory.affects(version))
+    }
+
+    // Function: references
+    // Purpose: Advisory IDs and aliases for a finding message
+    // Args: package - npm package name, version - exact version
+    // Returns: e.g. "MAL-2025-1234 (GHSA-xxxx-xxxx-xxxx)", None if no advisory matches
+    pub fn references(&self, package: &str, version: &str) -> Option<String> {
+        let mut references: Vec<String> = Vec::new();
+        for advisory in self.matching(package, version) {
+            let reference = advisory.reference();
+            if !references.contains(&reference) {
+                references.push(reference);
+            }
+        }
+        (!references.is_empty()).then(|| references.join(", "))
+    }
+}
+
+// Function: with_references
+// Purpose: Append matching advisory IDs to a finding message so triage can link to them
+// Args: message - finding message, references - Advisories::references() result
+// Returns: "message [MAL-2025-1234 (GHSA-...)]", or message unchanged without advisories
+pub fn with_references(message: String, references: Option<String>) -> String {
+    match references {
+        Some(references) => format!("{message} [{references}]"),
+        None => message,
+    }
+}
+
+// Function: is_osv_source
+// Purpose: Decide whether a --feed path holds OSV JSON rather than compromised-packages.txt
+// Args: path - --feed file or directory
+// Returns: true for directories and .json files
+pub fn is_osv_source(path: &Path) -> bool {
+    path.is_dir() || path.extension().is_some_and(|ext| ext == "json")
+}
+
+// Turn OSV range events into affected intervals (events are applied in order)
+fn intervals(events: &[OsvEvent]) -> Vec<AffectedRange> {
+    let mut ranges = Vec::new();
+    let mut open: Option<AffectedRange> = None;
+    for event in events {
+        if let Some(ref introduced) = event.introduced {
+            if let Some(range) = open.take() {
+                ranges.push(range);
+            }
+            open = Some(AffectedRange {
+                introduced: (introduced != "0").then(|| introduced.clone()),
+                fixed: None,
+                last_affected: None,
+            });
+        } else if let Some(mut range) = open.take() {
+            range.fixed.clone_from(&event.fixed);
+            range.last_affected.clone_from(&event.last_affected);
+            ranges.push(range);
+        }
+    }
+    ranges.extend(open);
+    ranges
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    const MAL_RECORD: &str = r#"{
+        "id": "MAL-2025-0001",
+        "aliases": ["GHSA-aaaa-bbbb-cccc"],
+        "affected": [{
+            "package": {"ecosystem": "npm", "name": "@ctrl/tinycolor"},
+            "ranges": [{"type": "SEMVER", "events": [
+                {"introduced": "4.1.1"}, {"fixed": "4.1.3"},
+                {"introduced": "5.0.0"}, {"last_affected": "5.0.1"}
+            ]}],
+            "versions": ["4.1.1", "4.1.2"]
+        }, {
+            "package": {"ecosystem": "PyPI", "name": "tinycolor"},
+            "versions": ["1.0.0"]
+        }]
+    }"#;
+
+    #[test]
+    fn test_parse_ranges_and_versions() {
+        let advisories = Advisories::parse(MAL_RECORD).unwrap();
+        assert_eq!(advisories.len(), 1);
+        let advisory = &advisories[0];
+        assert!(advisory.affects("4.1.1"));
+        assert!(advisory.affects("4.1.2"));
+        assert!(!advisory.affects("4.1.3"));
+        assert!(!advisory.affects("4.1.0"));
+        assert!(advisory.affects("5.0.1"));
+        assert!(!advisory.affects("5.0.2"));
+        assert!(!advisory.affects("^4.1.0"));
+        assert_eq!(advisory.reference(), "MAL-2025-0001 (GHSA-aaaa-bbbb-cccc)");
+    }
+
+    #[test]
+    fn test_introduced_zero_affects_every_version() {
+        let record = r#"[{
+            "id": "MAL-2025-0002",
+            "affected": [{"package": {"ecosystem": "npm", "name": "evil-pkg"},
+                          "ranges": [{"type": "SEMVER", "events": [{"introduced": "0"}]}]}]
+        }, {
+            "id": "MAL-2025-0003",
+            "withdrawn": "2025-10-01T00:00:00Z",
+            "affected": [{"package": {"ecosystem": "npm", "name": "fine-pkg"}, "versions": ["1.0.0"]}]
+        }]"#;
+        let mut advisories = Advisories::default();
+        advisories.extend(Advisories::parse(record).unwrap());
+
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(
+            advisories.references("evil-pkg", "0.0.1").as_deref(),
+            Some("MAL-2025-0002")
+        );
+        assert!(advisories.references("fine-pkg", "1.0.0").is_none());
+    }
+
+    #[test]
+    fn test_load_directory() {
+        let temp = TempDir::new().unwrap();
+        let nested = temp.path().join("npm/@ctrl");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(nested.join("MAL-2025-0001.json"), MAL_RECORD).unwrap();
+        fs::write(temp.path().join("README.md"), "not json").unwrap();
+
+        assert!(is_osv_source(temp.path()));
+        let (advisories, content) = Advisories::load(temp.path()).unwrap();
+        assert_eq!(advisories.len(), 1);
+        assert_eq!(advisories.package_count(), 1);
+        assert_eq!(advisories.exact_versions().count(), 2);
+        assert_eq!(content, MAL_RECORD);
+    }
+}
@@ -5,6 +5,7 @@
 
 use crate::colors::{print_status, Color};
 use crate::detectors::{verification, RiskLevel, ScanResults};
+use crate::feeds::FeedOrigin;
 use std::collections::BTreeSet;
 use std::fs::File;
 use std::io::Write;
@@ -17,10 +18,10 @@ fn show_file_preview(file_path: &Path, context: &str) {
     // Only show file preview for HIGH RISK items to reduce noise
     if context.contains("HIGH RISK") {
         let normalized = crate::utils::normalize_path(file_path);
-        println!("   \x1b[34m┌─ File: {normalized}\x1b[0m");
-        println!("   \x1b[34m│  Context: {context}\x1b[0m");
-        println!("   \x1b[34m└─\x1b[0m");
-        println!();
+        human_println!("   \x1b[34m┌─ File: {normalized}\x1b[0m");
+        human_println!("   \x1b[34m│  Context: {context}\x1b[0m");
+        human_println!("   \x1b[34m└─\x1b[0m");
+        human_println!();
     }
 }
 
@@ -31,7 +32,7 @@ fn show_file_preview(file_path: &Path, context: &str) {
 // Returns: Outputs formatted report to stdout with HIGH/MEDIUM/LOW risk sections
 #[allow(clippy::too_many_lines)]
 pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
-    println!();
+    human_println!();
     print_status(
         Color::Blue,
         "==============================================",
@@ -47,7 +48,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
         Color::Blue,
         "==============================================",
     );
-    println!();
+    human_println!();
 
     let high_risk = results.high_risk_count();
     let medium_risk = results.medium_risk_count(paranoid_mode);
@@ -58,7 +59,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
     if !results.workflow_files.is_empty() {
         print_status(Color::Red, "HIGH RISK: Malicious workflow files detected:");
         for finding in &results.workflow_files {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             show_file_preview(
                 &finding.file_path,
                 "HIGH RISK: Known malicious workflow filename",
@@ -74,7 +75,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Fake Bun runtime installation files (November 2025 attack):",
         );
         for finding in &results.bun_setup_files {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             // BASH COMPATIBILITY: Bash doesn't print the message here, only the file preview
             // println!("     {}", finding.message);
             show_file_preview(
@@ -82,7 +83,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
                 &format!("HIGH RISK: {}", finding.message),
             );
         }
-        println!();
+        human_println!();
     }
 
     // Bun Environment Files
@@ -92,7 +93,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Obfuscated credential harvesting payloads:",
         );
         for finding in &results.bun_environment_files {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             // BASH COMPATIBILITY: Bash doesn't print the message here, only the file preview
             // println!("     {}", finding.message);
             show_file_preview(
@@ -100,7 +101,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
                 &format!("HIGH RISK: {}", finding.message),
             );
         }
-        println!();
+        human_println!();
     }
 
     // New Workflow Files
@@ -110,9 +111,9 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Malicious formatter workflow patterns:",
         );
         for finding in &results.new_workflow_files {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
         }
-        println!();
+        human_println!();
     }
 
     // Actions Secrets Files
@@ -122,9 +123,9 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: GitHub Actions secrets exfiltration files:",
         );
         for finding in &results.actions_secrets_files {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
         }
-        println!();
+        human_println!();
     }
 
     // Discussion Workflows
@@ -134,15 +135,15 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Malicious discussion-triggered workflows:",
         );
         for finding in &results.discussion_workflows {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             // BASH COMPATIBILITY: Add "Reason: " prefix to match bash output format
-            println!("     Reason: {}", finding.message);
+            human_println!("     Reason: {}", finding.message);
             show_file_preview(
                 &finding.file_path,
                 "HIGH RISK: Discussion workflow - Enables arbitrary command execution via GitHub discussions",
             );
         }
-        println!();
+        human_println!();
     }
 
     // GitHub Runners
@@ -152,15 +153,15 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Self-hosted GitHub Actions runners (persistent backdoors):",
         );
         for finding in &results.github_runners {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             // BASH COMPATIBILITY: Add "Reason: " prefix to match bash output format
-            println!("     Reason: {}", finding.message);
+            human_println!("     Reason: {}", finding.message);
             show_file_preview(
                 &finding.file_path,
                 "HIGH RISK: GitHub Actions runner - Self-hosted backdoor for persistent access",
             );
         }
-        println!();
+        human_println!();
     }
 
     // Destructive Patterns
@@ -171,11 +172,11 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "CRITICAL: Destructive payload patterns detected:",
         );
         for finding in &results.destructive_patterns {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
             // BASH COMPATIBILITY: Add "Pattern: " prefix
-            println!("     Pattern: {}", finding.message);
+            human_println!("     Pattern: {}", finding.message);
         }
-        println!();
+        human_println!();
     }
 
     // Preinstall Bun Patterns
@@ -185,9 +186,9 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Malicious preinstall scripts (fake Bun installation):",
         );
         for finding in &results.preinstall_bun_patterns {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
         }
-        println!();
+        human_println!();
     }
 
     // SHA1HULUD Runners
@@ -197,9 +198,9 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: SHA1HULUD malicious runner references:",
         );
         for finding in &results.github_sha1hulud_runners {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
         }
-        println!();
+        human_println!();
     }
 
     // Second Coming Repos
@@ -209,19 +210,19 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Repositories with 'Second Coming' marker:",
         );
         for finding in &results.second_coming_repos {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
         }
-        println!();
+        human_println!();
     }
 
     // Report malicious file hashes
     if !results.malicious_hashes.is_empty() {
         print_status(Color::Red, "HIGH RISK: Files with known malicious hashes:");
         for finding in &results.malicious_hashes {
-            println!("   - {}", crate::utils::normalize_path(&finding.file_path));
-            println!("     {}", finding.message);
+            human_println!("   - {}", crate::utils::normalize_path(&finding.file_path));
+            human_println!("     {}", finding.message);
         }
-        println!();
+        human_println!();
     }
 
     // Report compromised packages
@@ -236,8 +237,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
         sorted_findings.sort_by(|a, b| a.message.cmp(&b.message));
 
         for finding in &sorted_findings {
-            println!("   - Package: {}", finding.message);
-            println!(
+            human_println!("   - Package: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -257,7 +258,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   You should immediately update or remove these packages.",
         );
-        println!();
+        human_println!();
     }
 
     // Report suspicious packages
@@ -267,8 +268,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK: Suspicious package versions detected:",
         );
         for finding in &results.suspicious_found {
-            println!("   - Package: {}", finding.message);
-            println!(
+            human_println!("   - Package: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -301,7 +302,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   NOTE: Manual review required to determine if these are malicious.",
         );
-        println!();
+        human_println!();
     }
 
     // Report lockfile-safe packages (BASH LINE 1440-1453)
@@ -311,8 +312,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "ℹ️  LOW RISK: Packages with safe lockfile versions:",
         );
         for finding in &results.lockfile_safe_versions {
-            println!("   - Package: {}", finding.message);
-            println!(
+            human_println!("   - Package: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -325,15 +326,15 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Blue,
             "   Your current installation is safe. Avoid running 'npm update' without reviewing changes.",
         );
-        println!();
+        human_println!();
     }
 
     // Report suspicious content
     if !results.suspicious_content.is_empty() {
         print_status(Color::Yellow, "MEDIUM RISK: Suspicious content patterns:");
         for finding in &results.suspicious_content {
-            println!("   - Pattern: {}", finding.message);
-            println!(
+            human_println!("   - Pattern: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -342,7 +343,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   NOTE: Manual review required to determine if these are malicious.",
         );
-        println!();
+        human_println!();
     }
 
     // Report cryptocurrency theft patterns (separated by risk level)
@@ -363,7 +364,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Cryptocurrency theft patterns detected:",
         );
         for finding in crypto_high {
-            println!(
+            human_println!(
                 "   - {}:{}",
                 crate::utils::normalize_path(&finding.file_path),
                 finding.message
@@ -377,7 +378,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Red,
             "   Immediate investigation and remediation required.",
         );
-        println!();
+        human_println!();
     }
 
     if !crypto_medium.is_empty() {
@@ -386,7 +387,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK: Potential cryptocurrency manipulation patterns:",
         );
         for finding in crypto_medium {
-            println!(
+            human_println!(
                 "   - {}:{}",
                 crate::utils::normalize_path(&finding.file_path),
                 finding.message
@@ -421,24 +422,24 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "   NOTE: These may be legitimate crypto tools or framework code.",
         );
         print_status(Color::Yellow, "   Manual review required.");
-        println!();
+        human_println!();
     }
 
     // Report git branches
     if !results.git_branches.is_empty() {
         print_status(Color::Yellow, "MEDIUM RISK: Suspicious git branches:");
         for finding in &results.git_branches {
-            println!(
+            human_println!(
                 "   - Repository: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
-            println!("     {}", finding.message);
+            human_println!("     {}", finding.message);
         }
         print_status(
             Color::Yellow,
             "   NOTE: 'shai-hulud' branches may indicate compromise.",
         );
-        println!();
+        human_println!();
     }
 
     // Report suspicious postinstall hooks
@@ -448,8 +449,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Suspicious postinstall hooks detected:",
         );
         for finding in &results.postinstall_hooks {
-            println!("   - Hook: {}", finding.message);
-            println!(
+            human_println!("   - Hook: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -488,18 +489,18 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   Review these hooks carefully for malicious behavior.",
         );
-        println!();
+        human_println!();
     }
 
     // Report Shai-Hulud repositories
     if !results.shai_hulud_repos.is_empty() {
         print_status(Color::Red, "HIGH RISK: Shai-Hulud repositories detected:");
         for finding in &results.shai_hulud_repos {
-            println!(
+            human_println!(
                 "   - Repository: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
-            println!("     {}", finding.message);
+            human_println!("     {}", finding.message);
         }
         print_status(
             Color::Yellow,
@@ -509,7 +510,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   These should be deleted immediately after investigation.",
         );
-        println!();
+        human_println!();
     }
 
     // Report package integrity issues
@@ -519,8 +520,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK: Package integrity issues detected:",
         );
         for finding in &results.integrity_issues {
-            println!("   - Issue: {}", finding.message);
-            println!(
+            human_println!("   - Issue: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -529,7 +530,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   NOTE: These issues may indicate tampering with package dependencies.",
         );
-        println!();
+        human_println!();
     }
 
     // Report Trufflehog activity (separated by risk level)
@@ -550,8 +551,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "HIGH RISK: Trufflehog/secret scanning activity detected:",
         );
         for finding in trufflehog_high {
-            println!("   - Activity: {}", finding.message);
-            println!(
+            human_println!("   - Activity: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -568,7 +569,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Red,
             "   Immediate investigation and remediation required.",
         );
-        println!();
+        human_println!();
     }
 
     if !trufflehog_medium.is_empty() {
@@ -577,8 +578,8 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK: Potentially suspicious secret scanning patterns:",
         );
         for finding in trufflehog_medium {
-            println!("   - Pattern: {}", finding.message);
-            println!(
+            human_println!("   - Pattern: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
@@ -591,7 +592,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   Manual review recommended to determine if they are malicious.",
         );
-        println!();
+        human_println!();
     }
 
     // BASH LINE 1513-1534: Report typosquatting warnings (only in paranoid mode)
@@ -601,14 +602,14 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK (PARANOID): Potential typosquatting/homoglyph attacks detected:",
         );
         for finding in results.typosquatting_warnings.iter().take(5) {
-            println!("   - Warning: {}", finding.message);
-            println!(
+            human_println!("   - Warning: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
         }
         if results.typosquatting_warnings.len() > 5 {
-            println!(
+            human_println!(
                 "   - ... and {} more typosquatting warnings (truncated for brevity)",
                 results.typosquatting_warnings.len() - 5
             );
@@ -621,7 +622,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   Verify package names carefully and check if they should be legitimate packages.",
         );
-        println!();
+        human_println!();
     }
 
     // BASH LINE 1535-1556: Report network exfiltration warnings (only in paranoid mode)
@@ -631,14 +632,14 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             "MEDIUM RISK (PARANOID): Network exfiltration patterns detected:",
         );
         for finding in results.network_exfiltration_warnings.iter().take(5) {
-            println!("   - Warning: {}", finding.message);
-            println!(
+            human_println!("   - Warning: {}", finding.message);
+            human_println!(
                 "     Found in: {}",
                 crate::utils::normalize_path(&finding.file_path)
             );
         }
         if results.network_exfiltration_warnings.len() > 5 {
-            println!(
+            human_println!(
                 "   - ... and {} more network warnings (truncated for brevity)",
                 results.network_exfiltration_warnings.len() - 5
             );
@@ -651,7 +652,63 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Yellow,
             "   Review network connections and data flows carefully.",
         );
-        println!();
+        human_println!();
+    }
+
+    // Report findings from registered detectors without a dedicated section
+    for (key, findings) in &results.additional_findings {
+        if findings.is_empty() {
+            continue;
+        }
+        let description = crate::detectors::registry::describe(key).unwrap_or(key.as_str());
+        let (color, label) = if findings.iter().any(|f| f.risk_level == RiskLevel::High) {
+            (Color::Red, "HIGH RISK")
+        } else if findings.iter().any(|f| f.risk_level == RiskLevel::Medium) {
+            (Color::Yellow, "MEDIUM RISK")
+        } else {
+            (Color::Blue, "ℹ️  LOW RISK")
+        };
+        print_status(color, &format!("{label}: {description}:"));
+        for finding in findings {
+            human_println!("   - {}", finding.message);
+            human_println!(
+                "     Found in: {}",
+                crate::utils::normalize_path(&finding.file_path)
+            );
+        }
+        human_println!();
+    }
+
+    // Report suppressed findings (.shai-hulud-ignore.toml) - not part of the risk counts
+    if !results.suppressed.is_empty() {
+        print_status(
+            Color::Blue,
+            &format!(
+                "🔕 SUPPRESSED FINDINGS ({}, excluded from risk counts):",
+                results.suppressed.len()
+            ),
+        );
+        for suppressed in &results.suppressed {
+            let finding = &suppressed.finding;
+            human_println!(
+                "   - [{}] {}: {}",
+                finding.category,
+                crate::utils::normalize_path(&finding.file_path),
+                finding.message
+            );
+            let expires = suppressed
+                .expires
+                .as_ref()
+                .map(|d| format!(", expires {d}"))
+                .unwrap_or_default();
+            human_println!(
+                "     Reason: {} (owner: {}{})",
+                suppressed.reason,
+                suppressed.owner,
+                expires
+            );
+        }
+        human_println!();
     }
 
     // Summary
@@ -672,10 +729,10 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
 
         // Show low risk findings if any (informational only)
         if low_risk > 0 {
-            println!();
+            human_println!();
             print_status(Color::Blue, "ℹ️  LOW RISK FINDINGS (informational only):");
             for finding in &results.namespace_warnings {
-                println!("   - {}", finding.message);
+                human_println!("   - {}", finding.message);
             }
             print_status(
                 Color::Blue,
@@ -699,7 +756,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
             Color::Blue,
             &format!("   Total Critical Issues: {total_issues}"),
         );
-        println!();
+        human_println!();
         print_status(Color::Yellow, "IMPORTANT:");
         print_status(
             Color::Yellow,
@@ -730,7 +787,7 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
 
         // BASH EXACT: Show LOW risk findings in detail only if total_issues < 5
         if low_risk > 0 && total_issues < 5 {
-            println!();
+            human_println!();
             print_status(
                 Color::Blue,
                 "ℹ️  LOW RISK FINDINGS (likely false positives):",
@@ -738,27 +795,43 @@ pub fn generate_report(results: &ScanResults, paranoid_mode: bool) {
 
             // Show all LOW risk findings
             for finding in &results.namespace_warnings {
-                println!("   - {}", finding.message);
+                human_println!("   - {}", finding.message);
             }
 
             for finding in &results.crypto_patterns {
                 if finding.risk_level == RiskLevel::Low {
-                    println!("   - Crypto pattern: {}", finding.message);
+                    human_println!("   - Crypto pattern: {}", finding.message);
                 }
             }
 
             // Show trufflehog activity (environment variable access)
             for finding in &results.trufflehog_activity {
-                println!("   - {}", finding.message);
+                human_println!("   - {}", finding.message);
             }
 
-            println!("   \x1b[34mNOTE: These are typically legitimate framework patterns.\x1b[0m");
+            human_println!(
+                "   \x1b[34mNOTE: These are typically legitimate framework patterns.\x1b[0m"
+            );
         }
     }
 
     // Add verification summary if any findings were verified
     print_verification_summary(results, paranoid_mode);
 
+    // State which feed generation the results are based on
+    if !results.feeds.is_empty() {
+        human_println!();
+        print_status(Color::Blue, "🗂️  Feed generation used:");
+        for feed in &results.feeds {
+            let color = if feed.origin.is_some_and(FeedOrigin::is_fresh) {
+                Color::Blue
+            } else {
+                Color::Yellow
+            };
+            print_status(color, &format!("🗂️     {}", feed.generation()));
+        }
+    }
+
     print_status(
         Color::Blue,
         "==============================================",
@@ -786,7 +859,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
         return; // No verification data, skip summary
     }
 
-    println!();
+    human_println!();
     print_status(
         Color::Blue,
         "==============================================",
@@ -796,7 +869,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
         Color::Blue,
         "==============================================",
     );
-    println!();
+    human_println!();
 
     // Count verifications by category
     let mut high_total = 0;
@@ -841,7 +914,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
     // Print HIGH RISK summary
     if high_total > 0 {
         print_status(Color::Red, "🔴 HIGH RISK VERIFICATION:");
-        println!("   Total findings: {high_total}");
+        human_println!("   Total findings: {high_total}");
         if high_verified_safe > 0 {
             print_status(
                 Color::Green,
@@ -862,13 +935,13 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
                 ),
             );
         }
-        println!();
+        human_println!();
     }
 
     // Print MEDIUM RISK summary
     if medium_total > 0 {
         print_status(Color::Yellow, "🟡 MEDIUM RISK VERIFICATION:");
-        println!("   Total findings: {medium_total}");
+        human_println!("   Total findings: {medium_total}");
         if medium_verified_safe > 0 {
             print_status(
                 Color::Green,
@@ -889,7 +962,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
                 ),
             );
         }
-        println!();
+        human_println!();
     }
 
     // Overall conclusion
@@ -902,11 +975,11 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
     };
 
     print_status(Color::Blue, "📊 VERIFICATION STATISTICS:");
-    println!("   Total critical findings analyzed: {total_findings}");
-    println!("   Verified as false positives: {total_verified} ({false_positive_rate:.0}%)");
+    human_println!("   Total critical findings analyzed: {total_findings}");
+    human_println!("   Verified as false positives: {total_verified} ({false_positive_rate:.0}%)");
 
     if high_needs_review + medium_needs_review == 0 {
-        println!();
+        human_println!();
         print_status(
             Color::Green,
             "✅ CONCLUSION: All findings verified as SAFE (false positives)",
@@ -916,7 +989,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
             "   No malicious activity detected. Project appears clean.",
         );
     } else {
-        println!();
+        human_println!();
         print_status(
             Color::Yellow,
             &format!(
@@ -929,7 +1002,7 @@ fn print_verification_summary(results: &ScanResults, _paranoid_mode: bool) {
             "   Review items marked without [VERIFIED SAFE] tags above.",
         );
     }
-    println!();
+    human_println!();
 }
 
 // Function: write_log_file
@@ -1027,6 +1100,13 @@ pub fn write_log_file(
         high_files.insert(crate::utils::normalize_path(&finding.file_path));
     }
 
+    // Registry-only detectors (HIGH risk)
+    for finding in results.additional_findings.values().flatten() {
+        if finding.risk_level == RiskLevel::High {
+            high_files.insert(crate::utils::normalize_path(&finding.file_path));
+        }
+    }
+
     // Write HIGH section
     writeln!(file, "# HIGH")?;
     for path in &high_files {
@@ -1084,6 +1164,13 @@ pub fn write_log_file(
         }
     }
 
+    // Registry-only detectors (MEDIUM risk)
+    for finding in results.additional_findings.values().flatten() {
+        if finding.risk_level == RiskLevel::Medium {
+            medium_files.insert(crate::utils::normalize_path(&finding.file_path));
+        }
+    }
+
     // Write MEDIUM section
     writeln!(file, "# MEDIUM")?;
     for path in &medium_files {
@@ -1110,6 +1197,13 @@ pub fn write_log_file(
         low_files.insert(crate::utils::normalize_path(&finding.file_path));
     }
 
+    // Registry-only detectors (LOW risk)
+    for finding in results.additional_findings.values().flatten() {
+        if finding.risk_level == RiskLevel::Low {
+            low_files.insert(crate::utils::normalize_path(&finding.file_path));
+        }
+    }
+
     // Write LOW section
     writeln!(file, "# LOW")?;
     for path in &low_files {
@@ -0,0 +1,578 @@
+// SARIF Output - Static Analysis Results Interchange Format 2.1.0
+// Purpose: Emit ScanResults for GitHub code scanning and other SARIF consumers
+//
+// Each finding category becomes a rule, each Finding a result. Locations are
+// relative to the scanned directory (uriBaseId SRCROOT).
+
+use crate::detectors::{registry, Finding, RiskLevel, ScanResults};
+use anyhow::{Context, Result};
+use serde_json::{json, Value};
+use std::fs;
+use std::path::Path;
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const INFORMATION_URI: &str = "https://github.com/gstrainovic/shai-hulud-detect-rust";
+
+/// Rule metadata for a finding category
+struct RuleInfo {
+    id: &'static str,
+    short: &'static str,
+    help: &'static str,
+    level: RiskLevel,
+}
+
+// Known finding categories in detector execution order
+const RULES: &[RuleInfo] = &[
+    RuleInfo {
+        id: "workflow",
+        short: "Known malicious workflow file",
+        help: "A shai-hulud-workflow.yml file was found. This workflow is dropped by the Shai-Hulud worm to exfiltrate secrets. Delete it and rotate all repository secrets.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "malicious_hash",
+        short: "File matches known malicious hash",
+        help: "The SHA-256 hash of this file matches a known Shai-Hulud payload. Remove the file, reinstall dependencies from a clean lockfile and rotate credentials.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "compromised_package",
+        short: "Compromised package version",
+        help: "package.json references an exact package version that was published by the Shai-Hulud worm. Pin a known-safe version and reinstall.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "lockfile_safe_version",
+        short: "Semver range admits a compromised version",
+        help: "A dependency range could resolve to a compromised version, but the lockfile pins a safe version. Keep the lockfile committed and avoid updating until the range is narrowed.",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "namespace_warning",
+        short: "Package from compromised namespace",
+        help: "The project depends on packages from an npm namespace in which some packages were compromised. Review the versions in use.",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "dependency_source",
+        short: "Dependency from git, a tarball URL or a local path",
+        help: "package.json declares a dependency from git, a raw tarball URL or a local path (file:, link:) instead of the registry. These sources skip registry integrity checks and can change without a version bump; confirm the source is trusted or switch to a registry version.",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "installed_packages",
+        short: "Compromised package installed in node_modules",
+        help: "A compromised package version is installed (node_modules, the pnpm store or a Yarn cache zip); its code may already have run in install scripts. Remove node_modules, pin a safe version, reinstall and rotate the credentials available on this machine.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "package_caches",
+        short: "Compromised package in a package manager cache",
+        help: "An npm, pnpm or yarn cache holds a compromised package version, a tarball with a known malicious integrity hash or a file with a known malicious hash. The next offline install reinstalls it; purge the cache entry named in the message.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "postinstall_hook",
+        short: "Suspicious postinstall hook",
+        help: "A postinstall script downloads or executes remote code (curl, wget, node -e, eval). Review the script before installing.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "suspicious_content",
+        short: "Suspicious content pattern",
+        help: "The file references webhook.site or a known Shai-Hulud exfiltration endpoint.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "crypto_malicious_functions",
+        short: "Known crypto theft function names",
+        help: "The file contains function names from the chalk/debug crypto-stealer payload (checkethereumw, runmask, newdlocal, _0x19ca67).",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "crypto_attacker_wallet",
+        short: "Known attacker wallet address",
+        help: "The file contains a cryptocurrency wallet address used by the chalk/debug attacker.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "crypto_phishing",
+        short: "Phishing domain reference",
+        help: "The file references a phishing domain used to steal npm credentials (npmjs.help).",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "crypto_xhr_hijack",
+        short: "XMLHttpRequest prototype modification",
+        help: "XMLHttpRequest.prototype.send is overridden together with crypto patterns, a technique used to swap wallet addresses in transit.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "crypto_xhr_framework",
+        short: "XMLHttpRequest modification in framework code",
+        help: "XMLHttpRequest.prototype.send is overridden in framework code together with crypto patterns. Usually legitimate, review to confirm.",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "crypto_xhr_simple",
+        short: "XMLHttpRequest prototype modification",
+        help: "XMLHttpRequest.prototype.send is overridden. Review whether the override is legitimate.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "crypto_obfuscation",
+        short: "JavaScript obfuscation",
+        help: "The file was produced by javascript-obfuscator, which Shai-Hulud payloads use to hide their logic.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "crypto_wallet_pattern",
+        short: "Ethereum wallet address pattern",
+        help: "The file contains Ethereum wallet addresses in a crypto-related context.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "trufflehog_binary",
+        short: "Trufflehog binary",
+        help: "A Trufflehog binary was found. Shai-Hulud downloads Trufflehog to harvest secrets from the machine.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "trufflehog_download",
+        short: "Dynamic Trufflehog download",
+        help: "The file downloads Trufflehog at runtime (November 2025 Shai-Hulud pattern).",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "trufflehog_credential_scan",
+        short: "Trufflehog credential harvesting",
+        help: "The file runs Trufflehog against environment variables or cloud credentials.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "credential_exfiltration",
+        short: "Credential exfiltration",
+        help: "Credential environment variables (AWS_ACCESS_KEY, GITHUB_TOKEN, NPM_TOKEN) are used together with network calls.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "trufflehog_reference",
+        short: "Trufflehog reference",
+        help: "The source code references Trufflehog. May be a legitimate security tool, review manually.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "credential_patterns",
+        short: "Credential scanning pattern",
+        help: "The file reads credential environment variables. May be legitimate, review manually.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "env_suspicious",
+        short: "Suspicious environment variable scanning",
+        help: "Environment variables are scanned together with harvesting or exfiltration keywords.",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "git_branch",
+        short: "Suspicious git branch",
+        help: "A git branch named shai-hulud exists. The worm pushes this branch to spread.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "shai_hulud_repo",
+        short: "Shai-Hulud repository",
+        help: "A repository name, remote or data file matches Shai-Hulud worm artifacts (repository named Shai-Hulud, -migration repositories, data.json with encoded secrets).",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "integrity",
+        short: "Lockfile integrity issue",
+        help: "A lockfile entry pins a compromised package version (or one covered by an OSV advisory). The message names the entry; pin a safe version and reinstall.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "lockfile_not_verified",
+        short: "Binary bun.lockb not verified",
+        help: "The binary bun.lockb was not checked for compromised versions, because decoding it runs the bun CLI. Re-run with --decode-bun-lockb or commit a text bun.lock (bun install --save-text-lockfile).",
+        level: RiskLevel::Low,
+    },
+    RuleInfo {
+        id: "lockfile_sources",
+        short: "Lockfile dependency from an unexpected source",
+        help: "A lockfile entry resolves from git, a raw tarball URL, a local path or a registry not configured in .npmrc / .yarnrc / .yarnrc.yml, has a tarball integrity hash of a known malicious package, or lost its integrity hash. These sources bypass the registry that was reviewed; confirm the entry is intended or reinstall it from the registry.",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "bun_setup_files",
+        short: "Fake Bun runtime installer",
+        help: "setup_bun.js installs a fake Bun runtime (November 2025 \"Second Coming\" attack). Remove it and the preinstall hook that runs it.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "bun_environment_files",
+        short: "Obfuscated credential harvesting payload",
+        help: "bun_environment.js is the 10MB+ obfuscated credential harvesting payload of the November 2025 attack.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "new_workflow_files",
+        short: "Malicious formatter workflow",
+        help: "formatter_<digits>.yml workflows are created by the November 2025 attack to dump repository secrets.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "actions_secrets_files",
+        short: "Exfiltrated secrets file",
+        help: "actionsSecrets.json contains secrets dumped by the November 2025 attack workflow.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "discussion_workflows",
+        short: "Discussion-triggered workflow",
+        help: "A GitHub Actions workflow triggered by discussion events (or running discussion bodies on self-hosted runners) allows arbitrary command execution.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "github_runners",
+        short: "Self-hosted runner installation",
+        help: "A GitHub Actions runner installation was found where malware installs a backdoor runner.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "destructive_patterns",
+        short: "Destructive payload pattern",
+        help: "The file contains data destruction patterns that Shai-Hulud runs when credential theft fails.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "preinstall_bun_patterns",
+        short: "Fake Bun preinstall hook",
+        help: "package.json runs \"node setup_bun.js\" in a preinstall hook.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "github_sha1hulud_runners",
+        short: "SHA1HULUD runner reference",
+        help: "A workflow references the SHA1HULUD self-hosted runner registered by the attack.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "second_coming_repos",
+        short: "\"Second Coming\" repository description",
+        help: "The repository description matches \"Sha1-Hulud: The Second Coming\", used for repositories holding exfiltrated data.",
+        level: RiskLevel::High,
+    },
+    RuleInfo {
+        id: "typosquatting",
+        short: "Possible typosquatting",
+        help: "A dependency name is close to a popular package name (character substitution, homoglyphs, namespace confusion).",
+        level: RiskLevel::Medium,
+    },
+    RuleInfo {
+        id: "network_exfiltration",
+        short: "Network exfiltration pattern",
+        help: "The file contains hardcoded IP addresses, suspicious domains or data exfiltration patterns.",
+        level: RiskLevel::Medium,
+    },
+];
+
+// Function: sarif_level
+// Purpose: Map RiskLevel to SARIF result level
+// Args: level - finding risk level
+// Returns: "error" (HIGH), "warning" (MEDIUM) or "note" (LOW)
+fn sarif_level(level: &RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::High => "error",
+        RiskLevel::Medium => "warning",
+        RiskLevel::Low => "note",
+    }
+}
+
+// GitHub code scanning severity (0.0 - 10.0) for rule properties
+fn security_severity(level: &RiskLevel) -> &'static str {
+    match level {
+        RiskLevel::High => "9.0",
+        RiskLevel::Medium => "5.5",
+        RiskLevel::Low => "2.0",
+    }
+}
+
+// Function: rule_name
+// Purpose: Convert a snake_case category to a PascalCase SARIF rule name
+fn rule_name(id: &str) -> String {
+    id.split('_')
+        .map(|part| {
+            let mut chars = part.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect::<String>()
+            })
+        })
+        .collect()
+}
+
+fn rule_json(id: &str, short: &str, help: &str, level: &RiskLevel) -> Value {
+    json!({
+        "id": id,
+        "name": rule_name(id),
+        "shortDescription": { "text": short },
+        "fullDescription": { "text": help },
+        "help": { "text": help },
+        "defaultConfiguration": { "level": sarif_level(level) },
+        "properties": {
+            "tags": ["security", "supply-chain", "shai-hulud"],
+            "security-severity": security_severity(level),
+        }
+    })
+}
+
+// Function: artifact_uri
+// Purpose: Build a SARIF artifact URI for a finding path
+// Args: path - finding file path, scan_dir - scanned directory
+// Returns: (uri, true if relative to SRCROOT), or None for non-file paths ("Namespace warning")
+fn artifact_uri(path: &Path, scan_dir: &Path) -> Option<(String, bool)> {
+    if let Ok(relative) = path.strip_prefix(scan_dir) {
+        let uri = crate::utils::normalize_path(relative);
+        return Some((percent_encode(&uri), true));
+    }
+    if path.is_absolute() {
+        let uri = crate::utils::normalize_path(path);
+        return Some((format!("file://{}", percent_encode(&uri)), false));
+    }
+    None
+}
+
+// Percent-encode characters that are not allowed unescaped in a URI path
+fn percent_encode(path: &str) -> String {
+    let mut encoded = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte.is_ascii_alphanumeric() || b"/-._~@+".contains(&byte) {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("%{byte:02X}"));
+        }
+    }
+    encoded
+}
+
+fn result_json(finding: &Finding, rule_index: usize, scan_dir: &Path) -> Value {
+    let mut properties = json!({
+        "category": finding.category,
+        "riskLevel": finding.risk_level,
+    });
+    if let Some(ref verification) = finding.verification {
+        properties["verification"] = json!(verification);
+    }
+
+    let mut result = json!({
+        "ruleId": finding.category,
+        "ruleIndex": rule_index,
+        "level": sarif_level(&finding.risk_level),
+        "message": { "text": finding.message },
+        "properties": properties,
+    });
+
+    if let Some((uri, relative)) = artifact_uri(&finding.file_path, scan_dir) {
+        let mut location = json!({ "uri": uri });
+        if relative {
+            location["uriBaseId"] = json!("SRCROOT");
+        }
+        result["locations"] = json!([{ "physicalLocation": { "artifactLocation": location } }]);
+    }
+
+    result
+}
+
+// Function: to_sarif
+// Purpose: Convert scan results into a SARIF 2.1.0 log
+// Args: results - scan results, scan_dir - scanned directory (SRCROOT)
+// Returns: SARIF log as JSON value
+pub fn to_sarif(results: &ScanResults, scan_dir: &Path) -> Value {
+    let mut rules: Vec<Value> = RULES
+        .iter()
+        .map(|r| rule_json(r.id, r.short, r.help, &r.level))
+        .collect();
+    let mut rule_ids: Vec<String> = RULES.iter().map(|r| r.id.to_string()).collect();
+
+    // Suppressed findings are kept as results with an accepted external suppression
+    let active = results
+        .buckets()
+        .into_iter()
+        .flat_map(|(key, findings)| findings.iter().map(move |finding| (key, finding, None)));
+    let suppressed = results
+        .suppressed
+        .iter()
+        .map(|s| (s.finding.category.as_str(), &s.finding, Some(s)));
+
+    let mut sarif_results = Vec::new();
+    for (key, finding, suppression) in active.chain(suppressed) {
+        let rule_index = if let Some(index) = rule_ids.iter().position(|id| *id == finding.category)
+        {
+            index
+        } else {
+            // Category from a detector without a built-in rule - describe it via the registry,
+            // at the detector's default severity so the rule level doesn't depend on scan order
+            let help = registry::describe(key).unwrap_or("Shai-Hulud detector finding");
+            let level = registry::default_severity(key).unwrap_or(finding.risk_level.clone());
+            rules.push(rule_json(&finding.category, help, help, &level));
+            rule_ids.push(finding.category.clone());
+            rule_ids.len() - 1
+        };
+        let mut result = result_json(finding, rule_index, scan_dir);
+        if let Some(suppressed) = suppression {
+            result["suppressions"] = json!([{
+                "kind": "external",
+                "status": "accepted",
+                "justification": format!("{} (owner: {})", suppressed.reason, suppressed.owner),
+            }]);
+        }
+        sarif_results.push(result);
+    }
+
+    let srcroot = format!(
+        "file://{}/",
+        percent_encode(crate::utils::normalize_path(scan_dir).trim_end_matches('/'))
+    );
+
+    json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": "shai-hulud-detector",
+                    "version": env!("CARGO_PKG_VERSION"),
+                    "informationUri": INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "originalUriBaseIds": {
+                "SRCROOT": { "uri": srcroot }
+            },
+            "results": sarif_results,
+        }]
+    })
+}
+
+// Function: write_sarif
+// Purpose: Write scan results as SARIF 2.1.0 to a file
+// Args: path - output file, results - scan results, scan_dir - scanned directory
+// Returns: Result indicating success
+pub fn write_sarif(path: &Path, results: &ScanResults, scan_dir: &Path) -> Result<()> {
+    let sarif = serde_json::to_string_pretty(&to_sarif(results, scan_dir))?;
+    fs::write(path, sarif).with_context(|| format!("Failed to write SARIF file {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::verification::{Confidence, VerificationMethod, VerificationStatus};
+    use std::path::PathBuf;
+
+    #[test]
+    fn test_rule_ids_unique() {
+        let mut ids: Vec<&str> = RULES.iter().map(|r| r.id).collect();
+        ids.sort_unstable();
+        ids.dedup();
+        assert_eq!(ids.len(), RULES.len());
+    }
+
+    #[test]
+    fn test_finding_to_sarif_result() {
+        let scan_dir = PathBuf::from("/project");
+        let mut results = ScanResults::new();
+        results.malicious_hashes.push(Finding::new(
+            PathBuf::from("/project/lib/bundle.js"),
+            "Hash: abc".to_string(),
+            RiskLevel::High,
+            "malicious_hash",
+        ));
+        let mut verified = Finding::new(
+            PathBuf::from("/project/node_modules/debug/package.json"),
+            "debug@4.4.2".to_string(),
+            RiskLevel::Medium,
+            "integrity",
+        );
+        verified.verification = Some(VerificationStatus::Verified {
+            reason: "Lockfile pins to safe version".to_string(),
+            confidence: Confidence::High,
+            method: VerificationMethod::LockfileMatch,
+        });
+        results.integrity_issues.push(verified);
+
+        let sarif = to_sarif(&results, &scan_dir);
+        let run = &sarif["runs"][0];
+        let sarif_results = run["results"].as_array().unwrap();
+
+        assert_eq!(sarif["version"], "2.1.0");
+        assert_eq!(sarif_results.len(), 2);
+        assert_eq!(sarif_results[0]["ruleId"], "malicious_hash");
+        assert_eq!(sarif_results[0]["level"], "error");
+        assert_eq!(
+            sarif_results[0]["locations"][0]["physicalLocation"]["artifactLocation"]["uri"],
+            "lib/bundle.js"
+        );
+        let rule_index = sarif_results[0]["ruleIndex"].as_u64().unwrap() as usize;
+        assert_eq!(
+            run["tool"]["driver"]["rules"][rule_index]["id"],
+            "malicious_hash"
+        );
+
+        assert_eq!(sarif_results[1]["level"], "warning");
+        assert_eq!(
+            sarif_results[1]["properties"]["verification"]["verified"]["confidence"],
+            "high"
+        );
+    }
+
+    #[test]
+    fn test_unknown_category_gets_rule() {
+        let mut results = ScanResults::new();
+        results.bucket_mut("future_detector").push(Finding::new(
+            PathBuf::from("Namespace warning"),
+            "something".to_string(),
+            RiskLevel::Low,
+            "future_category",
+        ));
+
+        let sarif = to_sarif(&results, Path::new("/project"));
+        let run = &sarif["runs"][0];
+        let result = &run["results"][0];
+        let rule_index = result["ruleIndex"].as_u64().unwrap() as usize;
+
+        assert_eq!(result["level"], "note");
+        assert!(result.get("locations").is_none());
+        assert_eq!(
+            run["tool"]["driver"]["rules"][rule_index]["id"],
+            "future_category"
+        );
+    }
+
+    #[test]
+    fn test_rule_level_does_not_depend_on_finding_order() {
+        let mut results = ScanResults::new();
+        for level in [RiskLevel::Low, RiskLevel::High] {
+            results.bucket_mut("lockfile_sources").push(Finding::new(
+                PathBuf::from("/project/package-lock.json"),
+                "entry".to_string(),
+                level,
+                "lockfile_sources",
+            ));
+        }
+
+        let sarif = to_sarif(&results, Path::new("/project"));
+        let run = &sarif["runs"][0];
+        let rule_index = run["results"][0]["ruleIndex"].as_u64().unwrap() as usize;
+        let rule = &run["tool"]["driver"]["rules"][rule_index];
+        assert_eq!(rule["id"], "lockfile_sources");
+        assert_eq!(rule["defaultConfiguration"]["level"], "warning");
+        assert_eq!(run["results"][1]["level"], "error");
+    }
+
+    #[test]
+    fn test_percent_encode() {
+        assert_eq!(percent_encode("a b/c.js"), "a%20b/c.js");
+        assert_eq!(percent_encode("@scope/pkg"), "@scope/pkg");
+    }
+}
@@ -0,0 +1,393 @@
+// Scanner - Public scan entry point
+// Purpose: Run all registered detectors against a directory and collect ScanResults
+//
+// The shai-hulud-detector binary is a thin wrapper around `scan()`; embedding
+// applications can call `scan()` / `scan_with_data()` directly.
+
+use crate::colors;
+use crate::data::{self, DetectionData};
+use crate::detectors::projects::{ProjectIndex, ProjectResolvers};
+use crate::detectors::{registry, ScanContext, ScanResults};
+use crate::feeds::{self, FeedOptions};
+use crate::file_index::FileIndex;
+use crate::utils;
+use anyhow::{bail, Result};
+use std::path::{Path, PathBuf};
+
+/// Options controlling a scan (mirrors the command line flags)
+#[derive(Debug, Clone)]
+pub struct ScanOptions {
+    /// Enable additional security checks (typosquatting, network patterns)
+    pub paranoid: bool,
+    /// Use lockfiles and installed packages to reduce false positives
+    pub verify: bool,
+    /// Number of threads for parallelized steps
+    pub parallelism: usize,
+    /// Report semver ranges that could resolve to compromised versions
+    pub check_semver_ranges: bool,
+    /// Report compromised versions installed in node_modules (pnpm store, nested copies)
+    pub inventory: bool,
+    /// Scan ~/.npm/_cacache, the pnpm store and the yarn caches for compromised packages
+    pub scan_caches: bool,
+    /// Only run these detector ids (empty = all)
+    pub only: Vec<String>,
+    /// Skip these detector ids
+    pub skip: Vec<String>,
+    /// Local compromised-packages.txt used when the GitHub download fails
+    pub packages_file: PathBuf,
+    /// Feed source and offline mode for the compromised package list
+    pub feeds: FeedOptions,
+    /// Print nothing while scanning (status lines, progress). On by default so
+    /// embedding applications keep their stdout; the binary turns it off
+    pub quiet: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            paranoid: false,
+            verify: false,
+            parallelism: 4,
+            check_semver_ranges: false,
+            inventory: false,
+            scan_caches: false,
+            only: Vec::new(),
+            skip: Vec::new(),
+            packages_file: PathBuf::from(feeds::LOCAL_CACHE_FILE),
+            feeds: FeedOptions::default(),
+            quiet: true,
+        }
+    }
+}
+
+// Function: scan
+// Purpose: Load detection data and scan a directory with all selected detectors
+// Args: scan_dir - directory to scan, options - scan options
+// Returns: ScanResults with findings from every detector
+pub fn scan<P: AsRef<Path>>(scan_dir: P, options: &ScanOptions) -> Result<ScanResults> {
+    let _quiet = QuietScope::new(options.quiet);
+
+    // Reject unknown detector ids before any data is loaded
+    registry::select_detectors(&options.only, &options.skip)?;
+
+    let data = data::load_detection_data(&options.packages_file, &options.feeds)?;
+
+    scan_with_data(scan_dir, options, &data)
+}
+
+// Function: scan_with_data
+// Purpose: Scan a directory with already loaded detection data
+// Args: scan_dir - directory to scan, options - scan options,
+//       data - detection data from load_detection_data()
+// Returns: ScanResults with findings from every detector
+pub fn scan_with_data<P: AsRef<Path>>(
+    scan_dir: P,
+    options: &ScanOptions,
+    data: &DetectionData,
+) -> Result<ScanResults> {
+    let _quiet = QuietScope::new(options.quiet);
+
+    let scan_dir = scan_dir.as_ref();
+    if !scan_dir.is_dir() {
+        bail!("Error: '{}' is not a directory.", scan_dir.display());
+    }
+    // Absolute path for bash-identical output
+    let scan_dir = scan_dir.canonicalize()?;
+
+    let detectors = registry::select_detectors(&options.only, &options.skip)?;
+
+    colors::print_status(
+        colors::Color::Green,
+        "Starting Shai-Hulud detection scan...",
+    );
+
+    let paranoid_msg = if options.paranoid {
+        format!(
+            "Scanning directory: {} (with paranoid mode enabled)",
+            utils::normalize_path(&scan_dir)
+        )
+    } else {
+        format!("Scanning directory: {}", utils::normalize_path(&scan_dir))
+    };
+    colors::print_status(colors::Color::Blue, &paranoid_msg);
+    human_println!();
+
+    // Create results container
+    let mut results = ScanResults::new();
+    results.feeds.clone_from(&data.feeds);
+
+    // Walk the scan directory once - all detectors query this shared index
+    let index = FileIndex::build(&scan_dir, options.parallelism);
+
+    // Every file belongs to its nearest lockfile / workspace root; with --verify each
+    // project's resolvers are loaded on first use
+    let projects = ProjectIndex::build(&index);
+    let mut resolvers = options
+        .verify
+        .then(|| ProjectResolvers::new(projects.clone(), &scan_dir));
+
+    // Naming an opt-in detector in --only enables it (it would find nothing otherwise)
+    let named = |id: &str| options.only.iter().any(|only| only == id);
+
+    let mut ctx = ScanContext {
+        index: &index,
+        compromised_packages: &data.compromised_packages,
+        malicious_hashes: &data.malicious_hashes,
+        advisories: &data.advisories,
+        iocs: &data.iocs,
+        parallelism: options.parallelism,
+        check_semver_ranges: options.check_semver_ranges || named("semver_ranges"),
+        inventory: options.inventory || named("installed_packages"),
+        scan_caches: options.scan_caches || named("package_caches"),
+        resolvers: resolvers.as_mut(),
+    };
+
+    // Run all registered detectors (registry keeps bash function execution order)
+    // Additional security checks (typosquatting, network) only run in paranoid mode
+    for detector in &detectors {
+        if detector.paranoid_only() && !options.paranoid {
+            continue;
+        }
+        let mut findings = detector.run(&mut ctx);
+        for finding in &mut findings {
+            finding.project_root = projects.project_root(&finding.file_path);
+        }
+        results.record(detector.as_ref(), findings);
+    }
+
+    // BASH EXACT: Apply namespace warning logic - only include in results if they would be shown
+    // Bash shows namespace warnings in detail only when total_issues == 0 OR total_issues < 5
+    let total_issues = results.high_risk_count() + results.medium_risk_count(options.paranoid);
+    if total_issues >= 5 {
+        // Store count for bash compatibility before filtering
+        results.suppressed_namespace_count = results.namespace_warnings.len();
+        // Too many critical issues - don't include namespace warnings in detailed output/JSON
+        results.namespace_warnings = Vec::new();
+    }
+    // If total_issues == 0 or < 5, keep namespace warnings as-is
+
+    Ok(results)
+}
+
+// Helper: Apply ScanOptions::quiet while a scan runs and restore the previous setting
+struct QuietScope(bool);
+
+impl QuietScope {
+    fn new(quiet: bool) -> Self {
+        Self(colors::set_quiet(quiet))
+    }
+}
+
+impl Drop for QuietScope {
+    fn drop(&mut self) {
+        colors::set_quiet(self.0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::data::CompromisedPackage;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_scan_with_data_collects_findings() {
+        let temp = TempDir::new().unwrap();
+        let workflows_dir = temp.path().join(".github/workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+        fs::write(workflows_dir.join("shai-hulud-workflow.yml"), "x").unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"dependencies": {"@ctrl/tinycolor": "4.1.1"}}"#,
+        )
+        .unwrap();
+
+        let data = DetectionData {
+            compromised_packages: [CompromisedPackage::from_line("@ctrl/tinycolor:4.1.1").unwrap()]
+                .into_iter()
+                .collect(),
+            ..DetectionData::default()
+        };
+
+        let results = scan_with_data(temp.path(), &ScanOptions::default(), &data).unwrap();
+
+        assert_eq!(results.workflow_files.len(), 1);
+        assert_eq!(results.compromised_found.len(), 1);
+        assert!(results.high_risk_count() >= 2);
+    }
+
+    #[test]
+    fn test_scan_respects_only() {
+        let temp = TempDir::new().unwrap();
+        let workflows_dir = temp.path().join(".github/workflows");
+        fs::create_dir_all(&workflows_dir).unwrap();
+        fs::write(workflows_dir.join("shai-hulud-workflow.yml"), "x").unwrap();
+
+        let options = ScanOptions {
+            only: vec!["malicious_hashes".to_string()],
+            ..ScanOptions::default()
+        };
+        let results = scan_with_data(temp.path(), &options, &DetectionData::default()).unwrap();
+
+        assert!(results.workflow_files.is_empty());
+    }
+
+    #[test]
+    fn test_only_enables_opt_in_detector() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"dependencies": {"@ctrl/tinycolor": "^4.1.0"}}"#,
+        )
+        .unwrap();
+        let data = DetectionData {
+            compromised_packages: [CompromisedPackage::from_line("@ctrl/tinycolor:4.1.1").unwrap()]
+                .into_iter()
+                .collect(),
+            ..DetectionData::default()
+        };
+
+        let options = ScanOptions {
+            only: vec!["semver_ranges".to_string()],
+            ..ScanOptions::default()
+        };
+        let results = scan_with_data(temp.path(), &options, &data).unwrap();
+
+        assert_eq!(results.lockfile_safe_versions.len(), 1);
+    }
+
+    #[test]
+    fn test_osv_advisory_ranges_and_ids_in_findings() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("package.json"),
+            r#"{"dependencies": {"evil-pkg": "2.3.4", "safe-pkg": "1.0.0"}}"#,
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("package-lock.json"),
+            r#"{"packages": {"node_modules/evil-pkg": {"version": "2.0.0"}}}"#,
+        )
+        .unwrap();
+
+        let mut advisories = crate::osv::Advisories::default();
+        advisories.extend(
+            crate::osv::Advisories::parse(
+                r#"{"id": "MAL-2025-0042", "aliases": ["GHSA-xxxx-yyyy-zzzz"],
+                    "affected": [{"package": {"ecosystem": "npm", "name": "evil-pkg"},
+                                  "ranges": [{"type": "SEMVER", "events": [{"introduced": "2.0.0"}]}]}]}"#,
+            )
+            .unwrap(),
+        );
+        let data = DetectionData {
+            advisories,
+            ..DetectionData::default()
+        };
+
+        let results = scan_with_data(temp.path(), &ScanOptions::default(), &data).unwrap();
+
+        assert_eq!(results.compromised_found.len(), 1);
+        assert_eq!(
+            results.compromised_found[0].message,
+            "evil-pkg@2.3.4 [MAL-2025-0042 (GHSA-xxxx-yyyy-zzzz)]"
+        );
+        assert!(results.integrity_issues.iter().any(|f| f
+            .message
+            .ends_with(
+                "evil-pkg@2.0.0 (lockfile entry: node_modules/evil-pkg) [MAL-2025-0042 (GHSA-xxxx-yyyy-zzzz)]"
+            )));
+    }
+
+    #[test]
+    fn test_bun_lockfile_compromised_versions_in_integrity() {
+        let temp = TempDir::new().unwrap();
+        fs::write(
+            temp.path().join("bun.lock"),
+            r#"{
+  "lockfileVersion": 1,
+  "packages": {
+    "ngx-bootstrap": ["ngx-bootstrap@18.1.4", "", {}, "sha512-aaa"],
+    "left/ngx-bootstrap": ["ngx-bootstrap@19.0.3", "", {}, "sha512-bbb"],
+  },
+}"#,
+        )
+        .unwrap();
+        let data = DetectionData {
+            compromised_packages: [CompromisedPackage::from_line("ngx-bootstrap:19.0.3").unwrap()]
+                .into_iter()
+                .collect(),
+            ..DetectionData::default()
+        };
+
+        let results = scan_with_data(temp.path(), &ScanOptions::default(), &data).unwrap();
+
+        let messages: Vec<_> = results
+            .integrity_issues
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect();
+        assert_eq!(
+            messages,
+            vec!["Compromised package in lockfile: ngx-bootstrap@19.0.3 (lockfile entry: left/ngx-bootstrap)"]
+        );
+    }
+
+    #[test]
+    fn test_yarn_and_pnpm_lockfiles_matched_structurally() {
+        let temp = TempDir::new().unwrap();
+        for dir in ["classic", "berry", "pnpm"] {
+            fs::create_dir(temp.path().join(dir)).unwrap();
+        }
+        fs::write(
+            temp.path().join("classic/yarn.lock"),
+            "# yarn lockfile v1\n\n\"ngx@npm:ngx-bootstrap@^19.0.0\":\n  version \"19.0.3\"\n",
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("berry/yarn.lock"),
+            r#"__metadata:
+  version: 8
+
+"ngx-bootstrap@npm:^19.0.0":
+  version: 19.0.3
+  resolution: "ngx-bootstrap@npm:19.0.3"
+"#,
+        )
+        .unwrap();
+        fs::write(
+            temp.path().join("pnpm/pnpm-lock.yaml"),
+            r#"lockfileVersion: '9.0'
+packages:
+  ngx-bootstrap@19.0.3:
+    resolution: {integrity: sha512-aaa}
+  ngx-bootstrap@18.1.4:
+    resolution: {integrity: sha512-bbb}
+"#,
+        )
+        .unwrap();
+        let data = DetectionData {
+            compromised_packages: [CompromisedPackage::from_line("ngx-bootstrap:19.0.3").unwrap()]
+                .into_iter()
+                .collect(),
+            ..DetectionData::default()
+        };
+
+        let results = scan_with_data(temp.path(), &ScanOptions::default(), &data).unwrap();
+
+        let mut messages: Vec<_> = results
+            .integrity_issues
+            .iter()
+            .map(|f| f.message.as_str())
+            .collect();
+        messages.sort_unstable();
+        assert_eq!(
+            messages,
+            vec![
+                "Compromised package in lockfile: ngx-bootstrap@19.0.3 (lockfile entry: ngx-bootstrap@19.0.3)",
+                "Compromised package in lockfile: ngx-bootstrap@19.0.3 (lockfile entry: ngx-bootstrap@npm:^19.0.0)",
+                "Compromised package in lockfile: ngx-bootstrap@19.0.3 (lockfile entry: ngx@npm:ngx-bootstrap@^19.0.0)",
+            ]
+        );
+    }
+}
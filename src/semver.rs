@@ -1,192 +1,896 @@
 //! Semantic versioning support for package version matching
-//! Port of `semver_bash` from the original shell script
-//! Origin: <https://github.com/cloudflare/semver_bash/blob/6cc9ce10/semver.sh>
+//! node-semver compatible versions, ranges and matching (npm's rules, non-loose mode)
+//! Origin: <https://github.com/npm/node-semver> (range grammar and test vectors)
+//!
+//! Range grammar (as in node-semver's README):
+//!   range-set  ::= range ( '||' range )*
+//!   range      ::= hyphen | simple ( ' ' simple )* | ''
+//!   hyphen     ::= partial ' - ' partial
+//!   simple     ::= primitive | partial | tilde | caret
+//!   primitive  ::= ( '<' | '>' | '>=' | '<=' | '=' ) partial
+//!   partial    ::= xr ( '.' xr ( '.' xr qualifier? )? )?
+//!   xr         ::= 'x' | 'X' | '*' | nr
+//!
+//! Every range is desugared into comparator sets (`^1.2` -> `>=1.2.0 <2.0.0-0`).
+//! A prerelease version only satisfies a set that has a comparator with a prerelease
+//! on the same major.minor.patch (`1.2.3-beta` is in `>=1.2.3-alpha`, not in `^1.0.0`).
 
-#![allow(dead_code)]
-
-use regex::Regex;
 use std::cmp::Ordering;
-use std::sync::LazyLock;
+use std::fmt;
+
+/// Prerelease identifier: numeric identifiers sort numerically and below alphanumeric ones
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
 
-static SEMVER_RE: LazyLock<Regex> =
-    LazyLock::new(|| Regex::new(r"[^0-9]*([0-9]+)\.([0-9]+)\.([0-9]+)([0-9A-Za-z\-]*)").unwrap());
+impl fmt::Display for Identifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Numeric(n) => write!(f, "{n}"),
+            Self::AlphaNumeric(s) => f.write_str(s),
+        }
+    }
+}
 
-/// Parsed semantic version components
+/// An exact version ("1.2.3-beta.1+build"); build metadata is ignored for precedence
 #[derive(Debug, Clone, PartialEq, Eq)]
-pub struct SemVer {
-    pub major: u32,
-    pub minor: u32,
-    pub patch: u32,
-    pub special: String,
-}
-
-impl SemVer {
-    // Function: semverParseInto (as parse method)
-    // Purpose: Parse semantic version string into major, minor, patch, and special components
-    // Args: version_string
-    // Modifies: None
-    // Returns: Parsed SemVer struct or None
-    // Origin: https://github.com/cloudflare/semver_bash/blob/6cc9ce10/semver.sh
-    pub fn parse(version: &str) -> Option<Self> {
-        let version = version.trim();
-        let caps = SEMVER_RE.captures(version)?;
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub prerelease: Vec<Identifier>,
+    pub build: Vec<String>,
+}
 
-        let major = caps.get(1)?.as_str().parse().ok()?;
-        let minor = caps.get(2)?.as_str().parse().ok()?;
-        let patch = caps.get(3)?.as_str().parse().ok()?;
-        let special = caps.get(4).map_or("", |m| m.as_str()).to_string();
+impl Version {
+    pub fn new(major: u64, minor: u64, patch: u64) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+            prerelease: Vec::new(),
+            build: Vec::new(),
+        }
+    }
 
-        Some(SemVer {
+    // Function: parse
+    // Purpose: Parse an exact version (leading "v" / "=" and surrounding whitespace allowed)
+    // Args: version - e.g. "1.2.3", "v1.2.3-beta.1+build.5"
+    // Returns: Version, None for partial versions, ranges, tags and leading zeros ("01.2.3")
+    pub fn parse(version: &str) -> Option<Self> {
+        let version = version.trim().trim_start_matches(['v', '=', ' ', '\t']);
+        let (version, build) = match version.split_once('+') {
+            Some((version, build)) => (version, parse_build(build)?),
+            None => (version, Vec::new()),
+        };
+        let (core, prerelease) = match version.split_once('-') {
+            Some((core, prerelease)) => (core, parse_prerelease(prerelease)?),
+            None => (version, Vec::new()),
+        };
+        let mut parts = core.split('.').map(parse_numeric);
+        let (major, minor, patch) = (parts.next()??, parts.next()??, parts.next()??);
+        if parts.next().is_some() {
+            return None;
+        }
+        Some(Self {
             major,
             minor,
             patch,
-            special,
+            prerelease,
+            build,
         })
     }
+
+    pub fn is_prerelease(&self) -> bool {
+        !self.prerelease.is_empty()
+    }
+
+    /// Same major.minor.patch (prerelease and build ignored)
+    fn same_core(&self, other: &Self) -> bool {
+        (self.major, self.minor, self.patch) == (other.major, other.minor, other.patch)
+    }
+
+    /// Lowest possible version with this core ("1.2.3-0")
+    fn with_zero_prerelease(mut self) -> Self {
+        self.prerelease = vec![Identifier::Numeric(0)];
+        self
+    }
 }
 
-// Function: semver_match
-// Purpose: Check if version matches semver pattern with caret (^), tilde (~), wildcard (x/X), or exact matching
-// Args: test_subject (version to test), test_pattern (pattern like "^1.0.0", "~1.1.0", "4.x", "1.2.X")
-// Modifies: None
-// Returns: true for match, false for no match (supports || for multi-pattern matching)
-// Examples: "1.1.2" matches "^1.0.0", "~1.1.0", "1.x", "*" but not "^2.0.0" or "~1.2.0"
-pub fn semver_match(test_subject: &str, test_pattern: &str) -> bool {
-    // Always matches
-    if test_pattern == "*" {
-        return true;
+impl Ord for Version {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| {
+                // A release outranks its prereleases; otherwise compare identifier by identifier
+                match (self.prerelease.is_empty(), other.prerelease.is_empty()) {
+                    (true, true) => Ordering::Equal,
+                    (true, false) => Ordering::Greater,
+                    (false, true) => Ordering::Less,
+                    (false, false) => self.prerelease.cmp(&other.prerelease),
+                }
+            })
     }
+}
 
-    // Destructure subject
-    let Some(subject) = SemVer::parse(test_subject) else {
-        return false;
-    };
+impl PartialOrd for Version {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
 
-    // Handle multi-variant patterns (splits '||' into individual patterns)
-    for pattern in test_pattern.split("||") {
-        let pattern = pattern.trim();
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        if !self.prerelease.is_empty() {
+            let ids: Vec<String> = self.prerelease.iter().map(ToString::to_string).collect();
+            write!(f, "-{}", ids.join("."))?;
+        }
+        Ok(())
+    }
+}
+
+// Helper: Numeric identifier without leading zeros
+fn parse_numeric(part: &str) -> Option<u64> {
+    let valid = !part.is_empty()
+        && part.bytes().all(|b| b.is_ascii_digit())
+        && (part == "0" || !part.starts_with('0'));
+    valid.then(|| part.parse().ok()).flatten()
+}
+
+// Helper: Dot-separated identifiers of [0-9A-Za-z-]
+fn identifiers(text: &str) -> Option<impl Iterator<Item = &str>> {
+    let valid = text
+        .split('.')
+        .all(|id| !id.is_empty() && id.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-'));
+    valid.then(|| text.split('.'))
+}
+
+// Helper: "beta.1" -> [beta, 1] (numeric identifiers must not have leading zeros)
+fn parse_prerelease(text: &str) -> Option<Vec<Identifier>> {
+    identifiers(text)?
+        .map(|id| {
+            if id.bytes().all(|b| b.is_ascii_digit()) {
+                parse_numeric(id).map(Identifier::Numeric)
+            } else {
+                Some(Identifier::AlphaNumeric(id.to_string()))
+            }
+        })
+        .collect()
+}
+
+// Helper: "build.5" -> ["build", "5"] (leading zeros allowed)
+fn parse_build(text: &str) -> Option<Vec<String>> {
+    Some(identifiers(text)?.map(str::to_string).collect())
+}
 
-        // Always matches
-        if pattern == "*" {
-            return true;
+/// Comparison operator of a comparator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Operator {
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Eq,
+}
+
+impl Operator {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Eq => "",
         }
+    }
+}
 
-        if let Some(stripped) = pattern.strip_prefix('^') {
-            // Caret: Major must match, minor.patch >= pattern
-            let Some(pattern_ver) = SemVer::parse(stripped) else {
-                continue;
-            };
+/// A single comparator such as ">=1.2.3" (an empty operator means "=")
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    pub operator: Operator,
+    pub version: Version,
+}
 
-            if subject.major != pattern_ver.major {
-                continue;
+impl Comparator {
+    fn new(operator: Operator, version: Version) -> Self {
+        Self { operator, version }
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        let ordering = version.cmp(&self.version);
+        match self.operator {
+            Operator::Lt => ordering == Ordering::Less,
+            Operator::Le => ordering != Ordering::Greater,
+            Operator::Gt => ordering == Ordering::Greater,
+            Operator::Ge => ordering != Ordering::Less,
+            Operator::Eq => ordering == Ordering::Equal,
+        }
+    }
+}
+
+impl fmt::Display for Comparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.operator.as_str(), self.version)
+    }
+}
+
+/// A range: comparator sets joined by "||"; an empty set matches every version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Range {
+    pub sets: Vec<Vec<Comparator>>,
+}
+
+impl Range {
+    // Function: parse
+    // Purpose: Parse and desugar a node-semver range
+    // Args: range - e.g. "^1.2.3", ">=1.2.0 <2.0.0", "1.2 - 2", "1.x || >=3.0.0-beta"
+    // Returns: Range, None if any part is not valid range syntax (dist-tags, URLs, aliases)
+    pub fn parse(range: &str) -> Option<Self> {
+        let mut sets = range
+            .split("||")
+            .map(|set| {
+                let set = parse_comparator_set(set)?;
+                // A set containing "<0.0.0-0" can never match
+                let null = null_comparator();
+                Some(if set.contains(&null) { vec![null] } else { set })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        // Like node-semver: "*" swallows the other alternatives, impossible sets are dropped
+        if sets.iter().any(Vec::is_empty) {
+            sets = vec![Vec::new()];
+        } else if sets.len() > 1 {
+            let null = null_comparator();
+            let possible: Vec<_> = sets.iter().filter(|s| s[0] != null).cloned().collect();
+            if !possible.is_empty() {
+                sets = possible;
+            } else {
+                sets.truncate(1);
             }
+        }
+        Some(Self { sets })
+    }
 
-            match subject.minor.cmp(&pattern_ver.minor) {
-                Ordering::Greater => return true,
-                Ordering::Less => (),
-                Ordering::Equal => {
-                    if subject.patch >= pattern_ver.patch {
-                        return true;
-                    }
+    // Function: satisfies
+    // Purpose: Check if a version is in the range (node-semver `satisfies`)
+    // Args: version - exact version
+    // Returns: true if any comparator set admits the version
+    pub fn satisfies(&self, version: &Version) -> bool {
+        self.sets.iter().any(|set| set_satisfies(set, version))
+    }
+
+    // Function: at_least
+    // Purpose: Restrict the range to versions >= floor
+    // Args: floor - lowest version to keep
+    // Returns: Range with one ">=lower <upper" set per alternative that still admits a
+    //          version (None if none does), e.g. "^4.1.0" at least 4.1.3 -> ">=4.1.3 <5.0.0-0"
+    pub fn at_least(&self, floor: &Version) -> Option<Self> {
+        let sets: Vec<_> = self
+            .sets
+            .iter()
+            .filter_map(|set| {
+                let (mut lower, upper) = set_bounds(set);
+                if lower.as_ref().is_none_or(|(version, _)| version < floor) {
+                    lower = Some((floor.clone(), true));
                 }
-            }
-        } else if let Some(stripped) = pattern.strip_prefix('~') {
-            // Tilde: Major+minor must match, patch >= pattern
-            let Some(pattern_ver) = SemVer::parse(stripped) else {
-                continue;
-            };
-
-            if subject.major == pattern_ver.major
-                && subject.minor == pattern_ver.minor
-                && subject.patch >= pattern_ver.patch
-            {
-                return true;
-            }
-        } else if pattern.contains('x') || pattern.contains('X') {
-            // Wildcard pattern (4.x, 1.2.x, 4.X, 1.2.X, x.x.x, etc.)
-            // Parse pattern components, handling 'x'/'X' wildcards specially
-            let pattern_parts: Vec<&str> = pattern.split('.').collect();
-            let subject_parts = [
-                subject.major.to_string(),
-                subject.minor.to_string(),
-                subject.patch.to_string(),
-            ];
-
-            // Check each component, skip comparison for 'x'/'X' wildcards
-            let mut matches = true;
-            for i in 0..3 {
-                if i < pattern_parts.len() && i < subject_parts.len() {
-                    let pattern_part = pattern_parts[i];
-                    let subject_part = &subject_parts[i];
-
-                    // Skip wildcard components (both lowercase x and uppercase X)
-                    if pattern_part == "x" || pattern_part == "X" {
-                        continue;
-                    }
-
-                    // Extract numeric part (remove any non-numeric suffix)
-                    let pattern_num = pattern_part
-                        .chars()
-                        .take_while(char::is_ascii_digit)
-                        .collect::<String>();
-                    let subject_num = subject_part
-                        .chars()
-                        .take_while(char::is_ascii_digit)
-                        .collect::<String>();
-
-                    // Compare numeric parts
-                    if subject_num != pattern_num {
-                        matches = false;
-                        break;
-                    }
+                bounds_to_set(lower, upper)
+            })
+            .collect();
+        (!sets.is_empty()).then_some(Self { sets })
+    }
+
+    /// True for a single exact version ("1.2.3", "=1.2.3", "v1.2.3")
+    pub fn is_exact(&self) -> bool {
+        matches!(self.sets.as_slice(), [set] if matches!(set.as_slice(), [c] if c.operator == Operator::Eq))
+    }
+}
+
+impl fmt::Display for Range {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let sets: Vec<String> = self
+            .sets
+            .iter()
+            .map(|set| {
+                if set.is_empty() {
+                    "*".to_string()
+                } else {
+                    let comparators: Vec<String> = set.iter().map(ToString::to_string).collect();
+                    comparators.join(" ")
                 }
-            }
+            })
+            .collect();
+        f.write_str(&sets.join("||"))
+    }
+}
+
+/// One side of a comparator set: (version, inclusive), None if unbounded
+type Bound = Option<(Version, bool)>;
+
+// Helper: Tightest lower and upper bound of a comparator set
+fn set_bounds(set: &[Comparator]) -> (Bound, Bound) {
+    let (mut lower, mut upper): (Bound, Bound) = (None, None);
+    for comparator in set {
+        let version = comparator.version.clone();
+        let (is_lower, is_upper, inclusive) = match comparator.operator {
+            Operator::Gt => (true, false, false),
+            Operator::Ge => (true, false, true),
+            Operator::Lt => (false, true, false),
+            Operator::Le => (false, true, true),
+            Operator::Eq => (true, true, true),
+        };
+        // Higher lower bounds and lower upper bounds win; exclusive wins a tie
+        if is_lower
+            && lower
+                .as_ref()
+                .is_none_or(|(v, inc)| version > *v || (version == *v && *inc && !inclusive))
+        {
+            lower = Some((version.clone(), inclusive));
+        }
+        if is_upper
+            && upper
+                .as_ref()
+                .is_none_or(|(v, inc)| version < *v || (version == *v && *inc && !inclusive))
+        {
+            upper = Some((version, inclusive));
+        }
+    }
+    (lower, upper)
+}
 
-            if matches {
-                return true;
+// Helper: Comparators for bounds, None if no version lies between them
+fn bounds_to_set(lower: Bound, upper: Bound) -> Option<Vec<Comparator>> {
+    if let (Some((low, low_inclusive)), Some((high, high_inclusive))) = (&lower, &upper) {
+        if low > high || (low == high && !(*low_inclusive && *high_inclusive)) {
+            return None;
+        }
+        if low == high {
+            return Some(vec![Comparator::new(Operator::Eq, low.clone())]);
+        }
+    }
+    let lower = lower.map(|(version, inclusive)| {
+        Comparator::new(
+            if inclusive {
+                Operator::Ge
+            } else {
+                Operator::Gt
+            },
+            version,
+        )
+    });
+    let upper = upper.map(|(version, inclusive)| {
+        Comparator::new(
+            if inclusive {
+                Operator::Le
+            } else {
+                Operator::Lt
+            },
+            version,
+        )
+    });
+    Some(lower.into_iter().chain(upper).collect())
+}
+
+// Helper: Comparator that matches nothing ("<0.0.0-0", as node-semver writes it)
+fn null_comparator() -> Comparator {
+    Comparator::new(Operator::Lt, Version::new(0, 0, 0).with_zero_prerelease())
+}
+
+// Helper: All comparators match, with npm's prerelease rule
+fn set_satisfies(set: &[Comparator], version: &Version) -> bool {
+    if !set.iter().all(|comparator| comparator.matches(version)) {
+        return false;
+    }
+    // Prereleases only match a set that opts in on the same major.minor.patch
+    !version.is_prerelease()
+        || set
+            .iter()
+            .any(|c| c.version.is_prerelease() && c.version.same_core(version))
+}
+
+/// A partial version from a range: None components are wildcards (x, X, *, missing)
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Partial {
+    major: Option<u64>,
+    minor: Option<u64>,
+    patch: Option<u64>,
+    prerelease: Vec<Identifier>,
+}
+
+impl Partial {
+    // Helper: "1", "1.2.x", "v1.2.3-beta+build", "*"
+    fn parse(text: &str) -> Option<Self> {
+        let text = text.trim_start_matches(['v', '=']);
+        let text = text
+            .split_once('+')
+            .map_or(Some(text), |(text, build)| parse_build(build).map(|_| text))?;
+        let (core, prerelease) = match text.split_once('-') {
+            Some((core, prerelease)) => (core, parse_prerelease(prerelease)?),
+            None => (text, Vec::new()),
+        };
+        let mut parts = core.split('.');
+        let mut component = |required: bool| -> Option<Option<u64>> {
+            match parts.next() {
+                None if !required => Some(None),
+                Some("x" | "X" | "*") => Some(None),
+                Some(part) => parse_numeric(part).map(Some),
+                None => None,
             }
-        } else {
-            // Exact match
-            let Some(pattern_ver) = SemVer::parse(pattern) else {
-                continue;
-            };
-
-            if subject.major == pattern_ver.major
-                && subject.minor == pattern_ver.minor
-                && subject.patch == pattern_ver.patch
-                && subject.special == pattern_ver.special
-            {
-                return true;
+        };
+        let major = component(true)?;
+        let minor = component(false)?;
+        let patch = component(false)?;
+        if parts.next().is_some() {
+            return None;
+        }
+        // Components after a wildcard are wildcards too ("1.x.3" == "1.x"); a prerelease
+        // needs a full version
+        let (minor, patch) = match (major, minor) {
+            (None, _) => (None, None),
+            (_, None) => (None, None),
+            _ => (minor, patch),
+        };
+        if patch.is_none() && !prerelease.is_empty() {
+            return None;
+        }
+        Some(Self {
+            major,
+            minor,
+            patch,
+            prerelease,
+        })
+    }
+
+    /// Lowest version in the partial ("1.2" -> 1.2.0)
+    fn floor(&self) -> Version {
+        Version {
+            prerelease: self.prerelease.clone(),
+            ..Version::new(
+                self.major.unwrap_or(0),
+                self.minor.unwrap_or(0),
+                self.patch.unwrap_or(0),
+            )
+        }
+    }
+
+    /// First version after the partial ("1" -> 2.0.0-0, "1.2" -> 1.3.0-0), None if exact
+    fn ceiling(&self) -> Option<Version> {
+        match (self.major, self.minor, self.patch) {
+            (Some(major), None, _) => Some(Version::new(major + 1, 0, 0).with_zero_prerelease()),
+            (Some(major), Some(minor), None) => {
+                Some(Version::new(major, minor + 1, 0).with_zero_prerelease())
             }
+            _ => None,
+        }
+    }
+}
+
+// Helper: One "||" alternative
+fn parse_comparator_set(text: &str) -> Option<Vec<Comparator>> {
+    let text = text.trim();
+    if let Some(set) = parse_hyphen_range(text) {
+        return set;
+    }
+
+    // Operators may be separated from their version by whitespace (">= 1.2.3", "~ 1.2")
+    let mut tokens = Vec::new();
+    let mut pending = String::new();
+    for token in text.split_whitespace() {
+        pending.push_str(token);
+        if !matches!(token, "<" | "<=" | ">" | ">=" | "=" | "~" | "~>" | "^") {
+            tokens.push(std::mem::take(&mut pending));
+        }
+    }
+    if !pending.is_empty() {
+        return None;
+    }
+
+    let mut set = Vec::new();
+    for token in tokens {
+        set.extend(desugar(&token)?);
+    }
+    Some(set)
+}
+
+// Helper: "1.2.3 - 2.3.4" -> >=1.2.3 <=2.3.4 (partials widen: "1.2 - 2" -> >=1.2.0 <3.0.0-0)
+// Returns: None if `text` is not a hyphen range, Some(None) if it is one but invalid
+fn parse_hyphen_range(text: &str) -> Option<Option<Vec<Comparator>>> {
+    let tokens: Vec<&str> = text.split_whitespace().collect();
+    let [from, "-", to] = tokens.as_slice() else {
+        return None;
+    };
+    let (Some(from), Some(to)) = (Partial::parse(from), Partial::parse(to)) else {
+        return Some(None);
+    };
+    let mut set = Vec::new();
+    if from.major.is_some() {
+        set.push(Comparator::new(Operator::Ge, from.floor()));
+    }
+    if to.major.is_some() {
+        set.push(match to.ceiling() {
+            Some(ceiling) => Comparator::new(Operator::Lt, ceiling),
+            None => Comparator::new(Operator::Le, to.floor()),
+        });
+    }
+    Some(Some(set))
+}
+
+// Helper: Desugar one simple range ("^1.2", "~1", ">1.2", "1.x", "=1.2.3") into comparators
+fn desugar(token: &str) -> Option<Vec<Comparator>> {
+    if let Some(rest) = token.strip_prefix('^') {
+        let partial = Partial::parse(rest)?;
+        let Some(major) = partial.major else {
+            return Some(Vec::new());
+        };
+        // Caret allows changes that do not modify the left-most non-zero component
+        let upper = match (major, partial.minor, partial.patch) {
+            (0, Some(0), Some(patch)) => Version::new(0, 0, patch + 1),
+            (0, Some(minor), _) => Version::new(0, minor + 1, 0),
+            (major, _, _) => Version::new(major + 1, 0, 0),
+        };
+        return Some(vec![
+            Comparator::new(Operator::Ge, partial.floor()),
+            Comparator::new(Operator::Lt, upper.with_zero_prerelease()),
+        ]);
+    }
+
+    if let Some(rest) = token.strip_prefix("~>").or_else(|| token.strip_prefix('~')) {
+        let partial = Partial::parse(rest)?;
+        let Some(major) = partial.major else {
+            return Some(Vec::new());
+        };
+        let upper = match partial.minor {
+            Some(minor) => Version::new(major, minor + 1, 0),
+            None => Version::new(major + 1, 0, 0),
+        };
+        return Some(vec![
+            Comparator::new(Operator::Ge, partial.floor()),
+            Comparator::new(Operator::Lt, upper.with_zero_prerelease()),
+        ]);
+    }
+
+    let (operator, rest) = [
+        (">=", Operator::Ge),
+        ("<=", Operator::Le),
+        (">", Operator::Gt),
+        ("<", Operator::Lt),
+        ("=", Operator::Eq),
+    ]
+    .into_iter()
+    .find_map(|(prefix, operator)| token.strip_prefix(prefix).map(|rest| (operator, rest)))
+    .unwrap_or((Operator::Eq, token));
+    let partial = Partial::parse(rest)?;
+    if partial.major.is_none() {
+        // "*", "x", ">=*", "<=x" match everything; "<x" and ">x" nothing
+        return Some(match operator {
+            Operator::Lt | Operator::Gt => vec![null_comparator()],
+            _ => Vec::new(),
+        });
+    }
+    let Some(ceiling) = partial.ceiling() else {
+        // Exact version
+        return Some(vec![Comparator::new(operator, partial.floor())]);
+    };
+    Some(match operator {
+        // "1.2" / "=1.2" -> >=1.2.0 <1.3.0-0
+        Operator::Eq => vec![
+            Comparator::new(Operator::Ge, partial.floor()),
+            Comparator::new(Operator::Lt, ceiling),
+        ],
+        // ">1.2" -> >=1.3.0, "<=1.2" -> <1.3.0-0
+        Operator::Gt => {
+            let mut next = ceiling;
+            next.prerelease.clear();
+            vec![Comparator::new(Operator::Ge, next)]
         }
+        Operator::Le => vec![Comparator::new(Operator::Lt, ceiling)],
+        // ">=1.2" -> >=1.2.0, "<1.2" -> <1.2.0-0
+        Operator::Ge => vec![Comparator::new(Operator::Ge, partial.floor())],
+        Operator::Lt => vec![Comparator::new(
+            Operator::Lt,
+            partial.floor().with_zero_prerelease(),
+        )],
+    })
+}
+
+// Function: is_dist_tag
+// Purpose: Check if a dependency specifier is an npm dist-tag ("latest", "next", "beta")
+// Args: spec - package.json dependency value
+// Returns: true for tag names (not ranges, URLs, paths, aliases or protocols)
+pub fn is_dist_tag(spec: &str) -> bool {
+    let spec = spec.trim();
+    spec.starts_with(|c: char| c.is_ascii_alphabetic())
+        && spec
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '.' | '_'))
+        && Range::parse(spec).is_none()
+}
+
+// Function: semver_match
+// Purpose: Check if an exact version could be installed for a dependency specifier
+// Args: test_subject (exact version, e.g. "1.2.3"), test_pattern (node-semver range or dist-tag)
+// Returns: true if the range admits the version; dist-tags ("latest") admit any version,
+//          since the tag may point at it when installing. Invalid input never matches.
+// Examples: "1.1.2" matches "^1.0.0", "~1.1.0", "1.x", ">=1.0.0 <2.0.0", "1 - 2", "latest"
+pub fn semver_match(test_subject: &str, test_pattern: &str) -> bool {
+    let Some(subject) = Version::parse(test_subject) else {
+        return false;
+    };
+    match Range::parse(test_pattern) {
+        Some(range) => range.satisfies(&subject),
+        None => is_dist_tag(test_pattern),
     }
+}
 
-    // Fallthrough = no match
-    false
+// Function: compare_versions
+// Purpose: Order two exact versions by SemVer 2.0.0 precedence (used for advisory ranges)
+// Args: a, b - exact versions like "1.2.3", "v1.2.3-beta.1" (build metadata is ignored)
+// Returns: Ordering, or None if either is not an exact version (e.g. "^1.0.0", "latest")
+pub fn compare_versions(a: &str, b: &str) -> Option<Ordering> {
+    Some(Version::parse(a)?.cmp(&Version::parse(b)?))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    // npm node-semver test/fixtures/range-include.js (non-loose, default options)
+    const RANGE_INCLUDE: &[(&str, &str)] = &[
+        ("1.0.0 - 2.0.0", "1.2.3"),
+        ("^1.2.3+build", "1.2.3"),
+        ("^1.2.3+build", "1.3.0"),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3"),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "1.2.3-pre.2"),
+        ("1.2.3-pre+asdf - 2.4.3-pre+asdf", "2.4.3-alpha"),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3"),
+        ("1.0.0", "1.0.0"),
+        (">=*", "0.2.4"),
+        ("", "1.0.0"),
+        ("*", "1.2.3"),
+        (">=1.0.0", "1.0.0"),
+        (">=1.0.0", "1.0.1"),
+        (">=1.0.0", "1.1.0"),
+        (">1.0.0", "1.0.1"),
+        (">1.0.0", "1.1.0"),
+        ("<=2.0.0", "2.0.0"),
+        ("<=2.0.0", "1.9999.9999"),
+        ("<=2.0.0", "0.2.9"),
+        ("<2.0.0", "1.9999.9999"),
+        ("<2.0.0", "0.2.9"),
+        (">= 1.0.0", "1.0.0"),
+        (">=  1.0.0", "1.0.1"),
+        (">=   1.0.0", "1.1.0"),
+        ("> 1.0.0", "1.0.1"),
+        (">  1.0.0", "1.1.0"),
+        ("<=   2.0.0", "2.0.0"),
+        ("<= 2.0.0", "1.9999.9999"),
+        ("<=  2.0.0", "0.2.9"),
+        ("<    2.0.0", "1.9999.9999"),
+        ("<\t2.0.0", "0.2.9"),
+        (">=0.1.97", "v0.1.97"),
+        (">=0.1.97", "0.1.97"),
+        ("0.1.20 || 1.2.4", "1.2.4"),
+        (">=0.2.3 || <0.0.1", "0.0.0"),
+        (">=0.2.3 || <0.0.1", "0.2.3"),
+        (">=0.2.3 || <0.0.1", "0.2.4"),
+        ("||", "1.3.4"),
+        ("2.x.x", "2.1.3"),
+        ("1.2.x", "1.2.3"),
+        ("1.2.x || 2.x", "2.1.3"),
+        ("1.2.x || 2.x", "1.2.3"),
+        ("x", "1.2.3"),
+        ("2.*.*", "2.1.3"),
+        ("1.2.*", "1.2.3"),
+        ("1.2.* || 2.*", "2.1.3"),
+        ("1.2.* || 2.*", "1.2.3"),
+        ("2", "2.1.2"),
+        ("2.3", "2.3.1"),
+        ("~0.0.1", "0.0.1"),
+        ("~0.0.1", "0.0.2"),
+        ("~x", "0.0.9"),
+        ("~2", "2.0.9"),
+        ("~2.4", "2.4.0"),
+        ("~2.4", "2.4.5"),
+        ("~>3.2.1", "3.2.2"),
+        ("~1", "1.2.3"),
+        ("~>1", "1.2.3"),
+        ("~> 1", "1.2.3"),
+        ("~1.0", "1.0.2"),
+        ("~ 1.0", "1.0.2"),
+        ("~ 1.0.3", "1.0.12"),
+        (">=1", "1.0.0"),
+        (">= 1", "1.0.0"),
+        ("<1.2", "1.1.1"),
+        ("< 1.2", "1.1.1"),
+        ("~v0.5.4-pre", "0.5.5"),
+        ("~v0.5.4-pre", "0.5.4"),
+        ("=0.7.x", "0.7.2"),
+        ("<=0.7.x", "0.7.2"),
+        (">=0.7.x", "0.7.2"),
+        ("<=0.7.x", "0.6.2"),
+        ("~1.2.1 >=1.2.3", "1.2.3"),
+        ("~1.2.1 =1.2.3", "1.2.3"),
+        ("~1.2.1 1.2.3", "1.2.3"),
+        ("~1.2.1 >=1.2.3 1.2.3", "1.2.3"),
+        ("~1.2.1 1.2.3 >=1.2.3", "1.2.3"),
+        (">=1.2.1 1.2.3", "1.2.3"),
+        ("1.2.3 >=1.2.1", "1.2.3"),
+        (">=1.2.3 >=1.2.1", "1.2.3"),
+        (">=1.2.1 >=1.2.3", "1.2.3"),
+        (">=1.2", "1.2.8"),
+        ("^1.2.3", "1.8.1"),
+        ("^0.1.2", "0.1.2"),
+        ("^0.1", "0.1.2"),
+        ("^0.0.1", "0.0.1"),
+        ("^1.2", "1.4.2"),
+        ("^1.2 ^1", "1.4.2"),
+        ("^1.2.3-alpha", "1.2.3-pre"),
+        ("^1.2.0-alpha", "1.2.0-pre"),
+        ("^0.0.1-alpha", "0.0.1-beta"),
+        ("^0.0.1-alpha", "0.0.1"),
+        ("^0.1.1-alpha", "0.1.1-beta"),
+        ("^x", "1.2.3"),
+        ("x - 1.0.0", "0.9.7"),
+        ("x - 1.x", "0.9.7"),
+        ("1.0.0 - x", "1.9.7"),
+        ("1.x - x", "1.9.7"),
+        ("<=7.x", "7.9.9"),
+    ];
+
+    // npm node-semver test/fixtures/range-exclude.js (non-loose, default options)
+    const RANGE_EXCLUDE: &[(&str, &str)] = &[
+        ("1.0.0 - 2.0.0", "2.2.3"),
+        ("1.2.3+asdf - 2.4.3+asdf", "1.2.3-pre.2"),
+        ("1.2.3+asdf - 2.4.3+asdf", "2.4.3-alpha"),
+        ("^1.2.3+build", "2.0.0"),
+        ("^1.2.3+build", "1.2.0"),
+        ("^1.2.3", "1.2.3-pre"),
+        ("^1.2", "1.2.0-pre"),
+        (">1.2", "1.3.0-beta"),
+        ("<=1.2.3", "1.2.3-beta"),
+        ("^1.2.3", "1.2.3-beta"),
+        ("=0.7.x", "0.7.0-asdf"),
+        (">=0.7.x", "0.7.0-asdf"),
+        ("<=0.7.x", "0.7.0-asdf"),
+        ("1.0.0", "1.0.1"),
+        (">=1.0.0", "0.0.0"),
+        (">=1.0.0", "0.0.1"),
+        (">=1.0.0", "0.1.0"),
+        (">1.0.0", "0.0.1"),
+        (">1.0.0", "0.1.0"),
+        ("<=2.0.0", "3.0.0"),
+        ("<=2.0.0", "2.9999.9999"),
+        ("<=2.0.0", "2.2.9"),
+        ("<2.0.0", "2.9999.9999"),
+        ("<2.0.0", "2.2.9"),
+        (">=0.1.97", "v0.1.93"),
+        (">=0.1.97", "0.1.93"),
+        ("0.1.20 || 1.2.4", "1.2.3"),
+        (">=0.2.3 || <0.0.1", "0.0.3"),
+        (">=0.2.3 || <0.0.1", "0.2.2"),
+        ("2.x.x", "1.1.3"),
+        ("2.x.x", "3.1.3"),
+        ("1.2.x", "1.3.3"),
+        ("1.2.x || 2.x", "3.1.3"),
+        ("1.2.x || 2.x", "1.1.3"),
+        ("2.*.*", "1.1.3"),
+        ("2.*.*", "3.1.3"),
+        ("1.2.*", "1.3.3"),
+        ("1.2.* || 2.*", "3.1.3"),
+        ("1.2.* || 2.*", "1.1.3"),
+        ("2", "1.1.2"),
+        ("2.3", "2.4.1"),
+        ("~0.0.1", "0.1.0-alpha"),
+        ("~0.0.1", "0.1.0"),
+        ("~2.4", "2.5.0"),
+        ("~2.4", "2.3.9"),
+        ("~>3.2.1", "3.3.2"),
+        ("~>3.2.1", "3.2.0"),
+        ("~1", "0.2.3"),
+        ("~>1", "2.2.3"),
+        ("~1.0", "1.1.0"),
+        ("<1", "1.0.0"),
+        (">=1.2", "1.1.1"),
+        ("~v0.5.4-beta", "0.5.4-alpha"),
+        ("=0.7.x", "0.8.2"),
+        (">=0.7.x", "0.6.2"),
+        ("<0.7.x", "0.7.2"),
+        ("<1.2.3", "1.2.3-beta"),
+        ("=1.2.3", "1.2.3-beta"),
+        (">1.2", "1.2.8"),
+        ("^0.0.1", "0.0.2-alpha"),
+        ("^0.0.1", "0.0.2"),
+        ("^1.2.3", "2.0.0-alpha"),
+        ("^1.2.3", "1.2.2"),
+        ("^1.2", "1.1.9"),
+        ("^1.0.0", "2.0.0-rc1"),
+        ("1 - 2", "2.0.0-pre"),
+        ("1 - 2", "1.0.0-pre"),
+        ("1.0 - 2", "1.0.0-pre"),
+        ("1.1.x", "1.0.0-a"),
+        ("1.1.x", "1.1.0-a"),
+        ("1.1.x", "1.2.0-a"),
+        ("1.x", "1.0.0-a"),
+        ("1.x", "1.1.0-a"),
+        ("1.x", "1.2.0-a"),
+        (">=1.0.0 <1.1.0", "1.1.0"),
+        (">=1.0.0 <1.1.0", "1.1.0-pre"),
+        (">=1.0.0 <1.1.0-pre", "1.1.0-pre"),
+        ("*", "1.0.0-rc1"),
+        ("^1.2.3", "not a version"),
+        (">=1.2.3", "1.2"),
+    ];
+
+    // npm node-semver test/fixtures/range-parse.js (desugared comparator sets)
+    const RANGE_PARSE: &[(&str, &str)] = &[
+        ("1.0.0 - 2.0.0", ">=1.0.0 <=2.0.0"),
+        ("1 - 2", ">=1.0.0 <3.0.0-0"),
+        ("1.0 - 2.0", ">=1.0.0 <2.1.0-0"),
+        ("1.0.0", "1.0.0"),
+        (">=*", "*"),
+        ("", "*"),
+        ("*", "*"),
+        (">=1.0.0", ">=1.0.0"),
+        (">1.0.0", ">1.0.0"),
+        ("<=2.0.0", "<=2.0.0"),
+        ("1", ">=1.0.0 <2.0.0-0"),
+        ("<2.0.0", "<2.0.0"),
+        (">= 1.0.0", ">=1.0.0"),
+        ("0.1.20 || 1.2.4", "0.1.20||1.2.4"),
+        (">=0.2.3 || <0.0.1", ">=0.2.3||<0.0.1"),
+        ("||", "*"),
+        ("2.x.x", ">=2.0.0 <3.0.0-0"),
+        ("1.2.x", ">=1.2.0 <1.3.0-0"),
+        ("x", "*"),
+        ("2.3", ">=2.3.0 <2.4.0-0"),
+        ("~2.4", ">=2.4.0 <2.5.0-0"),
+        ("~>3.2.1", ">=3.2.1 <3.3.0-0"),
+        ("~1", ">=1.0.0 <2.0.0-0"),
+        ("~1.0", ">=1.0.0 <1.1.0-0"),
+        ("^0", ">=0.0.0 <1.0.0-0"),
+        ("^0.1", ">=0.1.0 <0.2.0-0"),
+        ("^1.0", ">=1.0.0 <2.0.0-0"),
+        ("^1.2", ">=1.2.0 <2.0.0-0"),
+        ("^0.0.1", ">=0.0.1 <0.0.2-0"),
+        ("^0.0.1-beta", ">=0.0.1-beta <0.0.2-0"),
+        ("^0.1.2", ">=0.1.2 <0.2.0-0"),
+        ("^1.2.3", ">=1.2.3 <2.0.0-0"),
+        ("^1.2.3-beta.4", ">=1.2.3-beta.4 <2.0.0-0"),
+        ("<1", "<1.0.0-0"),
+        (">=1", ">=1.0.0"),
+        ("<1.2", "<1.2.0-0"),
+        (">01.02.03", "invalid"),
+        ("~1.2.3beta", "invalid"),
+        (">X", "<0.0.0-0"),
+        ("<X", "<0.0.0-0"),
+        ("<x <* || >* 2.x", "<0.0.0-0"),
+        (">x 2.x || * || <x", "*"),
+        (">1", ">=2.0.0"),
+        (">1.2", ">=1.3.0"),
+        ("<=1.2", "<1.3.0-0"),
+        ("^ 1.2.3", ">=1.2.3 <2.0.0-0"),
+        ("1.2 - 3.4.5", ">=1.2.0 <=3.4.5"),
+        ("1.2.3 - 3.4", ">=1.2.3 <3.5.0-0"),
+        ("1.2 - 3.4", ">=1.2.0 <3.5.0-0"),
+    ];
+
     #[test]
     fn test_semver_parse() {
-        let v = SemVer::parse("1.2.3").unwrap();
-        assert_eq!(v.major, 1);
-        assert_eq!(v.minor, 2);
-        assert_eq!(v.patch, 3);
-        assert_eq!(v.special, "");
+        let v = Version::parse("1.2.3").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+        assert!(v.prerelease.is_empty());
 
-        let v = SemVer::parse("1.2.3-beta").unwrap();
-        assert_eq!(v.major, 1);
-        assert_eq!(v.minor, 2);
-        assert_eq!(v.patch, 3);
-        assert_eq!(v.special, "-beta");
+        let v = Version::parse("1.2.3-beta").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+        assert_eq!(
+            v.prerelease,
+            vec![Identifier::AlphaNumeric("beta".to_string())]
+        );
 
-        let v = SemVer::parse("v2.0.0").unwrap();
-        assert_eq!(v.major, 2);
-        assert_eq!(v.minor, 0);
-        assert_eq!(v.patch, 0);
+        let v = Version::parse("v2.0.0").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (2, 0, 0));
     }
 
     #[test]
@@ -258,4 +962,104 @@ mod tests {
         // Test mixed case
         assert!(semver_match("2.3.4", "2.X.x"));
     }
+
+    #[test]
+    fn test_compare_versions() {
+        assert_eq!(compare_versions("1.2.3", "1.2.3"), Some(Ordering::Equal));
+        assert_eq!(compare_versions("1.10.0", "1.9.9"), Some(Ordering::Greater));
+        assert_eq!(
+            compare_versions("v2.0.0", "=2.0.0+build"),
+            Some(Ordering::Equal)
+        );
+        assert_eq!(
+            compare_versions("1.0.0-beta.2", "1.0.0-beta.11"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.0.0-rc.1", "1.0.0"),
+            Some(Ordering::Less)
+        );
+        assert_eq!(
+            compare_versions("1.0.0-alpha", "1.0.0-1"),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(compare_versions("^1.0.0", "1.0.0"), None);
+        assert_eq!(compare_versions("1.0", "1.0.0"), None);
+    }
+
+    #[test]
+    fn test_npm_range_include_vectors() {
+        for (range, version) in RANGE_INCLUDE {
+            assert!(
+                semver_match(version, range),
+                "{range:?} should include {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_npm_range_exclude_vectors() {
+        for (range, version) in RANGE_EXCLUDE {
+            assert!(
+                !semver_match(version, range),
+                "{range:?} should exclude {version}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_npm_range_parse_vectors() {
+        for (range, expected) in RANGE_PARSE {
+            let parsed = Range::parse(range).map_or("invalid".to_string(), |r| r.to_string());
+            assert_eq!(&parsed, expected, "range {range:?}");
+        }
+    }
+
+    #[test]
+    fn test_version_parse_and_precedence() {
+        let v = Version::parse("v1.2.3-beta.1+build.5").unwrap();
+        assert_eq!((v.major, v.minor, v.patch), (1, 2, 3));
+        assert_eq!(
+            v.prerelease,
+            vec![
+                Identifier::AlphaNumeric("beta".to_string()),
+                Identifier::Numeric(1)
+            ]
+        );
+        assert_eq!(v.build, vec!["build", "5"]);
+        assert_eq!(Version::parse("1.2"), None);
+        assert_eq!(Version::parse("01.2.3"), None);
+        assert_eq!(Version::parse("1.2.3-01"), None);
+
+        // SemVer 2.0.0 section 11 example order
+        let ordered = [
+            "1.0.0-alpha",
+            "1.0.0-alpha.1",
+            "1.0.0-alpha.beta",
+            "1.0.0-beta",
+            "1.0.0-beta.2",
+            "1.0.0-beta.11",
+            "1.0.0-rc.1",
+            "1.0.0",
+        ];
+        for pair in ordered.windows(2) {
+            assert_eq!(
+                compare_versions(pair[0], pair[1]),
+                Some(Ordering::Less),
+                "{pair:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_dist_tags() {
+        assert!(is_dist_tag("latest"));
+        assert!(is_dist_tag("next"));
+        assert!(!is_dist_tag("^1.0.0"));
+        assert!(!is_dist_tag("x"));
+        assert!(!is_dist_tag("github:user/repo"));
+        assert!(!is_dist_tag("file:../lib"));
+        assert!(semver_match("4.1.1", "latest"));
+        assert!(!semver_match("4.1.1", "github:user/repo"));
+    }
 }
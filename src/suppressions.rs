@@ -0,0 +1,366 @@
+// Suppressions - Reviewed, auditable exceptions from .shai-hulud-ignore.toml
+// Purpose: Move accepted findings out of the risk counts into a `suppressed` section
+//
+// Every entry matches on finding category (as in scan_results.json), path glob (relative to the scanned directory)
+// and/or message regex, and must carry a reason and an owner. Entries with an
+// expiry date stop suppressing after that date, so the finding becomes active again.
+//
+//   [[suppress]]
+//   category = "crypto_xhr_hijack"
+//   path = "vendor/**/*.js"
+//   message = "XMLHttpRequest prototype"
+//   reason = "Vendored polyfill, reviewed in SEC-123"
+//   owner = "security@example.com"
+//   expires = 2026-06-30
+
+use crate::detectors::{Finding, ScanResults};
+use crate::utils::relative_path;
+use anyhow::{bail, Context, Result};
+use chrono::NaiveDate;
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+/// Default suppression file, looked up in the scanned directory
+pub const DEFAULT_IGNORE_FILE: &str = ".shai-hulud-ignore.toml";
+
+/// A finding moved out of the risk counts by a suppression entry
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SuppressedFinding {
+    #[serde(flatten)]
+    pub finding: Finding,
+    pub reason: String,
+    pub owner: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub expires: Option<String>,
+}
+
+/// Raw [[suppress]] entry as written in the TOML file
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RawSuppression {
+    category: Option<String>,
+    path: Option<String>,
+    message: Option<String>,
+    reason: String,
+    owner: String,
+    // TOML date (expires = 2026-06-30) or string (expires = "2026-06-30")
+    expires: Option<toml::Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct SuppressionFile {
+    #[serde(default)]
+    suppress: Vec<RawSuppression>,
+}
+
+/// A validated suppression entry
+#[derive(Debug, Clone)]
+pub struct Suppression {
+    pub category: Option<String>,
+    pub path: Option<GlobMatcher>,
+    pub message: Option<Regex>,
+    pub reason: String,
+    pub owner: String,
+    pub expires: Option<NaiveDate>,
+}
+
+impl Suppression {
+    // Function: matches
+    // Purpose: Check if a finding matches every matcher of this entry
+    // Args: finding - finding to check, relative - finding path relative to scan_dir
+    // Returns: true if category, path glob and message regex all match
+    fn matches(&self, finding: &Finding, relative: &str) -> bool {
+        self.category
+            .as_ref()
+            .is_none_or(|category| *category == finding.category)
+            && self
+                .path
+                .as_ref()
+                .is_none_or(|glob| glob.is_match(relative))
+            && self
+                .message
+                .as_ref()
+                .is_none_or(|regex| regex.is_match(&finding.message))
+    }
+
+    /// Expired entries (today is after the expiry date) no longer suppress
+    pub fn is_expired(&self, today: NaiveDate) -> bool {
+        self.expires.is_some_and(|expires| today > expires)
+    }
+}
+
+/// An expired suppression entry that matched active findings
+#[derive(Debug, Clone)]
+pub struct ExpiredSuppression {
+    pub suppression: Suppression,
+    pub reactivated: usize,
+}
+
+/// All entries from a suppression file
+#[derive(Debug, Clone, Default)]
+pub struct Suppressions {
+    entries: Vec<Suppression>,
+}
+
+impl Suppressions {
+    // Function: load
+    // Purpose: Load and validate a suppression file
+    // Args: path - .shai-hulud-ignore.toml
+    // Returns: Suppressions or error naming the invalid entry
+    pub fn load(path: &Path) -> Result<Self> {
+        let content = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read suppression file {}", path.display()))?;
+        Self::parse(&content)
+            .with_context(|| format!("Invalid suppression file {}", path.display()))
+    }
+
+    // Function: parse
+    // Purpose: Parse and validate suppression file content
+    // Args: content - TOML with [[suppress]] entries
+    // Returns: Suppressions or error naming the invalid entry
+    pub fn parse(content: &str) -> Result<Self> {
+        let file: SuppressionFile = toml::from_str(content)?;
+        let entries = file
+            .suppress
+            .into_iter()
+            .enumerate()
+            .map(|(i, raw)| {
+                Self::validate(raw).with_context(|| format!("[[suppress]] entry #{}", i + 1))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self { entries })
+    }
+
+    fn validate(raw: RawSuppression) -> Result<Suppression> {
+        if raw.category.is_none() && raw.path.is_none() && raw.message.is_none() {
+            bail!("needs at least one of category, path or message");
+        }
+        if raw.reason.trim().is_empty() {
+            bail!("reason must not be empty");
+        }
+        if raw.owner.trim().is_empty() {
+            bail!("owner must not be empty");
+        }
+
+        let path = raw
+            .path
+            .map(|glob| {
+                GlobBuilder::new(&glob)
+                    .literal_separator(true)
+                    .build()
+                    .map(|g| g.compile_matcher())
+                    .with_context(|| format!("invalid path glob '{glob}'"))
+            })
+            .transpose()?;
+        let message = raw
+            .message
+            .map(|pattern| {
+                Regex::new(&pattern).with_context(|| format!("invalid message regex '{pattern}'"))
+            })
+            .transpose()?;
+        let expires = raw
+            .expires
+            .map(|value| {
+                let date = match &value {
+                    toml::Value::String(s) => s.clone(),
+                    toml::Value::Datetime(dt) => dt.to_string(),
+                    other => bail!("invalid expires value {other}"),
+                };
+                NaiveDate::parse_from_str(&date, "%Y-%m-%d")
+                    .with_context(|| format!("invalid expires date '{date}' (use YYYY-MM-DD)"))
+            })
+            .transpose()?;
+
+        Ok(Suppression {
+            category: raw.category,
+            path,
+            message,
+            reason: raw.reason,
+            owner: raw.owner,
+            expires,
+        })
+    }
+
+    pub fn entries(&self) -> &[Suppression] {
+        &self.entries
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    // Function: apply
+    // Purpose: Move findings matched by an active entry into results.suppressed
+    // Args: results - scan results (modified in place), scan_dir - scanned directory,
+    //       today - date used to decide which entries are expired
+    // Returns: Expired entries that matched findings (those findings stay active)
+    pub fn apply(
+        &self,
+        results: &mut ScanResults,
+        scan_dir: &Path,
+        today: NaiveDate,
+    ) -> Vec<ExpiredSuppression> {
+        let mut reactivated = vec![0usize; self.entries.len()];
+        let mut suppressed_by = Vec::new();
+
+        let removed = results.retain_findings(|finding| {
+            let relative = relative_path(&finding.file_path, scan_dir);
+            let matching = self
+                .entries
+                .iter()
+                .enumerate()
+                .filter(|(_, entry)| entry.matches(finding, &relative));
+
+            let mut first_expired = None;
+            for (i, entry) in matching {
+                if !entry.is_expired(today) {
+                    suppressed_by.push(i);
+                    return false;
+                }
+                first_expired.get_or_insert(i);
+            }
+            if let Some(i) = first_expired {
+                reactivated[i] += 1;
+            }
+            true
+        });
+
+        for (finding, i) in removed.into_iter().zip(suppressed_by) {
+            let entry = &self.entries[i];
+            results.suppressed.push(SuppressedFinding {
+                finding,
+                reason: entry.reason.clone(),
+                owner: entry.owner.clone(),
+                expires: entry.expires.map(|d| d.format("%Y-%m-%d").to_string()),
+            });
+        }
+
+        self.entries
+            .iter()
+            .zip(reactivated)
+            .filter(|(_, count)| *count > 0)
+            .map(|(entry, count)| ExpiredSuppression {
+                suppression: entry.clone(),
+                reactivated: count,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::detectors::RiskLevel;
+    use std::path::PathBuf;
+
+    fn finding(path: &str, message: &str, category: &str) -> Finding {
+        Finding::new(
+            PathBuf::from(path),
+            message.to_string(),
+            RiskLevel::Medium,
+            category,
+        )
+    }
+
+    fn date(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_rejects_incomplete_entries() {
+        let missing_owner = r#"
+            [[suppress]]
+            category = "crypto_patterns"
+            reason = "reviewed"
+        "#;
+        assert!(Suppressions::parse(missing_owner).is_err());
+
+        let no_matcher = r#"
+            [[suppress]]
+            reason = "reviewed"
+            owner = "sec"
+        "#;
+        assert!(Suppressions::parse(no_matcher).is_err());
+
+        let bad_regex = r#"
+            [[suppress]]
+            message = "("
+            reason = "reviewed"
+            owner = "sec"
+        "#;
+        assert!(Suppressions::parse(bad_regex).is_err());
+    }
+
+    #[test]
+    fn test_apply_moves_matching_findings_to_suppressed() {
+        let suppressions = Suppressions::parse(
+            r#"
+            [[suppress]]
+            category = "crypto_xhr_hijack"
+            path = "vendor/**/*.js"
+            message = "XMLHttpRequest"
+            reason = "Vendored polyfill"
+            owner = "security-team"
+            expires = 2030-01-01
+            "#,
+        )
+        .unwrap();
+
+        let mut results = ScanResults::new();
+        results.crypto_patterns.push(finding(
+            "/repo/vendor/lib/poly.js",
+            "XMLHttpRequest prototype modification",
+            "crypto_xhr_hijack",
+        ));
+        results.crypto_patterns.push(finding(
+            "/repo/src/app.js",
+            "XMLHttpRequest prototype modification",
+            "crypto_xhr_hijack",
+        ));
+
+        let expired = suppressions.apply(&mut results, Path::new("/repo"), date("2026-01-01"));
+
+        assert!(expired.is_empty());
+        assert_eq!(results.crypto_patterns.len(), 1);
+        assert_eq!(results.suppressed.len(), 1);
+        assert_eq!(results.suppressed[0].owner, "security-team");
+        assert_eq!(results.suppressed[0].expires.as_deref(), Some("2030-01-01"));
+        assert_eq!(results.medium_risk_count(false), 1);
+    }
+
+    #[test]
+    fn test_expired_suppression_reactivates_finding() {
+        let suppressions = Suppressions::parse(
+            r#"
+            [[suppress]]
+            category = "postinstall_hook"
+            reason = "Accepted until migration"
+            owner = "platform"
+            expires = "2025-12-31"
+            "#,
+        )
+        .unwrap();
+
+        let mut results = ScanResults::new();
+        results.postinstall_hooks.push(finding(
+            "/repo/package.json",
+            "Suspicious postinstall: curl",
+            "postinstall_hook",
+        ));
+
+        let expired = suppressions.apply(&mut results, Path::new("/repo"), date("2026-01-01"));
+
+        assert_eq!(expired.len(), 1);
+        assert_eq!(expired[0].reactivated, 1);
+        assert_eq!(results.postinstall_hooks.len(), 1);
+        assert!(results.suppressed.is_empty());
+    }
+}
@@ -1,8 +1,7 @@
 // Utility functions
-// Corresponds to bash utility functions like show_progress, count_files, etc.
+// Corresponds to bash utility functions like show_progress, normalize_path, etc.
 
 use std::path::Path;
-use walkdir::WalkDir;
 
 // Function: normalize_path
 // Purpose: Convert Windows backslashes to forward slashes for bash-identical output
@@ -28,37 +27,17 @@ pub fn normalize_path(path: &Path) -> String {
     }
 }
 
-// Function: count_files
-// Purpose: Count files matching find criteria, returns clean integer
-// Args: path - directory to search, extensions - file extensions to match
-// Modifies: None
-// Returns: Integer count of matching files
-pub fn count_files<P: AsRef<Path>>(path: P, extensions: &[&str]) -> usize {
-    WalkDir::new(path)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| {
-            e.path()
-                .extension()
-                .and_then(|ext| ext.to_str())
-                .is_some_and(|ext| extensions.contains(&ext))
-        })
-        .count()
-}
-
-// Function: count_files_by_name
-// Purpose: Count files with specific filename
-// Args: path - directory to search, filename - exact filename to match
-// Modifies: None
-// Returns: Integer count of matching files
-pub fn count_files_by_name<P: AsRef<Path>>(path: P, filename: &str) -> usize {
-    WalkDir::new(path)
-        .into_iter()
-        .filter_map(std::result::Result::ok)
-        .filter(|e| e.file_type().is_file())
-        .filter(|e| e.file_name() == filename)
-        .count()
+// Function: relative_path
+// Purpose: Normalize a path and make it relative to the scanned directory
+// Args: path - file path (absolute or already relative), scan_dir - scanned directory
+// Returns: Forward-slash path relative to scan_dir when path is inside it
+pub fn relative_path(path: &Path, scan_dir: &Path) -> String {
+    let path_str = normalize_path(path);
+    let root = normalize_path(scan_dir);
+    let root_prefix = format!("{}/", root.trim_end_matches('/'));
+    path_str
+        .strip_prefix(&root_prefix)
+        .map_or(path_str.clone(), std::string::ToString::to_string)
 }
 
 // Function: show_progress
@@ -67,11 +46,10 @@ pub fn count_files_by_name<P: AsRef<Path>>(path: P, filename: &str) -> usize {
 // Modifies: None (outputs to stderr with ANSI escape codes)
 // Returns: Prints "X / Y checked (Z %)" with line clearing
 pub fn show_progress(current: usize, total: usize) {
-    let percent = if total > 0 {
-        (current * 100) / total
-    } else {
-        0
-    };
+    if crate::colors::quiet() {
+        return;
+    }
+    let percent = (current * 100).checked_div(total).unwrap_or(0);
     eprint!("\r\x1b[K{current} / {total} checked ({percent} %)");
 }
 
@@ -81,6 +59,9 @@ pub fn show_progress(current: usize, total: usize) {
 // Modifies: None (outputs to stderr)
 // Returns: Clears current line
 pub fn clear_progress() {
+    if crate::colors::quiet() {
+        return;
+    }
     eprint!("\r\x1b[K");
 }
 
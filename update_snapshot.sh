@@ -0,0 +1,36 @@
+#!/bin/bash
+# Refresh the compromised-packages snapshot embedded into the binary at build time
+# (data/compromised-packages.txt, used when no feed, cache or local file is available)
+set -euo pipefail
+
+URL="https://raw.githubusercontent.com/Cobenian/shai-hulud-detect/main/compromised-packages.txt"
+SNAPSHOT="data/compromised-packages.txt"
+# The upstream list holds 600+ packages - fewer means a truncated or wrong download
+# (keep in sync with MIN_SNAPSHOT_ENTRIES in build.rs)
+MIN_ENTRIES=500
+TMP=$(mktemp)
+trap 'rm -f "$TMP"' EXIT
+
+echo "📡 Downloading $URL..."
+curl -fsSL "$URL" -o "$TMP"
+
+ENTRIES=$(grep -cvE '^\s*(#|$)' "$TMP" || true)
+if [ "$ENTRIES" -lt "$MIN_ENTRIES" ]; then
+    echo "❌ Downloaded feed has only $ENTRIES entries (expected at least $MIN_ENTRIES) - keeping $SNAPSHOT"
+    exit 1
+fi
+
+{
+    echo "# Shai-Hulud compromised packages - embedded snapshot"
+    echo "# Last-resort fallback compiled into the binary when neither the upstream feed,"
+    echo "# the feed cache nor a local compromised-packages.txt is available."
+    echo "#"
+    echo "# Snapshot-Date: $(date -u +%Y-%m-%d)"
+    echo "# Source: $URL"
+    echo "#"
+    echo "# Refresh with ./update_snapshot.sh (release.sh runs it before building) so"
+    echo "# offline scans keep the full upstream coverage. Format: package:version per line."
+    grep -vE '^\s*(#|$)' "$TMP"
+} > "$SNAPSHOT"
+
+echo "✅ $SNAPSHOT: $ENTRIES packages, sha256 $(sha256sum "$SNAPSHOT" | cut -c1-12)"